@@ -0,0 +1,284 @@
+## Common bot messages
+
+error-generic = ❌ Error: { $error }
+unknown-command = ❌ Unknown command. Type /help for the list of commands.
+button-outdated = ❌ This button is outdated or unknown
+dialog-start-failed = ❌ Could not start the dialog: { $error }
+work-not-found-id = ❌ Work with ID { $id } was not found.
+file-unavailable = ⚠️ The work file is temporarily unavailable
+button-details = 📄 Details { $n }
+button-download = ⬇️ Download
+button-open-app = 📱 Open
+
+## /start and /help
+
+start-text =
+    👋 Welcome to the Digital Archive of GPOU YuTK named after G.A. Pavlyuchkov!
+
+    📚 Here you can find competition works and papers by the college's students and teachers.
+
+    🔎 Available commands:
+    /search <query> — search works
+    /browse — browse works by specialty, year and type
+    /work <ID> — show a work by its ID
+    /submit — submit your work to the archive
+    /subscribe — get notified about new works
+    /help — help
+start-mini-app-hint = 💡 Tap «Open» below to search comfortably in the mini app!
+help-title = 📖 Commands:
+help-reviewers = For teachers and methodists:
+help-mini-app-hint = 💡 Tip: tap «Open» below to launch the mini app with the full set of features for searching and viewing works.
+
+## Bot commands: descriptions for help and the menu
+
+command-start = welcome and main menu
+command-search = search by title, author or keywords
+command-search-args = <query>
+command-browse = step by step: specialty → year → work type
+command-work = show a work by its ID
+command-work-args = <ID>
+command-submit = submit your work to the archive (with a file)
+command-my = my submitted works and their statuses
+command-subscribe = get notified about new works
+command-subscribe-args = <specialty|tag|supervisor>
+command-subscriptions = my subscriptions
+command-unsubscribe = unsubscribe
+command-cancel = cancel the current dialog
+command-link = link your archive account
+command-link-args = [code]
+command-unlink = unlink your archive account
+command-whoami = show your archive account
+command-filter = default specialty for a group chat
+command-filter-args = [specialty|off]
+command-help = this help
+command-pending = works awaiting review
+command-approve = publish a work
+command-approve-args = <ID>
+command-reject = reject a work
+command-reject-args = <ID>
+
+## Search
+
+search-empty-query =
+    🔍 Please enter a search query.
+    Example: /search web development
+search-error = ❌ Search failed: { $error }
+search-nothing = 🔍 Nothing found { $filter }.
+search-nothing-more = 🔍 Nothing more found { $filter }.
+search-results = 🔍 Search results { $filter }:
+search-next = ➡️ Next
+filter-query = for "{ $query }"
+filter-specialty = specialty { $specialty }
+filter-year = year { $year }
+filter-all = across all works
+
+## Work card
+
+work-id-missing =
+    📄 Please specify the work ID.
+    Example: /work 123e4567-e89b-12d3-a456-426614174000
+work-id-invalid =
+    ❌ Invalid ID format. A UUID is expected.
+    Example: /work 123e4567-e89b-12d3-a456-426614174000
+work-load-error = ❌ Could not load the work: { $error }
+work-field-title = Title
+work-field-type = Type
+work-field-specialty = Specialty
+work-field-author = Author
+work-field-supervisor = Supervisor
+work-field-year = Year
+work-field-annotation = Abstract
+work-field-keywords = Keywords
+work-field-download = Download
+work-field-file = File
+work-file-attached = 📎 <b>The work file</b> is attached
+
+work-type-article = Article
+work-type-competition = Competition work
+work-type-essay = Essay
+work-type-report = Report
+work-type-project = Project
+work-type-presentation = Presentation
+work-type-speech = Speech
+work-type-other = Other
+
+work-status-draft = Draft
+work-status-submitted = Under review
+work-status-published = Published
+work-status-rejected = Rejected
+
+## Buttons
+
+callback-opening-work = 📄 Opening the work…
+callback-sending-file = ⬇️ Sending the file…
+callback-work-not-found = ❌ Work not found
+callback-done = 📝 Done
+
+## Dialogs
+
+dialog-cancelled = ✖️ Dialog cancelled.
+dialog-none = ℹ️ There is no active dialog.
+dialog-finished = ℹ️ The dialog is already over. Type /help for the list of commands.
+dialog-expired = ⌛ The reply timed out and the dialog was closed. Please start again.
+dialog-broken = ❌ The dialog was interrupted by an error. Please start again.
+dialog-cancel-button = ✖️ Cancel
+dialog-any-specialty = Any
+dialog-any = Any
+
+browse-specialty = 🎓 <b>Step 1 of 3.</b> Choose a specialty or type its code:
+browse-year = 📅 <b>Step 2 of 3.</b> Choose a year:
+browse-year-invalid = 📅 Enter the year as a number, e.g. 2024, or «any».
+browse-work-type = 📌 <b>Step 3 of 3.</b> Choose the work type:
+browse-work-type-invalid = 📌 Choose the work type with a button below or type «any».
+
+submit-start =
+    📤 <b>Submitting a work to the archive</b>
+    Author: { $author }
+
+    ✏️ <b>Step 1 of 6.</b> Enter the title of the work:
+submit-work-type = 📌 <b>Step 2 of 6.</b> Choose the work type:
+submit-specialty = 🎓 <b>Step 3 of 6.</b> Choose a specialty or type it, e.g. «09.02.07 Information systems and programming»:
+submit-year = 📅 <b>Step 4 of 6.</b> Enter the year the work was completed:
+submit-supervisor = 👨‍🏫 <b>Step 5 of 6.</b> Enter the supervisor's full name:
+submit-file =
+    📎 <b>Step 6 of 6.</b> Attach the work file as a document.
+    Formats: { $formats }. Size — up to { $size } MB.
+submit-value-empty = The value cannot be empty
+submit-value-too-long = The value is too long
+submit-year-invalid = The year must be a number between 1900 and 2100
+submit-file-format = Unsupported file format. Allowed: { $formats }
+submit-file-size = The file is larger than { $size } MB
+submit-file-size-unknown = Could not determine the file size — send it as a document
+submit-retry = ⚠️ { $error }. Try again or tap «Cancel».
+submit-confirm-title = 📋 <b>Check the work details:</b>
+submit-send-button = 📨 Send for review
+submit-draft-button = 📝 Save as draft
+submit-incomplete = ❌ Some work details are missing. Start again: /submit
+submit-download-failed = ❌ Could not download the file from MAX. Please submit the work again: /submit
+submit-too-large = ❌ The file is larger than { $size } MB.
+submit-store-failed = ❌ Could not save the file. Please try again later.
+submit-saved =
+    ✅ The work has been saved!
+
+    🆔 <b>ID:</b> { $id }
+    📊 <b>Status:</b> { $status }
+submit-save-failed = ❌ Could not save the work: { $error }
+
+## Reviewing works
+
+review-forbidden = ⛔ Only teachers and methodists can review works.
+review-link-first = ℹ️ Link your archive account first: /link
+my-works-empty = 📭 You have not submitted any works yet. Submit one: /submit
+my-works-title = 🗂 <b>My works:</b>
+pending-empty = ✅ No works are awaiting review.
+pending-title = 📥 <b>Works awaiting review:</b>
+pending-supervisor = sup. { $name }
+review-id-invalid = ❌ Invalid ID format. Example: /approve 123e4567-e89b-12d3-a456-426614174000
+review-not-pending = ℹ️ The work «{ $title }» is not awaiting review (status: { $status }).
+review-published = ✅ The work «{ $title }» has been published.
+review-rejected = ❌ The work «{ $title }» has been rejected.
+
+## Account linking
+
+role-admin = Administrator
+role-methodist = Methodist
+role-teacher = Teacher
+role-student = Student
+
+link-code =
+    🔗 Your link code: <b>{ $code }</b>
+
+    Enter it in your profile in the archive web app within 10 minutes.
+    If the website has already given you a code, send it to the bot: /link <code>
+link-code-failed = ❌ Could not issue a code: { $error }
+link-done = ✅ Your MAX account is now linked to { $name } ({ $role }).
+link-invalid = ❌ The code is invalid or expired. Get a new code in the web app.
+link-failed = ❌ Linking failed: { $error }
+unlink-done = ✖️ Your MAX account has been unlinked from the archive.
+unlink-none = ℹ️ Your MAX account is not linked.
+link-private-only = 🔒 Account linking is only available in a private chat with the bot — message me directly.
+whoami =
+    👤 { $name }
+    Login: { $login }
+    Role: { $role }
+whoami-none = ℹ️ Your MAX account is not linked to the archive. Link it: /link
+
+## Subscriptions
+
+subscriptions-usage =
+    🔔 <b>Subscribing to new works</b>
+
+    /subscribe specialty 09.02.07 — by specialty
+    /subscribe tag machine learning — by keyword
+    /subscribe supervisor Ivanova — by supervisor
+
+    Short form: /subscribe 09.02.07 or /subscribe #python
+subscription-kind-specialty = 🎓 Specialty
+subscription-kind-tag = 🔑 Keyword
+subscription-kind-supervisor = 👨‍🏫 Supervisor
+subscription-item = { $kind } «{ $value }»
+subscribe-done =
+    ✅ Subscribed: { $subscription }
+
+    I will post new works to this chat as they appear.
+    All subscriptions: /subscriptions
+subscribe-exists = ℹ️ This subscription already exists. All subscriptions: /subscriptions
+subscribe-failed = ❌ { $error }
+subscriptions-empty = 📭 No subscriptions.
+subscriptions-title = 🔔 <b>Chat subscriptions:</b>
+subscriptions-unsubscribe-button = ✖️ Unsubscribe { $n }
+subscriptions-unsubscribe-all = Unsubscribe from everything: /unsubscribe all
+unsubscribe-none = ℹ️ No subscriptions.
+unsubscribe-all-done = ✅ Subscriptions removed: { $count }
+unsubscribe-not-found = ❌ Subscription not found. Your subscriptions: /subscriptions
+unsubscribe-done = ✅ Unsubscribed: { $subscription }
+unsubscribe-already = ℹ️ The subscription has already been removed.
+
+## Group chats
+
+chat-filter-group-only = ℹ️ A default specialty can only be set in group chats.
+chat-filter-current =
+    🎓 Search in this chat is limited to the specialty «{ $specialty }».
+    Reset: /filter off
+chat-filter-none =
+    🎓 No default specialty is set.
+    Chat admins can set one: /filter 09.02.07
+chat-filter-admins-only = ⛔ Only chat admins can change chat settings.
+chat-filter-set = ✅ /search and /browse in this chat now look in the specialty «{ $specialty }».
+chat-filter-cleared = ✖️ The default specialty has been reset.
+
+## Digest of new works
+
+digest-title = 🔔 <b>New works matching your subscriptions:</b>
+digest-more = …and { $count } more. Find them with /browse
+digest-manage = Manage subscriptions: /subscriptions
+
+## API errors
+
+error-internal = Internal server error
+error-unauthorized = Authentication required
+error-forbidden = Access denied
+error-not-found = Resource not found
+error-database = Database error
+error-validation = Some request fields are invalid
+error-payload-too-large = The request is too large: up to { $limit } MB is allowed
+error-too-many-requests = Too many requests, please try again later
+error-unsupported-media-type = Unsupported content type: { $content_type }
+error-service-unavailable = The service is temporarily unavailable, please try again later
+error-link-code-invalid = The link code is invalid or expired
+error-username-taken = Username { $username } is already taken
+error-search-not-implemented = Search is not implemented yet
+
+validation-specialty-empty = The specialty cannot be empty
+validation-blank = The value cannot be empty
+validation-length-max = At most { $max } characters
+validation-length-min = At least { $min } characters
+validation-length-range = Between { $min } and { $max } characters
+validation-range = The value must be between { $min } and { $max }
+validation-work-type = Unknown work type
+validation-invalid = Invalid value
+validation-json = Invalid request body: { $error }
+validation-chat-specialty-too-long = The specialty name is too long
+validation-subscription-empty = The subscription value cannot be empty
+validation-subscription-too-long = The subscription value is too long
+validation-subscription-limit = This chat already has { $count } subscriptions — remove the ones you no longer need
@@ -0,0 +1,284 @@
+## Общие сообщения бота
+
+error-generic = ❌ Ошибка: { $error }
+unknown-command = ❌ Неизвестная команда. Введите /help для справки.
+button-outdated = ❌ Кнопка устарела или неизвестна
+dialog-start-failed = ❌ Не удалось начать диалог: { $error }
+work-not-found-id = ❌ Работа с ID { $id } не найдена.
+file-unavailable = ⚠️ Файл работы временно недоступен
+button-details = 📄 Подробнее { $n }
+button-download = ⬇️ Скачать
+button-open-app = 📱 Открыть
+
+## /start и /help
+
+start-text =
+    👋 Добро пожаловать в Цифровой архив ГПОУ ЮТК им. Павлючкова Г.А.!
+
+    📚 Здесь вы можете найти конкурсные работы и статьи обучающихся и преподавателей колледжа.
+
+    🔎 Доступные команды:
+    /search <запрос> — поиск работ
+    /browse — подбор работ по специальности, году и типу
+    /work <ID> — просмотр работы по ID
+    /submit — отправить свою работу в архив
+    /subscribe — подписка на новые работы
+    /help — справка
+start-mini-app-hint = 💡 Нажмите кнопку «Открыть» ниже для удобного поиска в мини-приложении!
+help-title = 📖 Справка по командам:
+help-reviewers = Для преподавателей и методистов:
+help-mini-app-hint = 💡 Совет: для удобного поиска и просмотра работ нажмите кнопку «Открыть» ниже — откроется мини-приложение с полным функционалом.
+
+## Команды бота: описание для справки и меню
+
+command-start = приветствие и основное меню
+command-search = поиск по названию, автору, ключевым словам
+command-search-args = <запрос>
+command-browse = пошаговый подбор: специальность → год → тип работы
+command-work = просмотр работы по уникальному идентификатору
+command-work-args = <ID>
+command-submit = отправить работу в архив (с файлом)
+command-my = мои отправленные работы и их статусы
+command-subscribe = уведомления о новых работах
+command-subscribe-args = <специальность|тег|руководитель>
+command-subscriptions = мои подписки
+command-unsubscribe = отписаться
+command-cancel = прервать текущий диалог
+command-link = привязать аккаунт архива
+command-link-args = [код]
+command-unlink = отвязать аккаунт архива
+command-whoami = кто я в архиве
+command-filter = специальность по умолчанию для группового чата
+command-filter-args = [специальность|off]
+command-help = эта справка
+command-pending = работы на проверке
+command-approve = опубликовать работу
+command-approve-args = <ID>
+command-reject = отклонить работу
+command-reject-args = <ID>
+
+## Поиск
+
+search-empty-query =
+    🔍 Укажите критерии поиска.
+    Пример: /search веб-разработка
+search-error = ❌ Ошибка при поиске: { $error }
+search-nothing = 🔍 Ничего не найдено { $filter }.
+search-nothing-more = 🔍 Больше ничего не найдено { $filter }.
+search-results = 🔍 Результаты поиска { $filter }:
+search-next = ➡️ Далее
+filter-query = по запросу "{ $query }"
+filter-specialty = специальность { $specialty }
+filter-year = { $year } год
+filter-all = по всем работам
+
+## Карточка работы
+
+work-id-missing =
+    📄 Укажите ID работы.
+    Пример: /work 123e4567-e89b-12d3-a456-426614174000
+work-id-invalid =
+    ❌ Неверный формат ID. Ожидается UUID.
+    Пример: /work 123e4567-e89b-12d3-a456-426614174000
+work-load-error = ❌ Ошибка при получении работы: { $error }
+work-field-title = Название
+work-field-type = Тип
+work-field-specialty = Специальность
+work-field-author = Автор
+work-field-supervisor = Руководитель
+work-field-year = Год
+work-field-annotation = Аннотация
+work-field-keywords = Ключевые слова
+work-field-download = Скачать
+work-field-file = Файл
+work-file-attached = 📎 <b>Файл работы</b> — во вложении
+
+work-type-article = Статья
+work-type-competition = Конкурсная работа
+work-type-essay = Эссе
+work-type-report = Доклад
+work-type-project = Проект
+work-type-presentation = Презентация
+work-type-speech = Выступление
+work-type-other = Другое
+
+work-status-draft = Черновик
+work-status-submitted = На проверке
+work-status-published = Опубликована
+work-status-rejected = Отклонена
+
+## Кнопки
+
+callback-opening-work = 📄 Открываю работу…
+callback-sending-file = ⬇️ Отправляю файл…
+callback-work-not-found = ❌ Работа не найдена
+callback-done = 📝 Готово
+
+## Диалоги
+
+dialog-cancelled = ✖️ Диалог отменён.
+dialog-none = ℹ️ Нет активного диалога.
+dialog-finished = ℹ️ Диалог уже завершён. Введите /help для списка команд.
+dialog-expired = ⌛ Время ожидания ответа истекло, диалог завершён. Начните заново.
+dialog-broken = ❌ Диалог прерван из-за ошибки. Начните заново.
+dialog-cancel-button = ✖️ Отмена
+dialog-any-specialty = Любая
+dialog-any = Любой
+
+browse-specialty = 🎓 <b>Шаг 1 из 3.</b> Выберите специальность или напишите её код:
+browse-year = 📅 <b>Шаг 2 из 3.</b> Выберите год:
+browse-year-invalid = 📅 Введите год числом, например 2024, или «любой».
+browse-work-type = 📌 <b>Шаг 3 из 3.</b> Выберите тип работы:
+browse-work-type-invalid = 📌 Выберите тип работы кнопкой ниже или напишите «любой».
+
+submit-start =
+    📤 <b>Отправка работы в архив</b>
+    Автор: { $author }
+
+    ✏️ <b>Шаг 1 из 6.</b> Введите название работы:
+submit-work-type = 📌 <b>Шаг 2 из 6.</b> Выберите тип работы:
+submit-specialty = 🎓 <b>Шаг 3 из 6.</b> Выберите специальность или напишите её, например «09.02.07 Информационные системы и программирование»:
+submit-year = 📅 <b>Шаг 4 из 6.</b> Введите год выполнения работы:
+submit-supervisor = 👨‍🏫 <b>Шаг 5 из 6.</b> Введите ФИО руководителя:
+submit-file =
+    📎 <b>Шаг 6 из 6.</b> Прикрепите файл работы документом.
+    Форматы: { $formats }. Размер — до { $size } МБ.
+submit-value-empty = Значение не может быть пустым
+submit-value-too-long = Слишком длинное значение
+submit-year-invalid = Год должен быть числом в диапазоне 1900-2100
+submit-file-format = Недопустимый формат файла. Разрешены: { $formats }
+submit-file-size = Файл больше { $size } МБ
+submit-file-size-unknown = Не удалось определить размер файла — отправьте его как документ
+submit-retry = ⚠️ { $error }. Попробуйте ещё раз или нажмите «Отмена».
+submit-confirm-title = 📋 <b>Проверьте данные работы:</b>
+submit-send-button = 📨 Отправить на проверку
+submit-draft-button = 📝 Сохранить черновик
+submit-incomplete = ❌ Не все данные работы заполнены. Начните заново: /submit
+submit-download-failed = ❌ Не удалось скачать файл из МАКС. Попробуйте отправить работу заново: /submit
+submit-too-large = ❌ Файл больше { $size } МБ.
+submit-store-failed = ❌ Не удалось сохранить файл. Попробуйте позже.
+submit-saved =
+    ✅ Работа сохранена!
+
+    🆔 <b>ID:</b> { $id }
+    📊 <b>Статус:</b> { $status }
+submit-save-failed = ❌ Не удалось сохранить работу: { $error }
+
+## Проверка работ
+
+review-forbidden = ⛔ Проверять работы могут только преподаватели и методисты.
+review-link-first = ℹ️ Сначала привяжите аккаунт архива: /link
+my-works-empty = 📭 Вы ещё не отправляли работ. Отправить: /submit
+my-works-title = 🗂 <b>Мои работы:</b>
+pending-empty = ✅ Работ на проверке нет.
+pending-title = 📥 <b>Работы на проверке:</b>
+pending-supervisor = рук. { $name }
+review-id-invalid = ❌ Неверный формат ID. Пример: /approve 123e4567-e89b-12d3-a456-426614174000
+review-not-pending = ℹ️ Работа «{ $title }» не ожидает проверки (статус: { $status }).
+review-published = ✅ Работа «{ $title }» — опубликована.
+review-rejected = ❌ Работа «{ $title }» — отклонена.
+
+## Привязка аккаунта
+
+role-admin = Администратор
+role-methodist = Методист
+role-teacher = Преподаватель
+role-student = Обучающийся
+
+link-code =
+    🔗 Ваш код привязки: <b>{ $code }</b>
+
+    Введите его в профиле веб-приложения архива в течение 10 минут.
+    Если код уже выдан на сайте, отправьте его боту: /link <код>
+link-code-failed = ❌ Не удалось выдать код: { $error }
+link-done = ✅ Аккаунт МАКС привязан к пользователю { $name } ({ $role }).
+link-invalid = ❌ Код неверный или истёк. Получите новый код в веб-приложении.
+link-failed = ❌ Ошибка привязки: { $error }
+unlink-done = ✖️ Аккаунт МАКС отвязан от архива.
+unlink-none = ℹ️ Аккаунт МАКС не привязан.
+link-private-only = 🔒 Привязка аккаунта доступна только в личном чате с ботом — напишите мне напрямую.
+whoami =
+    👤 { $name }
+    Логин: { $login }
+    Роль: { $role }
+whoami-none = ℹ️ Аккаунт МАКС не привязан к архиву. Привязать: /link
+
+## Подписки
+
+subscriptions-usage =
+    🔔 <b>Подписка на новые работы</b>
+
+    /subscribe специальность 09.02.07 — по специальности
+    /subscribe тег машинное обучение — по ключевому слову
+    /subscribe руководитель Иванова — по руководителю
+
+    Коротко: /subscribe 09.02.07 или /subscribe #python
+subscription-kind-specialty = 🎓 Специальность
+subscription-kind-tag = 🔑 Ключевое слово
+subscription-kind-supervisor = 👨‍🏫 Руководитель
+subscription-item = { $kind } «{ $value }»
+subscribe-done =
+    ✅ Подписка оформлена: { $subscription }
+
+    Когда появятся новые работы, я пришлю их в этот чат.
+    Все подписки: /subscriptions
+subscribe-exists = ℹ️ Такая подписка уже есть. Все подписки: /subscriptions
+subscribe-failed = ❌ { $error }
+subscriptions-empty = 📭 Подписок нет.
+subscriptions-title = 🔔 <b>Подписки чата:</b>
+subscriptions-unsubscribe-button = ✖️ Отписаться { $n }
+subscriptions-unsubscribe-all = Отписаться от всех: /unsubscribe все
+unsubscribe-none = ℹ️ Подписок нет.
+unsubscribe-all-done = ✅ Удалено подписок: { $count }
+unsubscribe-not-found = ❌ Подписка не найдена. Список подписок: /subscriptions
+unsubscribe-done = ✅ Подписка удалена: { $subscription }
+unsubscribe-already = ℹ️ Подписка уже удалена.
+
+## Групповые чаты
+
+chat-filter-group-only = ℹ️ Специальность по умолчанию задаётся только в групповых чатах.
+chat-filter-current =
+    🎓 Поиск в этом чате ограничен специальностью «{ $specialty }».
+    Сбросить: /filter off
+chat-filter-none =
+    🎓 Специальность по умолчанию не задана.
+    Администраторы чата могут задать её: /filter 09.02.07
+chat-filter-admins-only = ⛔ Менять настройки чата могут только его администраторы.
+chat-filter-set = ✅ Теперь /search и /browse в этом чате ищут по специальности «{ $specialty }».
+chat-filter-cleared = ✖️ Специальность по умолчанию сброшена.
+
+## Дайджест новых работ
+
+digest-title = 🔔 <b>Новые работы по вашим подпискам:</b>
+digest-more = …и ещё { $count }. Найти их можно через /browse
+digest-manage = Управление подписками: /subscriptions
+
+## Ошибки API
+
+error-internal = Внутренняя ошибка сервера
+error-unauthorized = Требуется авторизация
+error-forbidden = Доступ запрещён
+error-not-found = Ресурс не найден
+error-database = Ошибка базы данных
+error-validation = Проверьте поля запроса
+error-payload-too-large = Слишком большой запрос: допускается до { $limit } МБ
+error-too-many-requests = Слишком много запросов, повторите позже
+error-unsupported-media-type = Неподдерживаемый тип содержимого: { $content_type }
+error-service-unavailable = Сервис временно недоступен, повторите позже
+error-link-code-invalid = Код привязки неверный или истёк
+error-username-taken = Логин { $username } уже занят
+error-search-not-implemented = Поиск пока не реализован
+
+validation-specialty-empty = Специальность не может быть пустой
+validation-blank = Значение не может быть пустым
+validation-length-max = Не длиннее { $max } символов
+validation-length-min = Не короче { $min } символов
+validation-length-range = Длина — от { $min } до { $max } символов
+validation-range = Значение должно быть от { $min } до { $max }
+validation-work-type = Неизвестный тип работы
+validation-invalid = Недопустимое значение
+validation-json = Некорректное тело запроса: { $error }
+validation-chat-specialty-too-long = Слишком длинное название специальности
+validation-subscription-empty = Значение подписки не может быть пустым
+validation-subscription-too-long = Слишком длинное значение подписки
+validation-subscription-limit = В чате уже { $count } подписок — удалите ненужные
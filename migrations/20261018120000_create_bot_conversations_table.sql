@@ -0,0 +1,13 @@
+-- Состояние многошаговых диалогов чат-бота МАКС
+CREATE TABLE bot_conversations (
+    chat_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    state JSONB NOT NULL,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (chat_id, user_id)
+);
+
+-- Индекс для очистки просроченных диалогов
+CREATE INDEX idx_bot_conversations_expires_at ON bot_conversations(expires_at);
@@ -0,0 +1,10 @@
+-- Статус работы: черновик → на проверке → опубликована / отклонена
+CREATE TYPE work_status AS ENUM ('draft', 'submitted', 'published', 'rejected');
+
+-- Уже загруженные работы считаются опубликованными
+ALTER TABLE works
+    ADD COLUMN status work_status NOT NULL DEFAULT 'published',
+    ADD COLUMN submitter_max_user_id BIGINT;
+
+CREATE INDEX idx_works_status ON works(status);
+CREATE INDEX idx_works_submitter_max_user_id ON works(submitter_max_user_id);
@@ -0,0 +1,8 @@
+-- Кэш токенов файлов, загруженных на платформу МАКС (по SHA-256 содержимого)
+CREATE TABLE max_upload_cache (
+    file_hash CHAR(64) NOT NULL,
+    upload_type VARCHAR(20) NOT NULL,
+    token TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (file_hash, upload_type)
+);
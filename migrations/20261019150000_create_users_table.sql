@@ -0,0 +1,30 @@
+-- Роли пользователей архива
+CREATE TYPE user_role AS ENUM ('admin', 'methodist', 'teacher', 'student');
+
+-- Пользователи веб-приложения
+CREATE TABLE users (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    username VARCHAR(100) NOT NULL UNIQUE,
+    password_hash VARCHAR(255) NOT NULL,
+    role user_role NOT NULL DEFAULT 'student',
+    full_name VARCHAR(300),
+    email VARCHAR(255),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    max_user_id BIGINT UNIQUE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- Одноразовые коды привязки аккаунта МАКС.
+-- Код, выданный в веб-приложении, содержит user_id и вводится в боте;
+-- код, выданный ботом, содержит max_user_id и вводится в веб-приложении.
+CREATE TABLE max_link_codes (
+    code_hash CHAR(64) PRIMARY KEY,
+    user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+    max_user_id BIGINT,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    CHECK ((user_id IS NULL) <> (max_user_id IS NULL))
+);
+
+CREATE INDEX idx_max_link_codes_expires_at ON max_link_codes(expires_at);
@@ -0,0 +1,23 @@
+-- Что отслеживает подписка: специальность, ключевое слово или руководителя
+CREATE TYPE subscription_kind AS ENUM ('specialty', 'tag', 'supervisor');
+
+-- Подписки чатов бота на новые работы
+CREATE TABLE bot_subscriptions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    chat_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    kind subscription_kind NOT NULL,
+    value VARCHAR(300) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- Значения хранятся в нижнем регистре
+CREATE UNIQUE INDEX idx_bot_subscriptions_unique ON bot_subscriptions(chat_id, kind, value);
+
+-- Когда подписчикам отправлено уведомление об опубликованной работе.
+-- Уже опубликованные работы считаются разосланными.
+ALTER TABLE works ADD COLUMN notified_at TIMESTAMP WITH TIME ZONE;
+UPDATE works SET notified_at = NOW() WHERE status = 'published';
+
+CREATE INDEX idx_works_pending_notification ON works(created_at)
+    WHERE status = 'published' AND notified_at IS NULL;
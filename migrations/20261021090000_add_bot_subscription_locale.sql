@@ -0,0 +1,2 @@
+-- Язык, на котором чату отправляются дайджесты новых работ
+ALTER TABLE bot_subscriptions ADD COLUMN locale VARCHAR(8) NOT NULL DEFAULT 'ru';
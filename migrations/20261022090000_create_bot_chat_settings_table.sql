@@ -0,0 +1,9 @@
+-- Настройки групповых чатов бота, которые задают администраторы чата
+CREATE TABLE bot_chat_settings (
+    chat_id BIGINT PRIMARY KEY,
+    -- Специальность, которой по умолчанию ограничены поиск и подбор работ в чате
+    default_specialty VARCHAR(300),
+    -- Администратор, изменивший настройки последним
+    updated_by BIGINT NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,22 @@
+-- Обезличенные события бота для аналитики использования
+CREATE TABLE bot_events (
+    id BIGSERIAL PRIMARY KEY,
+    -- HMAC от ID пользователя МАКС: пользователей можно различать, но не узнать
+    user_hash VARCHAR(64) NOT NULL,
+    -- message, callback, dialog или bot_started
+    kind VARCHAR(16) NOT NULL,
+    -- Команда или действие кнопки; NULL — нераспознанное сообщение
+    command VARCHAR(32),
+    -- Поисковый запрос в нижнем регистре
+    query VARCHAR(200),
+    -- Сколько работ показано на первой странице результатов
+    result_count INTEGER,
+    latency_ms INTEGER NOT NULL,
+    locale VARCHAR(8) NOT NULL,
+    -- dialog, chat или channel
+    chat_type VARCHAR(16) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_bot_events_created_at ON bot_events(created_at);
+CREATE INDEX idx_bot_events_query ON bot_events(query, created_at) WHERE query IS NOT NULL;
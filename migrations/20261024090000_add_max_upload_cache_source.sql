@@ -0,0 +1,2 @@
+-- Откуда загружен файл: по этим данным бот загружает его заново, если платформа отвергла токен
+ALTER TABLE max_upload_cache ADD COLUMN file_path TEXT, ADD COLUMN file_name TEXT;
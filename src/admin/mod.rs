@@ -0,0 +1,5 @@
+//! Операции `max_app-admin`, которым мало одного вызова сервиса:
+//! перенос работ, обложки и проверка хранилища.
+
+pub mod storage;
+pub mod works;
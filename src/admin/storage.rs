@@ -0,0 +1,70 @@
+use std::collections::{HashMap, HashSet};
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    bot::media::is_remote,
+    core::services::WorkService,
+    error::AppError,
+    infrastructure::storage::FileStorage,
+};
+
+/// Файл, на который ссылается работа, но которого нет в хранилище (или он пустой)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingFile {
+    pub work_id: Uuid,
+    pub path: String,
+    pub problem: &'static str,
+}
+
+/// Итог сверки хранилища с таблицей `works`
+#[derive(Debug, Default)]
+pub struct StorageReport {
+    /// Сколько локальных файлов проверено
+    pub checked: usize,
+    pub missing: Vec<MissingFile>,
+    /// Файлы, на которые не ссылается ни одна работа
+    pub orphaned: Vec<String>,
+}
+
+impl StorageReport {
+    /// Все файлы работ на месте; лишние файлы ошибкой не считаются
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// Сверка файлов и обложек всех работ с содержимым хранилища
+pub async fn verify(pool: &PgPool, storage: &FileStorage) -> Result<StorageReport, AppError> {
+    let works = WorkService::new(pool.clone()).list_all(None).await?;
+    let files = storage.list_files().await.map_err(|e| AppError::Internal(format!("Хранилище недоступно: {}", e)))?;
+    let sizes: HashMap<&str, u64> = files.iter().map(|(path, size)| (path.as_str(), *size)).collect();
+
+    let mut report = StorageReport::default();
+    let mut referenced = HashSet::new();
+
+    for work in &works {
+        let paths = std::iter::once(work.file_path.as_str()).chain(work.thumbnail_path.as_deref());
+        for path in paths.filter(|p| !is_remote(p)) {
+            let normalized = path.trim_start_matches("./");
+            referenced.insert(normalized);
+            report.checked += 1;
+
+            let problem = match sizes.get(normalized) {
+                None => "нет файла",
+                Some(0) => "пустой файл",
+                Some(_) => continue,
+            };
+            report.missing.push(MissingFile { work_id: work.id, path: path.to_string(), problem });
+        }
+    }
+
+    report.orphaned = files
+        .iter()
+        .map(|(path, _)| path.clone())
+        .filter(|path| !referenced.contains(path.as_str()))
+        .collect();
+
+    Ok(report)
+}
@@ -0,0 +1,105 @@
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    bot::media::is_remote,
+    core::{
+        models::{WorkCreateDto, WorkStatus, WorkUpdateDto},
+        services::WorkService,
+    },
+    error::{AppError, FieldError},
+    infrastructure::{storage::FileStorage, thumbnails},
+};
+
+/// Запись файла импорта: поля работы и её статус. Статус из JSON принимается только здесь,
+/// [`WorkCreateDto`] его не читает
+#[derive(Debug, Deserialize)]
+pub struct WorkImport {
+    #[serde(flatten)]
+    pub work: WorkCreateDto,
+    #[serde(default)]
+    pub status: WorkStatus,
+}
+
+impl From<WorkImport> for WorkCreateDto {
+    fn from(record: WorkImport) -> Self {
+        WorkCreateDto { status: record.status, ..record.work }
+    }
+}
+
+/// Итог пересоздания обложек
+#[derive(Debug, Default)]
+pub struct ThumbnailReport {
+    pub generated: usize,
+    /// Обложка уже есть, файл внешний или не изображение (PDF, документы)
+    pub skipped: usize,
+    pub failed: Vec<(Uuid, String)>,
+}
+
+/// Импорт работ из выгрузки (`works export` подходит как есть).
+/// Сначала проверяются все записи: при ошибках не импортируется ничего, поле указывается как `works[3].title`.
+/// Опубликованные работы сразу отмечаются разосланными, чтобы подписчики не получили дайджест со старым архивом.
+/// Записи сохраняются одной транзакцией: после сбоя базы импорт можно просто повторить.
+pub async fn import(pool: &PgPool, works: Vec<WorkCreateDto>) -> Result<Vec<Uuid>, AppError> {
+    let works: Vec<WorkCreateDto> = works.into_iter().map(WorkCreateDto::trimmed).collect();
+    let mut errors = Vec::new();
+    for (index, dto) in works.iter().enumerate() {
+        if let Err(e) = dto.validate() {
+            if let AppError::InvalidFields(fields) = AppError::from(e) {
+                errors.extend(fields.into_iter().map(|f| FieldError {
+                    field: format!("works[{}].{}", index, f.field),
+                    message: f.message,
+                }));
+            }
+        }
+    }
+    if !errors.is_empty() {
+        return Err(AppError::InvalidFields(errors));
+    }
+
+    let service = WorkService::new(pool.clone());
+    let imported = service.create_all_notified(&works).await?;
+
+    Ok(imported.into_iter().map(|w| w.id).collect())
+}
+
+/// Обложки для работ, чей файл — изображение. Без `force` работы с существующей обложкой пропускаются.
+pub async fn regenerate_thumbnails(pool: &PgPool, storage: &FileStorage, force: bool) -> Result<ThumbnailReport, AppError> {
+    let service = WorkService::new(pool.clone());
+    let mut report = ThumbnailReport::default();
+
+    for work in service.list_all(None).await? {
+        let has_thumbnail = match work.thumbnail_path.as_deref() {
+            Some(path) => is_remote(path) || storage.resolve(path).is_some_and(|p| p.is_file()),
+            None => false,
+        };
+        if (has_thumbnail && !force) || is_remote(&work.file_path) {
+            report.skipped += 1;
+            continue;
+        }
+
+        let data = match storage.read(&work.file_path).await {
+            Ok(data) => data,
+            Err(e) => {
+                report.failed.push((work.id, format!("{}: {}", work.file_path, e)));
+                continue;
+            }
+        };
+        let Ok(thumbnail) = thumbnails::generate(&data) else {
+            report.skipped += 1;
+            continue;
+        };
+
+        let path = thumbnails::thumbnail_path(work.id);
+        if let Err(e) = storage.write(&path, &thumbnail).await {
+            report.failed.push((work.id, format!("{}: {}", path, e)));
+            continue;
+        }
+        service.update(work.id, WorkUpdateDto { thumbnail_path: Some(path), ..Default::default() }).await?;
+        report.generated += 1;
+    }
+
+    Ok(report)
+}
@@ -0,0 +1,72 @@
+//! Экстракторы с проверкой DTO: при ошибках возвращают 400 со списком всех неверных полей.
+
+use axum::{
+    async_trait,
+    extract::{
+        rejection::{JsonRejection, QueryRejection},
+        FromRequest, FromRequestParts, Query, Request,
+    },
+    http::request::Parts,
+    Json,
+};
+use serde::de::DeserializeOwned;
+use validator::Validate;
+
+use crate::{error::AppError, t};
+
+/// Лимит тела запроса axum по умолчанию
+const DEFAULT_BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+/// Действующий лимит тела запроса; роутер кладёт его в расширения запроса, чтобы назвать его в ошибке 413
+#[derive(Debug, Clone, Copy)]
+pub struct BodyLimit(pub usize);
+
+/// JSON-тело, прошедшее `Validate`
+pub struct ValidatedJson<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let limit = request.extensions().get::<BodyLimit>().map_or(DEFAULT_BODY_LIMIT, |l| l.0);
+        let Json(value) =
+            Json::<T>::from_request(request, state).await.map_err(|rejection| json_rejection(rejection, limit))?;
+        value.validate()?;
+        Ok(ValidatedJson(value))
+    }
+}
+
+/// Параметры строки запроса, прошедшие `Validate`
+pub struct ValidatedQuery<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ValidatedQuery<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state)
+            .await
+            .map_err(|e: QueryRejection| AppError::BadRequest(t!("validation-json", error = e.body_text())))?;
+        value.validate()?;
+        Ok(ValidatedQuery(value))
+    }
+}
+
+fn json_rejection(rejection: JsonRejection, limit: usize) -> AppError {
+    match rejection {
+        JsonRejection::MissingJsonContentType(_) => AppError::UnsupportedMediaType("application/json".to_string()),
+        JsonRejection::BytesRejection(e) if e.status() == axum::http::StatusCode::PAYLOAD_TOO_LARGE => {
+            AppError::PayloadTooLarge(limit as u64)
+        }
+        other => AppError::BadRequest(t!("validation-json", error = other.body_text())),
+    }
+}
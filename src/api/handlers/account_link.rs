@@ -0,0 +1,58 @@
+use axum::{extract::State, http::StatusCode, Json};
+use serde::Deserialize;
+use std::sync::Arc;
+use validator::Validate;
+
+use crate::{
+    api::{extract::ValidatedJson, middleware::AuthUser},
+    core::{
+        models::User,
+        services::{account_link_service::LinkCode, AccountLinkService},
+        validation::not_blank,
+    },
+    error::AppError,
+    state::AppState,
+    t,
+};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct LinkRequest {
+    #[validate(length(max = 32), custom = "not_blank")]
+    pub code: String,
+}
+
+/// Выдача кода, который пользователь введёт в боте: `/link <код>`
+pub async fn create_link_code(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user): AuthUser,
+) -> Result<Json<LinkCode>, AppError> {
+    let service = AccountLinkService::new(state.pool.clone());
+    let code = service.issue_web_code(user.id).await?;
+
+    Ok(Json(code))
+}
+
+/// Привязка по коду, который выдал бот в ответ на `/link`
+pub async fn link_account(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user): AuthUser,
+    ValidatedJson(request): ValidatedJson<LinkRequest>,
+) -> Result<Json<User>, AppError> {
+    let service = AccountLinkService::new(state.pool.clone());
+    match service.link_by_bot_code(&request.code, user.id).await? {
+        Some(user) => Ok(Json(user)),
+        None => Err(AppError::BadRequest(t!("error-link-code-invalid"))),
+    }
+}
+
+pub async fn unlink_account(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user): AuthUser,
+) -> Result<StatusCode, AppError> {
+    if let Some(max_user_id) = user.max_user_id {
+        let service = AccountLinkService::new(state.pool.clone());
+        service.unlink(max_user_id).await?;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,36 @@
+use axum::{extract::State, Json};
+use serde::Deserialize;
+use std::sync::Arc;
+use validator::Validate;
+
+use crate::{
+    api::{extract::ValidatedQuery, middleware::AdminUser},
+    core::{
+        models::BotAnalyticsReport,
+        services::{analytics_service::MAX_REPORT_DAYS, AnalyticsService},
+    },
+    error::AppError,
+    state::AppState,
+};
+
+/// Период отчёта по умолчанию, дни
+const DEFAULT_REPORT_DAYS: u32 = 30;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ReportQuery {
+    /// Период отчёта в днях, по умолчанию — 30
+    #[validate(range(min = 1, max = "MAX_REPORT_DAYS"))]
+    pub days: Option<u32>,
+}
+
+/// Отчёт об использовании бота: активные пользователи, запросы, команды и воронка
+pub async fn bot_report(
+    AdminUser(_): AdminUser,
+    State(state): State<Arc<AppState>>,
+    ValidatedQuery(params): ValidatedQuery<ReportQuery>,
+) -> Result<Json<BotAnalyticsReport>, AppError> {
+    let service = AnalyticsService::new(state.pool.clone());
+    let report = service.bot_report(params.days.unwrap_or(DEFAULT_REPORT_DAYS)).await?;
+
+    Ok(Json(report))
+}
@@ -0,0 +1,76 @@
+use axum::{extract::State, Json};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{info, warn};
+use validator::Validate;
+
+use crate::{
+    api::{extract::ValidatedJson, middleware::AuthUser},
+    core::{models::User, services::AuthService, validation::not_blank},
+    error::AppError,
+    integrations::max::launch_data::{self, LaunchUser},
+    state::AppState,
+};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct LoginRequest {
+    #[validate(length(max = 100), custom = "not_blank")]
+    pub username: String,
+    #[validate(length(min = 1, max = 1024))]
+    pub password: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionResponse {
+    pub token: String,
+    pub user: User,
+}
+
+pub async fn login(
+    State(state): State<Arc<AppState>>,
+    ValidatedJson(request): ValidatedJson<LoginRequest>,
+) -> Result<Json<SessionResponse>, AppError> {
+    let service = AuthService::new(state.pool.clone(), state.jwt_secret.clone());
+    let (user, token) = service.login(&request.username, &request.password).await?;
+
+    Ok(Json(SessionResponse { token, user }))
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct MaxLoginRequest {
+    /// Строка `WebApp.initData` из мини-приложения
+    #[validate(length(max = 4096), custom = "not_blank")]
+    pub init_data: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MaxSessionResponse {
+    pub token: String,
+    /// Пользователь архива, если аккаунт МАКС привязан
+    pub user: Option<User>,
+    pub max_user: LaunchUser,
+}
+
+/// Обмен данных запуска мини-приложения на сессионный токен
+pub async fn login_max(
+    State(state): State<Arc<AppState>>,
+    ValidatedJson(request): ValidatedJson<MaxLoginRequest>,
+) -> Result<Json<MaxSessionResponse>, AppError> {
+    let max_age = Duration::seconds(state.max_launch_data_ttl_secs as i64);
+    let launch = launch_data::validate(&request.init_data, state.max_bot_token.expose(), max_age, Utc::now())
+        .map_err(|e| {
+            warn!("Отклонены данные запуска мини-приложения: {}", e);
+            AppError::Unauthorized
+        })?;
+
+    let service = AuthService::new(state.pool.clone(), state.jwt_secret.clone());
+    let (user, token) = service.login_max(&launch).await?;
+    info!("🔑 Вход из мини-приложения: пользователь МАКС {}", launch.user.id);
+
+    Ok(Json(MaxSessionResponse { token, user, max_user: launch.user }))
+}
+
+pub async fn me(AuthUser(user): AuthUser) -> Json<User> {
+    Json(user)
+}
@@ -0,0 +1,91 @@
+use axum::{extract::State, http::StatusCode, Json};
+use serde::Serialize;
+use std::{collections::BTreeMap, future::Future, sync::Arc, time::{Duration, Instant}};
+
+use crate::{infrastructure::database, state::AppState};
+
+/// Сколько ждать одну проверку, прежде чем считать зависимость недоступной
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Ok,
+    Degraded,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckResult {
+    pub status: HealthStatus,
+    pub latency_ms: u64,
+    /// Причина сбоя — только для журнала: эндпоинт доступен без аутентификации
+    #[serde(skip)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub checks: BTreeMap<&'static str, CheckResult>,
+}
+
+/// Процесс жив и отвечает на запросы; зависимости не проверяются
+pub async fn live() -> Json<HealthReport> {
+    Json(HealthReport { status: HealthStatus::Ok, checks: BTreeMap::new() })
+}
+
+/// Готовность принимать трафик: база, миграции, хранилище и (по настройке) MAX API.
+/// Если хоть одна проверка не прошла — 503 со статусом каждой проверки, причины пишутся в журнал.
+pub async fn ready(State(state): State<Arc<AppState>>) -> (StatusCode, Json<HealthReport>) {
+    let mut checks = BTreeMap::new();
+
+    checks.insert(
+        "database",
+        check(async { sqlx::query("SELECT 1").execute(&state.pool).await.map(|_| ()) }).await,
+    );
+    checks.insert(
+        "migrations",
+        check(async {
+            match database::pending_migrations(&state.pool).await {
+                Ok(pending) if pending.is_empty() => Ok(()),
+                Ok(pending) => Err(format!("не применены миграции: {:?}", pending)),
+                Err(e) => Err(e.to_string()),
+            }
+        })
+        .await,
+    );
+    checks.insert("storage", check(state.storage.check_writable()).await);
+    if state.health_check_max_api {
+        checks.insert("max_api", check(state.max_api.ping()).await);
+    }
+
+    let healthy = checks.values().all(|c| c.status == HealthStatus::Ok);
+    let (code, status) = if healthy {
+        (StatusCode::OK, HealthStatus::Ok)
+    } else {
+        for (name, result) in &checks {
+            if let Some(ref error) = result.error {
+                tracing::warn!("⚠️ Проверка готовности {} не пройдена: {}", name, error);
+            }
+        }
+        (StatusCode::SERVICE_UNAVAILABLE, HealthStatus::Degraded)
+    };
+
+    (code, Json(HealthReport { status, checks }))
+}
+
+async fn check<E: ToString>(probe: impl Future<Output = Result<(), E>>) -> CheckResult {
+    let started = Instant::now();
+    let error = match tokio::time::timeout(CHECK_TIMEOUT, probe).await {
+        Ok(Ok(())) => None,
+        Ok(Err(e)) => Some(e.to_string()),
+        Err(_) => Some(format!("нет ответа за {} с", CHECK_TIMEOUT.as_secs())),
+    };
+
+    CheckResult {
+        status: if error.is_none() { HealthStatus::Ok } else { HealthStatus::Degraded },
+        latency_ms: started.elapsed().as_millis() as u64,
+        error,
+    }
+}
@@ -0,0 +1,34 @@
+use axum::{
+    extract::State,
+    http::{
+        header::{AUTHORIZATION, CONTENT_TYPE},
+        HeaderMap,
+    },
+    response::IntoResponse,
+};
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+
+use crate::{error::AppError, infrastructure::metrics, state::AppState};
+
+/// Метрики в текстовом формате Prometheus
+pub async fn render(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Result<impl IntoResponse, AppError> {
+    let Some(ref handle) = state.metrics else {
+        return Err(AppError::NotFound);
+    };
+
+    if let Some(ref token) = state.metrics_token {
+        // Сравнение за постоянное время; нет заголовка — то же, что неверный токен
+        let authorized = headers
+            .get(AUTHORIZATION)
+            .and_then(|v| v.as_bytes().strip_prefix(b"Bearer "))
+            .is_some_and(|bearer| bool::from(bearer.ct_eq(token.expose().as_bytes())));
+        if !authorized {
+            return Err(AppError::Unauthorized);
+        }
+    }
+
+    metrics::record_snapshot(&state.pool);
+
+    Ok(([(CONTENT_TYPE, "text/plain; version=0.0.4")], handle.render()))
+}
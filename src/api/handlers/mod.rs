@@ -1,15 +1,14 @@
+pub mod account_link;
+pub mod analytics;
+pub mod auth;
+pub mod health;
 pub mod max_webhook;
+pub mod metrics;
 pub mod works;
 pub mod search;
 
-use axum::http::StatusCode;
-
 pub use max_webhook::handle_webhook;
 pub use works::{get_work_by_id, list_specialties};
 pub use search::search_works;
 
-pub async fn health_check() -> (StatusCode, &'static str) {
-    (StatusCode::OK, "OK")
-}
-
 
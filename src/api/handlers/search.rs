@@ -3,7 +3,7 @@ use std::sync::Arc;
 use sqlx::PgPool;
 use serde::Serialize;
 
-use crate::error::AppError;
+use crate::{error::AppError, t};
 
 #[derive(Serialize)]
 pub struct SearchResponse {
@@ -14,6 +14,6 @@ pub async fn search_works(
     State(_pool): State<Arc<PgPool>>,
 ) -> Result<Json<SearchResponse>, AppError> {
     Ok(Json(SearchResponse {
-        message: "Поиск пока не реализован".to_string(),
+        message: t!("error-search-not-implemented"),
     }))
 }
\ No newline at end of file
@@ -1,25 +1,36 @@
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{Path, State},
     Json,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
+use validator::Validate;
 
 use crate::{
-    core::services::WorkService,
+    api::{extract::ValidatedQuery, middleware::AuthSession},
+    core::{
+        models::work::{MAX_YEAR, MIN_YEAR},
+        services::WorkService,
+        validation,
+    },
     error::AppError,
     state::AppState,
 };
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct SearchQuery {
+    #[validate(length(max = 200))]
     pub query: Option<String>,
+    #[validate(length(max = 200))]
     pub specialty: Option<String>,
+    #[validate(custom = "validation::work_type")]
     pub work_type: Option<String>,
+    #[validate(range(min = "MIN_YEAR", max = "MAX_YEAR"))]
     pub year: Option<i32>,
+    #[validate(range(min = 1))]
     pub page: Option<u32>,
+    #[validate(range(min = 1, max = 100))]
     pub limit: Option<u32>,
 }
 
@@ -31,25 +42,31 @@ pub struct SearchResponse {
     pub limit: u32,
 }
 
-pub async fn health_check() -> &'static str {
-    "OK"
-}
-
+/// Карточка работы: неопубликованные доступны только их автору и проверяющим,
+/// для остальных — 404, как и несуществующие
 pub async fn get_work_by_id(
     Path(id): Path<Uuid>,
     State(state): State<Arc<AppState>>,
+    viewer: Option<AuthSession>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     let service = WorkService::new(state.pool.clone());
     let work = service.get_by_id(id).await?;
-    
+    // Сессия мини-приложения без привязки — тоже зритель: по ней узнаётся автор заявки
+    let (user, max_user_id) = match viewer {
+        Some(AuthSession(session)) => (session.user, session.max_user_id),
+        None => (None, None),
+    };
+
     match work {
-        Some(w) => Ok(Json(serde_json::json!(w))),
-        None => Err(AppError::NotFound),
+        Some(w) if w.is_visible_to(max_user_id, user.as_ref()) => {
+            Ok(Json(serde_json::json!(w)))
+        }
+        _ => Err(AppError::NotFound),
     }
 }
 
 pub async fn search_works(
-    Query(params): Query<SearchQuery>,
+    ValidatedQuery(params): ValidatedQuery<SearchQuery>,
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<SearchResponse>, AppError> {
     let service = WorkService::new(state.pool.clone());
@@ -0,0 +1,148 @@
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, MatchedPath, Request},
+    http::{
+        header::{ACCEPT_LANGUAGE, AUTHORIZATION},
+        request::Parts,
+        HeaderValue,
+    },
+    middleware::Next,
+    response::Response,
+};
+use std::{sync::Arc, time::Instant};
+use uuid::Uuid;
+
+use crate::{
+    core::{
+        models::{User, UserRole},
+        services::{auth_service::Session, AuthService},
+    },
+    error::AppError,
+    i18n::{self, Locale},
+    infrastructure::metrics,
+    state::AppState,
+};
+
+/// Заголовок с идентификатором запроса: принимается от клиента или прокси, иначе генерируется
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Самый длинный идентификатор запроса, принимаемый от клиента
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Идентификатор текущего HTTP-запроса (вне обработки запроса — `None`)
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// Счётчик и длительность HTTP-запросов по шаблону маршрута
+pub async fn track_metrics(request: Request, next: Next) -> Response {
+    let started = Instant::now();
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| metrics::UNMATCHED_ROUTE.to_string());
+
+    let response = next.run(request).await;
+    metrics::record_http_request(&method, &route, response.status().as_u16(), started.elapsed());
+    response
+}
+
+/// Идентификатор запроса: доступен обработчикам, ошибкам и span'у запроса в журнале,
+/// возвращается в заголовке `X-Request-Id`
+pub async fn request_id(mut request: Request, next: Next) -> Response {
+    let id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .filter(|v| !v.is_empty() && v.len() <= MAX_REQUEST_ID_LENGTH && v.bytes().all(|b| b.is_ascii_graphic()))
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().simple().to_string());
+
+    let value = HeaderValue::from_str(&id).ok();
+    if let Some(ref value) = value {
+        request.headers_mut().insert(REQUEST_ID_HEADER, value.clone());
+    }
+
+    let mut response = REQUEST_ID.scope(id, next.run(request)).await;
+    if let Some(value) = value {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// Язык сообщений API по заголовку `Accept-Language`; без заголовка — русский
+pub async fn locale(request: Request, next: Next) -> Response {
+    let locale = request
+        .headers()
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|v| v.to_str().ok())
+        .map(Locale::from_accept_language)
+        .unwrap_or_default();
+
+    i18n::scope(locale, next.run(request)).await
+}
+
+fn bearer_token(parts: &Parts) -> Result<&str, AppError> {
+    parts
+        .headers
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim)
+        .ok_or(AppError::Unauthorized)
+}
+
+/// Пользователь, аутентифицированный по заголовку `Authorization: Bearer <JWT>`
+pub struct AuthUser(pub User);
+
+#[async_trait]
+impl FromRequestParts<Arc<AppState>> for AuthUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &Arc<AppState>) -> Result<Self, Self::Rejection> {
+        let token = bearer_token(parts)?;
+        let service = AuthService::new(state.pool.clone(), state.jwt_secret.clone());
+        let user = service.authenticate(token).await?;
+
+        Ok(AuthUser(user))
+    }
+}
+
+/// Любая действующая сессия, включая сессию мини-приложения без привязанного аккаунта
+pub struct AuthSession(pub Session);
+
+#[async_trait]
+impl FromRequestParts<Arc<AppState>> for AuthSession {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &Arc<AppState>) -> Result<Self, Self::Rejection> {
+        let token = bearer_token(parts)?;
+        let service = AuthService::new(state.pool.clone(), state.jwt_secret.clone());
+        let session = service.session(token).await?;
+
+        Ok(AuthSession(session))
+    }
+}
+
+/// Аутентифицированный администратор архива; остальным пользователям — 403
+pub struct AdminUser(pub User);
+
+#[async_trait]
+impl FromRequestParts<Arc<AppState>> for AdminUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &Arc<AppState>) -> Result<Self, Self::Rejection> {
+        let AuthUser(user) = AuthUser::from_request_parts(parts, state).await?;
+        if user.role != UserRole::Admin {
+            return Err(AppError::Forbidden);
+        }
+
+        Ok(AdminUser(user))
+    }
+}
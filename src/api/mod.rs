@@ -1,3 +1,4 @@
+pub mod extract;
 pub mod handlers;
 pub mod middleware;
 pub mod routes;
\ No newline at end of file
@@ -1,11 +1,16 @@
-use axum::Router;
+use axum::{extract::DefaultBodyLimit, Extension, Router};
 use std::sync::Arc;
+use tower_http::{
+    trace::{DefaultOnResponse, TraceLayer},
+    LatencyUnit,
+};
+use tracing::Level;
 
-use super::handlers;
-use crate::state::AppState;
+use super::{extract::BodyLimit, handlers, middleware};
+use crate::{error::AppError, infrastructure::logger, state::AppState};
 
 pub fn create_router(state: Arc<AppState>) -> Router {
-    Router::new()
+    let router = Router::new()
         // Вебхук для чат-бота МАКС
         .route("/api/max/webhook", axum::routing::post(handlers::max_webhook::handle_webhook))
         
@@ -14,8 +19,56 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         .route("/api/works/:id", axum::routing::get(handlers::works::get_work_by_id))
         .route("/api/works/specialties", axum::routing::get(handlers::works::list_specialties))
         
-        // Health check
-        .route("/health", axum::routing::get(handlers::works::health_check))
-        
+        // Аутентификация
+        .route("/api/auth/login", axum::routing::post(handlers::auth::login))
+        .route("/api/auth/max", axum::routing::post(handlers::auth::login_max))
+        .route("/api/auth/me", axum::routing::get(handlers::auth::me))
+
+        // Привязка аккаунта МАКС
+        .route("/api/account/max/link-code", axum::routing::post(handlers::account_link::create_link_code))
+        .route(
+            "/api/account/max/link",
+            axum::routing::post(handlers::account_link::link_account)
+                .delete(handlers::account_link::unlink_account),
+        )
+
+        // Аналитика бота (только для администраторов)
+        .route("/api/admin/analytics/bot", axum::routing::get(handlers::analytics::bot_report))
+
+        // Проверки для оркестратора: жив ли процесс и готов ли он принимать трафик
+        .route("/health/live", axum::routing::get(handlers::health::live))
+        .route("/health/ready", axum::routing::get(handlers::health::ready))
+        .route("/health", axum::routing::get(handlers::health::ready));
+
+    // Метрики — на основном адресе, только если для них не выделен отдельный
+    let router = if state.metrics_bind_address.is_none() { router.merge(metrics_routes()) } else { router };
+
+    let body_limit = state.http_body_limit;
+    router
+        // Неизвестные адреса — та же JSON-ошибка, что и у обработчиков
+        .fallback(|| async { AppError::NotFound })
+        .layer(DefaultBodyLimit::max(body_limit))
+        .layer(Extension(BodyLimit(body_limit)))
+        .layer(axum::middleware::from_fn(middleware::track_metrics))
+        .layer(axum::middleware::from_fn(middleware::locale))
+        // Span на каждый запрос: метод, путь, идентификатор; по завершении — статус и время ответа
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(logger::http_span)
+                .on_response(DefaultOnResponse::new().level(Level::INFO).latency_unit(LatencyUnit::Millis)),
+        )
+        .layer(axum::middleware::from_fn(middleware::request_id))
+        .with_state(state)
+}
+
+/// Отдельный сервер метрик для `metrics_bind_address`
+pub fn create_metrics_router(state: Arc<AppState>) -> Router {
+    metrics_routes()
+        .fallback(|| async { AppError::NotFound })
+        .layer(axum::middleware::from_fn(middleware::locale))
         .with_state(state)
-}
\ No newline at end of file
+}
+
+fn metrics_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/metrics", axum::routing::get(handlers::metrics::render))
+}
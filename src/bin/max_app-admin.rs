@@ -0,0 +1,321 @@
+//! Администрирование архива из командной строки. Конфигурация та же, что у сервера:
+//! `Config.yaml` и переменные `MAX_APP_*`.
+
+use std::{io::BufRead, path::PathBuf};
+
+use clap::{Parser, Subcommand};
+use dotenv::dotenv;
+use rand::{distributions::Alphanumeric, Rng};
+use sqlx::PgPool;
+
+use max_app::{
+    admin::{self, works::WorkImport},
+    bot::setup,
+    config::{self, Config},
+    core::{
+        models::{UserCreateDto, UserRole, WorkStatus},
+        services::{AuthService, WorkService},
+    },
+    infrastructure::{database, storage::FileStorage},
+};
+
+type CliResult = Result<(), Box<dyn std::error::Error>>;
+
+/// Длина пароля, который генерируется, если его не передали через stdin
+const GENERATED_PASSWORD_LENGTH: usize = 20;
+
+#[derive(Parser)]
+#[command(name = "max_app-admin", about = "Обслуживание цифрового архива работ", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Пользователи веб-приложения
+    User {
+        #[command(subcommand)]
+        action: UserAction,
+    },
+    /// Миграции базы данных
+    Migrate {
+        #[command(subcommand)]
+        action: MigrateAction,
+    },
+    /// Работы архива
+    Works {
+        #[command(subcommand)]
+        action: WorksAction,
+    },
+    /// Файловое хранилище
+    Storage {
+        #[command(subcommand)]
+        action: StorageAction,
+    },
+    /// Вебхук бота в МАКС
+    Webhook {
+        #[command(subcommand)]
+        action: WebhookAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum UserAction {
+    /// Создать администратора
+    CreateAdmin {
+        username: String,
+        #[arg(long)]
+        full_name: Option<String>,
+        /// Прочитать пароль из первой строки stdin; иначе он будет сгенерирован и выведен
+        #[arg(long)]
+        password_stdin: bool,
+    },
+    /// Задать пользователю новый пароль
+    ResetPassword {
+        username: String,
+        /// Прочитать пароль из первой строки stdin; иначе он будет сгенерирован и выведен
+        #[arg(long)]
+        password_stdin: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum MigrateAction {
+    /// Применить недостающие миграции
+    Up,
+    /// Показать, какие миграции применены
+    Status,
+}
+
+#[derive(Subcommand)]
+enum WorksAction {
+    /// Выгрузить работы в JSON
+    Export {
+        /// Файл для выгрузки; по умолчанию — stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        /// Только работы в статусе draft, submitted, published или rejected
+        #[arg(long, value_parser = parse_status)]
+        status: Option<WorkStatus>,
+    },
+    /// Загрузить работы из JSON-массива (формат `works export`)
+    Import { file: PathBuf },
+    /// Пересчитать полнотекстовый поисковый индекс
+    Reindex,
+    /// Создать обложки для работ, чей файл — изображение
+    Thumbnails {
+        /// Пересоздать и существующие обложки
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum StorageAction {
+    /// Проверить, что файлы всех работ на месте
+    Verify,
+}
+
+#[derive(Subcommand)]
+enum WebhookAction {
+    /// Подписать бота на вебхук (адрес по умолчанию — `max_webhook_url`)
+    Register {
+        #[arg(long)]
+        url: Option<String>,
+    },
+}
+
+#[tokio::main]
+async fn main() {
+    dotenv().ok();
+    let cli = Cli::parse();
+
+    // Сообщения библиотеки — в stderr, чтобы не смешиваться с выводом команд
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| "max_app=info".into()),
+        )
+        .init();
+
+    if let Err(e) = run(cli).await {
+        eprintln!("❌ {}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run(cli: Cli) -> CliResult {
+    let config = config::load()?;
+
+    // Регистрация вебхука не требует базы данных
+    if let Command::Webhook { action: WebhookAction::Register { url } } = cli.command {
+        return register_webhook(&config, url).await;
+    }
+
+    let pool = database::connect(config.database_url.expose(), &config.pool_settings()).await?;
+    match cli.command {
+        Command::User { action } => user(&pool, &config, action).await,
+        Command::Migrate { action: MigrateAction::Up } => migrate_up(&pool).await,
+        Command::Migrate { action: MigrateAction::Status } => migrate_status(&pool).await,
+        Command::Works { action } => {
+            database::ensure_schema(&pool, false).await?;
+            works(&pool, &config, action).await
+        }
+        Command::Storage { action: StorageAction::Verify } => verify_storage(&pool, &config).await,
+        Command::Webhook { .. } => unreachable!("обработано выше"),
+    }
+}
+
+async fn user(pool: &PgPool, config: &Config, action: UserAction) -> CliResult {
+    let service = AuthService::new(pool.clone(), config.jwt_secret.clone());
+
+    match action {
+        UserAction::CreateAdmin { username, full_name, password_stdin } => {
+            let (password, generated) = password(password_stdin)?;
+            let user = service
+                .create_user(UserCreateDto { username, password: password.clone(), role: UserRole::Admin, full_name })
+                .await?;
+            println!("Администратор {} создан (id {})", user.username, user.id);
+            if generated {
+                println!("Пароль: {}", password);
+            }
+        }
+        UserAction::ResetPassword { username, password_stdin } => {
+            let (password, generated) = password(password_stdin)?;
+            let user = service.reset_password(&username, &password).await?;
+            println!("Пароль пользователя {} изменён", user.username);
+            if generated {
+                println!("Пароль: {}", password);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Пароль из stdin или случайный; второе значение — был ли он сгенерирован
+fn password(from_stdin: bool) -> Result<(String, bool), std::io::Error> {
+    if from_stdin {
+        let mut line = String::new();
+        std::io::stdin().lock().read_line(&mut line)?;
+        return Ok((line.trim_end_matches(['\r', '\n']).to_string(), false));
+    }
+
+    let password = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(GENERATED_PASSWORD_LENGTH)
+        .map(char::from)
+        .collect();
+    Ok((password, true))
+}
+
+async fn migrate_up(pool: &PgPool) -> CliResult {
+    let applied = database::migrate(pool).await?;
+    if applied.is_empty() {
+        println!("Схема актуальна, новых миграций нет");
+    } else {
+        println!("Применено миграций: {}", applied.len());
+    }
+    Ok(())
+}
+
+async fn migrate_status(pool: &PgPool) -> CliResult {
+    let migrations = database::migration_status(pool).await?;
+    for m in &migrations {
+        let state = match m.applied_at {
+            Some(at) => format!("применена {}", at.format("%Y-%m-%d %H:%M")),
+            None => "не применена".to_string(),
+        };
+        println!("{}  {:<45}  {}", m.version, m.description, state);
+    }
+
+    let pending = migrations.iter().filter(|m| m.applied_at.is_none()).count();
+    println!("Не применено миграций: {}", pending);
+    Ok(())
+}
+
+async fn works(pool: &PgPool, config: &Config, action: WorksAction) -> CliResult {
+    let service = WorkService::new(pool.clone());
+
+    match action {
+        WorksAction::Export { output, status } => {
+            let works = service.list_all(status).await?;
+            let json = serde_json::to_string_pretty(&works)?;
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, json)?;
+                    eprintln!("Выгружено работ: {} → {}", works.len(), path.display());
+                }
+                None => println!("{}", json),
+            }
+        }
+        WorksAction::Import { file } => {
+            let works: Vec<WorkImport> = serde_json::from_slice(&std::fs::read(&file)?)?;
+            let imported = admin::works::import(pool, works.into_iter().map(Into::into).collect()).await?;
+            println!("Импортировано работ: {}", imported.len());
+        }
+        WorksAction::Reindex => {
+            let count = service.rebuild_search_index().await?;
+            println!("Поисковый индекс пересчитан для работ: {}", count);
+        }
+        WorksAction::Thumbnails { force } => {
+            let storage = FileStorage::new(&config.file_storage_path);
+            let report = admin::works::regenerate_thumbnails(pool, &storage, force).await?;
+            println!("Обложек создано: {}, пропущено: {}", report.generated, report.skipped);
+            for (id, error) in &report.failed {
+                println!("  ошибка {}: {}", id, error);
+            }
+            if !report.failed.is_empty() {
+                return Err(format!("не удалось создать обложек: {}", report.failed.len()).into());
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn verify_storage(pool: &PgPool, config: &Config) -> CliResult {
+    let storage = FileStorage::new(&config.file_storage_path);
+    let report = admin::storage::verify(pool, &storage).await?;
+
+    println!("Проверено файлов: {}", report.checked);
+    for missing in &report.missing {
+        println!("  {} работы {}: {}", missing.problem, missing.work_id, missing.path);
+    }
+    for path in &report.orphaned {
+        println!("  лишний файл: {}", path);
+    }
+
+    if report.is_ok() {
+        println!("Все файлы работ на месте, лишних файлов: {}", report.orphaned.len());
+        Ok(())
+    } else {
+        Err(format!("недоступно файлов работ: {}", report.missing.len()).into())
+    }
+}
+
+async fn register_webhook(config: &Config, url: Option<String>) -> CliResult {
+    let url = url
+        .or_else(|| config.max_webhook_url.clone())
+        .ok_or("адрес вебхука не задан: передайте --url или max_webhook_url")?;
+
+    let changed = setup::sync_webhook(
+        &config.max_api_client(),
+        &url,
+        &config.max_webhook_update_types,
+        config.max_webhook_secret.expose(),
+    )
+    .await?;
+    if changed {
+        println!("Вебхук зарегистрирован: {}", url);
+    } else {
+        println!("Вебхук уже зарегистрирован, секрет обновлён: {}", url);
+    }
+    Ok(())
+}
+
+fn parse_status(value: &str) -> Result<WorkStatus, String> {
+    [WorkStatus::Draft, WorkStatus::Submitted, WorkStatus::Published, WorkStatus::Rejected]
+        .into_iter()
+        .find(|s| s.as_str() == value)
+        .ok_or_else(|| format!("неизвестный статус {}", value))
+}
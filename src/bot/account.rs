@@ -0,0 +1,86 @@
+use tracing::warn;
+
+use crate::{
+    core::{
+        models::{User, UserRole},
+        services::AccountLinkService,
+    },
+    integrations::max::{formatting, SendMessageRequest},
+    state::AppState,
+    t,
+};
+
+use super::{error_text, ChatContext};
+
+/// Пользователь архива, привязанный к аккаунту МАКС отправителя
+pub async fn linked_user(ctx: &ChatContext, state: &AppState) -> Option<User> {
+    let service = AccountLinkService::new(state.pool.clone());
+    match service.find_by_max_user(ctx.user_id).await {
+        Ok(user) => user,
+        Err(e) => {
+            warn!("Не удалось получить привязанного пользователя {}: {}", ctx.user_id, e);
+            None
+        }
+    }
+}
+
+pub fn format_role(role: &UserRole) -> String {
+    match role {
+        UserRole::Admin => t!("role-admin"),
+        UserRole::Methodist => t!("role-methodist"),
+        UserRole::Teacher => t!("role-teacher"),
+        UserRole::Student => t!("role-student"),
+    }
+}
+
+/// Команда /link: с кодом — привязка по коду из веб-приложения, без кода — выдача кода для веб-приложения.
+/// Только в личном диалоге: в группе код увидят все участники чата.
+pub async fn link(code: &str, ctx: &ChatContext, state: &AppState) -> SendMessageRequest {
+    if ctx.is_group {
+        return SendMessageRequest::html(t!("link-private-only"));
+    }
+    let service = AccountLinkService::new(state.pool.clone());
+
+    if code.is_empty() {
+        return match service.issue_bot_code(ctx.user_id).await {
+            Ok(link_code) => SendMessageRequest::html(t!("link-code", code = link_code.code)),
+            Err(e) => SendMessageRequest::html(t!("link-code-failed", error = error_text(&e))),
+        };
+    }
+
+    match service.link_by_web_code(code, ctx.user_id).await {
+        Ok(Some(user)) => SendMessageRequest::html(t!(
+            "link-done",
+            name = formatting::bold(user.full_name.as_deref().unwrap_or(&user.username)),
+            role = format_role(&user.role)
+        )),
+        Ok(None) => SendMessageRequest::html(t!("link-invalid")),
+        Err(e) => SendMessageRequest::html(t!("link-failed", error = error_text(&e))),
+    }
+}
+
+/// Команда /unlink, только в личном диалоге
+pub async fn unlink(ctx: &ChatContext, state: &AppState) -> SendMessageRequest {
+    if ctx.is_group {
+        return SendMessageRequest::html(t!("link-private-only"));
+    }
+    let service = AccountLinkService::new(state.pool.clone());
+    match service.unlink(ctx.user_id).await {
+        Ok(true) => SendMessageRequest::html(t!("unlink-done")),
+        Ok(false) => SendMessageRequest::html(t!("unlink-none")),
+        Err(e) => SendMessageRequest::html(t!("error-generic", error = error_text(&e))),
+    }
+}
+
+/// Команда /whoami
+pub async fn whoami(ctx: &ChatContext, state: &AppState) -> SendMessageRequest {
+    match linked_user(ctx, state).await {
+        Some(user) => SendMessageRequest::html(t!(
+            "whoami",
+            name = formatting::bold(user.full_name.as_deref().unwrap_or(&user.username)),
+            login = formatting::escape(&user.username),
+            role = format_role(&user.role)
+        )),
+        None => SendMessageRequest::html(t!("whoami-none")),
+    }
+}
@@ -0,0 +1,123 @@
+//! Аналитика использования бота: на каждое обновление пишется одно обезличенное событие.
+//!
+//! Обработчики дополняют событие по ходу работы (`record_command`, `record_search`) — оно хранится
+//! в task-local переменной, как и язык сообщений.
+
+use std::{cell::RefCell, future::Future, time::Instant};
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tracing::warn;
+
+use crate::{
+    core::{models::NewBotEvent, services::AnalyticsService},
+    i18n::Locale,
+    integrations::max::models::Update,
+    state::AppState,
+};
+
+/// Что известно об обновлении до его обработки
+pub struct EventSource {
+    user_id: i64,
+    kind: &'static str,
+    chat_type: String,
+}
+
+impl EventSource {
+    /// `None` для обновлений, которые бот не обрабатывает
+    pub fn from_update(update: &Update) -> Option<Self> {
+        let (user_id, kind, chat_type) = match update {
+            Update::MessageCreated { message, .. } => {
+                (message.sender.user_id, "message", message.recipient.chat_type.clone())
+            }
+            Update::MessageCallback { callback, message, .. } => (
+                callback.user.user_id,
+                "callback",
+                message.as_ref().map(|m| m.recipient.chat_type.clone()).unwrap_or_default(),
+            ),
+            Update::BotStarted { user, .. } => (user.user_id, "bot_started", "dialog".to_string()),
+            Update::Unsupported => return None,
+        };
+
+        Some(Self { user_id, kind, chat_type })
+    }
+}
+
+#[derive(Default)]
+struct Collected {
+    kind: Option<&'static str>,
+    command: Option<&'static str>,
+    query: Option<String>,
+    result_count: Option<i32>,
+    skipped: bool,
+}
+
+tokio::task_local! {
+    static CURRENT: RefCell<Collected>;
+}
+
+fn update(f: impl FnOnce(&mut Collected)) {
+    // Вне `track` (например, в рассылке дайджестов) события не пишутся
+    let _ = CURRENT.try_with(|collected| f(&mut collected.borrow_mut()));
+}
+
+/// Команда или действие кнопки, которое выполняет бот
+pub fn record_command(command: &'static str) {
+    update(|c| c.command = Some(command));
+}
+
+/// Ответ на шаг диалога
+pub fn record_dialog() {
+    update(|c| c.kind = Some("dialog"));
+}
+
+/// Результаты поиска: запрос (если искали по тексту) и число работ на первой странице
+pub fn record_search(query: Option<&str>, result_count: usize) {
+    update(|c| {
+        c.query = query.map(str::to_string);
+        c.result_count = Some(result_count as i32);
+    });
+}
+
+/// Обновление не адресовано боту (болтовня в группе) — событие не пишется
+pub fn skip() {
+    update(|c| c.skipped = true);
+}
+
+/// Обработка обновления с записью события после неё
+pub async fn track<F: Future>(state: &AppState, source: EventSource, locale: Locale, future: F) -> F::Output {
+    let started = Instant::now();
+    let (output, collected) = CURRENT
+        .scope(RefCell::new(Collected::default()), async {
+            let output = future.await;
+            (output, CURRENT.with(|c| c.take()))
+        })
+        .await;
+
+    if !state.bot_analytics_enabled || collected.skipped {
+        return output;
+    }
+
+    let event = NewBotEvent {
+        user_hash: anonymize(source.user_id, state.analytics_salt.expose()),
+        kind: collected.kind.unwrap_or(source.kind).to_string(),
+        command: collected.command.map(str::to_string),
+        query: collected.query,
+        result_count: collected.result_count,
+        latency_ms: started.elapsed().as_millis().min(i32::MAX as u128) as i32,
+        locale: locale.as_str().to_string(),
+        chat_type: source.chat_type,
+    };
+    if let Err(e) = AnalyticsService::new(state.pool.clone()).record(event).await {
+        warn!("Не удалось записать событие аналитики: {}", e);
+    }
+
+    output
+}
+
+/// Обезличенный ID пользователя: HMAC-SHA256 на `analytics_salt`, первые 128 бит
+pub fn anonymize(user_id: i64, secret: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(format!("max-user:{}", user_id).as_bytes());
+    hex::encode(&mac.finalize().into_bytes()[..16])
+}
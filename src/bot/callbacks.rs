@@ -0,0 +1,217 @@
+use uuid::Uuid;
+
+use crate::{
+    core::models::WorkType,
+    integrations::max::{formatting, CallbackAnswer, SendMessageRequest},
+    state::AppState,
+    t,
+};
+
+use super::{
+    analytics,
+    commands::{self, SearchFilter},
+    dialogs::{self, DialogInput},
+    error_text,
+    group, media, review, subscriptions, ChatContext,
+};
+
+/// Максимальная длина поискового запроса, сохраняемого в payload кнопки
+const MAX_QUERY_IN_PAYLOAD: usize = 200;
+
+/// Действие, закодированное в payload callback-кнопки
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallbackAction {
+    /// Показать карточку работы
+    Work(Uuid),
+    /// Получить файл работы
+    Download(Uuid),
+    /// Показать страницу результатов поиска
+    Search { page: u32, filter: SearchFilter },
+    /// Опубликовать или отклонить работу на проверке
+    Review { id: Uuid, approve: bool },
+    /// Удалить подписку чата
+    Unsubscribe(Uuid),
+    /// Выбор варианта на текущем шаге диалога
+    Dialog(String),
+    /// Прервать текущий диалог
+    Cancel,
+}
+
+impl CallbackAction {
+    pub fn encode(&self) -> String {
+        match self {
+            CallbackAction::Work(id) => format!("work:{}", id),
+            CallbackAction::Download(id) => format!("download:{}", id),
+            CallbackAction::Search { page, filter } => match filter.query {
+                Some(ref query) => {
+                    let query: String = query.chars().take(MAX_QUERY_IN_PAYLOAD).collect();
+                    format!("search:{}:{}", page, query)
+                }
+                // Фильтр пошагового подбора: специальность идёт последней, т.к. может содержать ':'
+                None => format!(
+                    "browse:{}:{}:{}:{}",
+                    page,
+                    filter.year.map(|y| y.to_string()).unwrap_or_default(),
+                    filter.work_type.map(|wt| wt.as_str()).unwrap_or_default(),
+                    filter.specialty.as_deref().unwrap_or_default()
+                ),
+            },
+            CallbackAction::Review { id, approve: true } => format!("approve:{}", id),
+            CallbackAction::Review { id, approve: false } => format!("reject:{}", id),
+            CallbackAction::Unsubscribe(id) => format!("unsub:{}", id),
+            CallbackAction::Dialog(value) => format!("dlg:{}", value),
+            CallbackAction::Cancel => "cancel".to_string(),
+        }
+    }
+
+    /// Имя действия в аналитике; открытие карточки и скачивание считаются так же, как команды
+    pub fn analytics_name(&self) -> &'static str {
+        match self {
+            CallbackAction::Work(_) => "work",
+            CallbackAction::Download(_) => "download",
+            CallbackAction::Search { .. } => "search_page",
+            CallbackAction::Review { approve: true, .. } => "approve",
+            CallbackAction::Review { approve: false, .. } => "reject",
+            CallbackAction::Unsubscribe(_) => "unsubscribe",
+            CallbackAction::Dialog(_) => "dialog",
+            CallbackAction::Cancel => "cancel",
+        }
+    }
+
+    pub fn parse(payload: &str) -> Option<Self> {
+        if payload == "cancel" {
+            return Some(CallbackAction::Cancel);
+        }
+
+        let (kind, rest) = payload.split_once(':')?;
+        match kind {
+            "work" => Uuid::parse_str(rest).ok().map(CallbackAction::Work),
+            "download" => Uuid::parse_str(rest).ok().map(CallbackAction::Download),
+            "search" => {
+                let (page, query) = rest.split_once(':')?;
+                let page = page.parse().ok().filter(|p| *p >= 1)?;
+                Some(CallbackAction::Search { page, filter: SearchFilter::by_query(query) })
+            }
+            "browse" => {
+                let mut parts = rest.splitn(4, ':');
+                let page = parts.next()?.parse().ok().filter(|p| *p >= 1)?;
+                let year = match parts.next()? {
+                    "" => None,
+                    y => Some(y.parse().ok()?),
+                };
+                let work_type = match parts.next()? {
+                    "" => None,
+                    wt => Some(WorkType::parse(wt)?),
+                };
+                let specialty = Some(parts.next()?).filter(|s| !s.is_empty()).map(str::to_string);
+                Some(CallbackAction::Search {
+                    page,
+                    filter: SearchFilter { query: None, specialty, year, work_type },
+                })
+            }
+            "approve" => Uuid::parse_str(rest).ok().map(|id| CallbackAction::Review { id, approve: true }),
+            "reject" => Uuid::parse_str(rest).ok().map(|id| CallbackAction::Review { id, approve: false }),
+            "unsub" => Uuid::parse_str(rest).ok().map(CallbackAction::Unsubscribe),
+            "dlg" => Some(CallbackAction::Dialog(rest.to_string())),
+            _ => None,
+        }
+    }
+}
+
+/// Результат обработки нажатия: ответ платформе и, возможно, новое сообщение в чат
+pub struct CallbackOutcome {
+    pub answer: CallbackAnswer,
+    pub follow_up: Option<SendMessageRequest>,
+}
+
+impl CallbackOutcome {
+    fn notification(text: impl Into<String>) -> Self {
+        Self {
+            answer: CallbackAnswer { message: None, notification: Some(text.into()) },
+            follow_up: None,
+        }
+    }
+}
+
+/// `ctx` отсутствует, если платформа не прислала сообщение, к которому относится кнопка
+pub async fn handle(payload: &str, ctx: Option<&ChatContext>, state: &AppState) -> CallbackOutcome {
+    let Some(action) = CallbackAction::parse(payload) else {
+        return CallbackOutcome::notification(t!("button-outdated"));
+    };
+    analytics::record_command(action.analytics_name());
+
+    match action {
+        CallbackAction::Work(id) => CallbackOutcome {
+            answer: CallbackAnswer { message: None, notification: Some(t!("callback-opening-work")) },
+            follow_up: Some(commands::work_by_id(id, ctx, state).await),
+        },
+        CallbackAction::Download(id) => {
+            match commands::visible_work(id, ctx, state).await {
+                Ok(Some(work)) => match media::work_file(&work, state).await {
+                    Some(file) => CallbackOutcome {
+                        answer: CallbackAnswer { message: None, notification: Some(t!("callback-sending-file")) },
+                        follow_up: Some(
+                            SendMessageRequest::html(format!("📎 {}", formatting::bold(&work.title))).with_attachment(file),
+                        ),
+                    },
+                    None => CallbackOutcome::notification(t!("file-unavailable")),
+                },
+                Ok(None) => CallbackOutcome::notification(t!("callback-work-not-found")),
+                Err(e) => CallbackOutcome::notification(t!("error-generic", error = error_text(&e))),
+            }
+        }
+        // Следующая страница заменяет текущее сообщение с результатами
+        // Специальность группового чата в payload не хранится — применяется заново
+        CallbackAction::Search { page, filter } => {
+            let filter = match ctx {
+                Some(ctx) => group::with_chat_defaults(filter, ctx, state).await,
+                None => filter,
+            };
+            CallbackOutcome {
+                answer: CallbackAnswer {
+                    message: Some(commands::search(&filter, page, state).await),
+                    notification: None,
+                },
+                follow_up: None,
+            }
+        }
+        CallbackAction::Review { id, approve } => match ctx {
+            Some(ctx) => CallbackOutcome {
+                answer: CallbackAnswer { message: None, notification: Some(t!("callback-done")) },
+                follow_up: Some(review::review(id, approve, ctx, state).await),
+            },
+            None => CallbackOutcome::notification(t!("button-outdated")),
+        },
+        CallbackAction::Unsubscribe(id) => match ctx {
+            Some(ctx) => CallbackOutcome {
+                answer: CallbackAnswer {
+                    message: Some(subscriptions::unsubscribe_by_id(id, ctx, state).await),
+                    notification: None,
+                },
+                follow_up: None,
+            },
+            None => CallbackOutcome::notification(t!("button-outdated")),
+        },
+        // Шаги диалога заменяют сообщение с вариантами выбора
+        CallbackAction::Dialog(value) => match ctx {
+            Some(ctx) => CallbackOutcome {
+                answer: CallbackAnswer {
+                    message: Some(dialogs::handle_input(DialogInput::text(&value), ctx, state).await),
+                    notification: None,
+                },
+                follow_up: None,
+            },
+            None => CallbackOutcome::notification(t!("button-outdated")),
+        },
+        CallbackAction::Cancel => match ctx {
+            Some(ctx) => CallbackOutcome {
+                answer: CallbackAnswer {
+                    message: Some(dialogs::cancel(ctx, state).await),
+                    notification: None,
+                },
+                follow_up: None,
+            },
+            None => CallbackOutcome::notification(t!("button-outdated")),
+        },
+    }
+}
@@ -0,0 +1,319 @@
+use uuid::Uuid;
+
+use crate::{
+    core::{
+        models::{Work, WorkStatus, WorkType},
+        services::WorkService,
+    },
+    error::AppError,
+    i18n,
+    integrations::max::{formatting, Button, InlineKeyboard, MessageBuilder, SendMessageRequest},
+    state::AppState,
+    t,
+};
+
+use super::{
+    account, analytics, callbacks::CallbackAction, dialogs, error_text, group, media, registry, review, subscriptions,
+    ChatContext,
+};
+
+/// Количество работ на одной странице результатов поиска
+pub const SEARCH_PAGE_SIZE: u32 = 5;
+
+/// Разбор текста сообщения и выбор команды
+pub async fn dispatch(text: &str, ctx: &ChatContext, state: &AppState) -> SendMessageRequest {
+    let Some((command, args)) = registry::parse(text) else {
+        return SendMessageRequest::html(t!("unknown-command"));
+    };
+    analytics::record_command(command.name);
+
+    match command.name {
+        "cancel" => dialogs::cancel(ctx, state).await,
+        "browse" => dialogs::start_browse(ctx, state).await,
+        "submit" => dialogs::start_submit(ctx, state).await,
+        "link" => account::link(args, ctx, state).await,
+        "unlink" => account::unlink(ctx, state).await,
+        "whoami" => account::whoami(ctx, state).await,
+        "my" => review::my_works(ctx, state).await,
+        "subscriptions" => subscriptions::list(ctx, state).await,
+        "subscribe" => subscriptions::subscribe(args, ctx, state).await,
+        "unsubscribe" => subscriptions::unsubscribe(args, ctx, state).await,
+        "pending" => review::pending(ctx, state).await,
+        "approve" => review::review_command(args, true, ctx, state).await,
+        "reject" => review::review_command(args, false, ctx, state).await,
+        "start" => start(state),
+        "help" => help(state),
+        "filter" => group::filter(args, ctx, state).await,
+        "search" => {
+            let filter = group::with_chat_defaults(SearchFilter::by_query(args), ctx, state).await;
+            search(&filter, 1, state).await
+        }
+        "work" => work(args, ctx, state).await,
+        name => unreachable!("команда /{} есть в реестре, но не обрабатывается", name),
+    }
+}
+
+/// Клавиатура с кнопкой запуска мини-приложения (если оно настроено)
+fn mini_app_keyboard(state: &AppState) -> InlineKeyboard {
+    let row = state
+        .max_mini_app
+        .as_ref()
+        .map(|app| vec![Button::open_app(t!("button-open-app"), app.clone())])
+        .unwrap_or_default();
+
+    InlineKeyboard::new().row(row)
+}
+
+pub fn start(state: &AppState) -> SendMessageRequest {
+    let mut text = t!("start-text");
+
+    if state.max_mini_app.is_some() {
+        text.push_str(&format!("\n\n{}", t!("start-mini-app-hint")));
+    }
+
+    SendMessageRequest::html(text).with_keyboard(mini_app_keyboard(state))
+}
+
+pub fn help(state: &AppState) -> SendMessageRequest {
+    let lines = |reviewer_only: bool| {
+        registry::COMMANDS
+            .iter()
+            .filter(|c| c.reviewer_only == reviewer_only)
+            .map(registry::help_line)
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let mut text = format!(
+        "{}\n\n{}\n\n{}\n{}",
+        t!("help-title"),
+        lines(false),
+        t!("help-reviewers"),
+        lines(true)
+    );
+
+    if state.max_mini_app.is_some() {
+        text.push_str(&format!("\n\n{}", t!("help-mini-app-hint")));
+    }
+
+    SendMessageRequest::html(text).with_keyboard(mini_app_keyboard(state))
+}
+
+/// Фильтр для выдачи работ в боте
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchFilter {
+    pub query: Option<String>,
+    pub specialty: Option<String>,
+    pub year: Option<i32>,
+    pub work_type: Option<WorkType>,
+}
+
+impl SearchFilter {
+    pub fn by_query(query: &str) -> Self {
+        Self { query: Some(query.to_string()), ..Self::default() }
+    }
+
+    fn describe(&self) -> String {
+        if let Some(ref query) = self.query {
+            let query = t!("filter-query", query = formatting::escape(query));
+            return match self.specialty {
+                Some(ref specialty) => format!("{} ({})", query, t!("filter-specialty", specialty = formatting::escape(specialty))),
+                None => query,
+            };
+        }
+
+        let mut parts = Vec::new();
+        if let Some(ref specialty) = self.specialty {
+            parts.push(t!("filter-specialty", specialty = formatting::escape(specialty)));
+        }
+        if let Some(year) = self.year {
+            parts.push(t!("filter-year", year = year.to_string()));
+        }
+        if let Some(ref work_type) = self.work_type {
+            parts.push(format_work_type(work_type).to_lowercase());
+        }
+
+        if parts.is_empty() {
+            t!("filter-all")
+        } else {
+            format!("({})", parts.join(", "))
+        }
+    }
+}
+
+/// Страница результатов поиска с кнопками «Подробнее», «Скачать» и «Далее»
+pub async fn search(filter: &SearchFilter, page: u32, state: &AppState) -> SendMessageRequest {
+    if filter.query.as_deref() == Some("") {
+        return SendMessageRequest::html(t!("search-empty-query"));
+    }
+
+    let service = WorkService::new(state.pool.clone());
+    let works = match service
+        .search(
+            filter.query.as_deref(),
+            filter.specialty.as_deref(),
+            filter.work_type.map(|wt| wt.as_str()),
+            filter.year,
+            page,
+            SEARCH_PAGE_SIZE,
+        )
+        .await
+    {
+        Ok(works) => works,
+        Err(e) => return SendMessageRequest::html(t!("search-error", error = error_text(&e))),
+    };
+    if page == 1 {
+        analytics::record_search(filter.query.as_deref(), works.len());
+    }
+
+    if works.is_empty() {
+        let text = if page > 1 {
+            t!("search-nothing-more", filter = filter.describe())
+        } else {
+            t!("search-nothing", filter = filter.describe())
+        };
+        return SendMessageRequest::html(text);
+    }
+
+    let first = (page - 1) * SEARCH_PAGE_SIZE;
+    let mut text = format!("{}\n", t!("search-results", filter = filter.describe()));
+    let mut keyboard = InlineKeyboard::new();
+
+    for (i, work) in works.iter().enumerate() {
+        let n = first + i as u32 + 1;
+        text.push_str(&format!(
+            "\n{}. {}\n{}, {} — {}\n",
+            n,
+            formatting::bold(&work.title),
+            formatting::escape(&work.author_name),
+            work.year,
+            format_work_type(&work.work_type)
+        ));
+
+        keyboard = keyboard.row(vec![
+            Button::callback(t!("button-details", n = n), CallbackAction::Work(work.id).encode()),
+            download_button(work),
+        ]);
+    }
+
+    // Полная страница — вероятно, есть продолжение
+    if works.len() as u32 == SEARCH_PAGE_SIZE {
+        keyboard = keyboard.row(vec![Button::callback(
+            t!("search-next"),
+            CallbackAction::Search { page: page + 1, filter: filter.clone() }.encode(),
+        )]);
+    }
+
+    SendMessageRequest::html(text).with_keyboard(keyboard)
+}
+
+pub async fn work(id_str: &str, ctx: &ChatContext, state: &AppState) -> SendMessageRequest {
+    if id_str.is_empty() {
+        return SendMessageRequest::html(t!("work-id-missing"));
+    }
+
+    // Попытка распарсить UUID
+    let id = match Uuid::parse_str(id_str) {
+        Ok(id) => id,
+        Err(_) => return SendMessageRequest::html(t!("work-id-invalid")),
+    };
+
+    work_by_id(id, Some(ctx), state).await
+}
+
+/// Карточка работы по ID
+pub async fn work_by_id(id: Uuid, ctx: Option<&ChatContext>, state: &AppState) -> SendMessageRequest {
+    match visible_work(id, ctx, state).await {
+        Ok(Some(work)) => work_card(&work, state).await,
+        Ok(None) => SendMessageRequest::html(t!("work-not-found-id", id = id.to_string())),
+        Err(e) => SendMessageRequest::html(t!("work-load-error", error = error_text(&e))),
+    }
+}
+
+/// Работа, которую может видеть пользователь чата: неопубликованные — только приславший её
+/// и проверяющие. Без `ctx` доступны только опубликованные
+pub async fn visible_work(id: Uuid, ctx: Option<&ChatContext>, state: &AppState) -> Result<Option<Work>, AppError> {
+    let service = WorkService::new(state.pool.clone());
+    let Some(work) = service.get_by_id(id).await? else {
+        return Ok(None);
+    };
+
+    let visible = match ctx {
+        Some(ctx) if work.status != WorkStatus::Published => {
+            let user = account::linked_user(ctx, state).await;
+            work.is_visible_to(Some(ctx.user_id), user.as_ref())
+        }
+        _ => work.is_visible_to(None, None),
+    };
+    Ok(visible.then_some(work))
+}
+
+/// Карточка работы с обложкой и файлом во вложениях
+async fn work_card(work: &Work, state: &AppState) -> SendMessageRequest {
+    let thumbnail = media::work_thumbnail(work, state).await;
+    let file = media::work_file(work, state).await;
+
+    let mut card = MessageBuilder::new()
+        .raw("📄 ")
+        .bold(&work.title)
+        .line()
+        .line()
+        .raw("📌 ")
+        .field(&t!("work-field-type"), &format_work_type(&work.work_type))
+        .raw("🎓 ")
+        .field(&t!("work-field-specialty"), &work.specialty)
+        .raw("👨‍🎓 ")
+        .field(&t!("work-field-author"), &work.author_name)
+        .raw("👨‍🏫 ")
+        .field(&t!("work-field-supervisor"), &work.supervisor_name)
+        .raw("📅 ")
+        .field(&t!("work-field-year"), &work.year.to_string())
+        .line();
+
+    if let Some(ref ann) = work.annotation {
+        card = card.raw(&format!("📝 <b>{}:</b>\n", t!("work-field-annotation"))).text(ann).line().line();
+    }
+    if let Some(ref kw) = work.keywords {
+        card = card.raw("🔑 ").field(&t!("work-field-keywords"), kw).line();
+    }
+
+    let card = if media::is_remote(&work.file_path) {
+        card.raw(&format!("🔗 <b>{}:</b> ", t!("work-field-download"))).link(&work.file_path, &work.file_path)
+    } else if file.is_some() {
+        card.raw(&t!("work-file-attached"))
+    } else {
+        card.raw(&t!("file-unavailable"))
+    };
+    let text = card.build();
+
+    let mut message = SendMessageRequest::html(text);
+    for attachment in thumbnail.into_iter().chain(file) {
+        message = message.with_attachment(attachment);
+    }
+
+    // Файл уже во вложении — кнопка нужна только для внешней ссылки
+    if media::is_remote(&work.file_path) {
+        message = message.with_keyboard(InlineKeyboard::new().row(vec![download_button(work)]));
+    }
+
+    message
+}
+
+/// Кнопка «Скачать»: прямая ссылка, если файл доступен по URL, иначе callback
+fn download_button(work: &Work) -> Button {
+    if media::is_remote(&work.file_path) {
+        Button::link(t!("button-download"), work.file_path.clone())
+    } else {
+        Button::callback(t!("button-download"), CallbackAction::Download(work.id).encode())
+    }
+}
+
+/// Название типа работы из каталога
+pub fn format_work_type(work_type: &WorkType) -> String {
+    i18n::message(i18n::current(), &format!("work-type-{}", work_type.as_str()), None)
+}
+
+/// Название статуса работы из каталога
+pub fn format_work_status(status: &WorkStatus) -> String {
+    i18n::message(i18n::current(), &format!("work-status-{}", status.as_str()), None)
+}
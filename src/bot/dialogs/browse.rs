@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::services::WorkService,
+    integrations::max::SendMessageRequest,
+    state::AppState,
+    t,
+};
+
+use super::{
+    abandon, any_button, cancel_button, choices_keyboard, finish, is_any, parse_work_type, save, work_type_choices,
+    DialogState,
+};
+use crate::bot::{
+    commands::{self, SearchFilter},
+    error_text, group, ChatContext,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BrowseStep {
+    Specialty,
+    Year,
+    WorkType,
+}
+
+/// Команда /browse — начало пошагового подбора работ.
+/// Если у группового чата задана специальность, подбор начинается сразу с года.
+pub async fn start_browse(ctx: &ChatContext, state: &AppState) -> SendMessageRequest {
+    let specialty = group::default_specialty(ctx, state).await;
+    let step = if specialty.is_some() { BrowseStep::Year } else { BrowseStep::Specialty };
+
+    // Новый диалог заменяет незаконченный
+    abandon(ctx, state).await;
+    let dialog = DialogState::Browse { step, specialty: specialty.clone(), year: None };
+    if let Err(e) = save(ctx, &dialog, state).await {
+        return SendMessageRequest::html(t!("dialog-start-failed", error = error_text(&e)));
+    }
+
+    match specialty {
+        Some(specialty) => ask_year(Some(&specialty), state).await,
+        None => ask_specialty(state).await,
+    }
+}
+
+pub(super) async fn handle_step(
+    step: BrowseStep,
+    specialty: Option<String>,
+    year: Option<i32>,
+    input: &str,
+    ctx: &ChatContext,
+    state: &AppState,
+) -> SendMessageRequest {
+    match step {
+        BrowseStep::Specialty => {
+            let specialty = (!is_any(input)).then(|| input.to_string());
+            let dialog = DialogState::Browse { step: BrowseStep::Year, specialty: specialty.clone(), year: None };
+            if let Err(e) = save(ctx, &dialog, state).await {
+                return SendMessageRequest::html(t!("error-generic", error = error_text(&e)));
+            }
+            ask_year(specialty.as_deref(), state).await
+        }
+        BrowseStep::Year => {
+            let year = if is_any(input) {
+                None
+            } else {
+                match input.parse::<i32>() {
+                    Ok(year) => Some(year),
+                    Err(_) => return SendMessageRequest::html(t!("browse-year-invalid")),
+                }
+            };
+            let dialog = DialogState::Browse { step: BrowseStep::WorkType, specialty, year };
+            if let Err(e) = save(ctx, &dialog, state).await {
+                return SendMessageRequest::html(t!("error-generic", error = error_text(&e)));
+            }
+            ask_work_type(&t!("browse-work-type"))
+        }
+        BrowseStep::WorkType => {
+            let work_type = if is_any(input) {
+                None
+            } else {
+                match parse_work_type(input) {
+                    Some(work_type) => Some(work_type),
+                    None => return ask_work_type(&t!("browse-work-type-invalid")),
+                }
+            };
+
+            finish(ctx, state).await;
+
+            let filter = SearchFilter { query: None, specialty, year, work_type };
+            commands::search(&filter, 1, state).await
+        }
+    }
+}
+
+async fn ask_specialty(state: &AppState) -> SendMessageRequest {
+    let service = WorkService::new(state.pool.clone());
+    let specialties = service.list_specialties().await.unwrap_or_default();
+
+    let keyboard = choices_keyboard(specialties.into_iter().map(|s| (s.clone(), s)), 1)
+        .row(vec![any_button(&t!("dialog-any-specialty")), cancel_button()]);
+
+    SendMessageRequest::html(t!("browse-specialty")).with_keyboard(keyboard)
+}
+
+async fn ask_year(specialty: Option<&str>, state: &AppState) -> SendMessageRequest {
+    let service = WorkService::new(state.pool.clone());
+    let years = service.list_years(specialty).await.unwrap_or_default();
+
+    let keyboard = choices_keyboard(years.into_iter().map(|y| (y.to_string(), y.to_string())), 4)
+        .row(vec![any_button(&t!("dialog-any")), cancel_button()]);
+
+    SendMessageRequest::html(t!("browse-year")).with_keyboard(keyboard)
+}
+
+fn ask_work_type(text: &str) -> SendMessageRequest {
+    let keyboard = choices_keyboard(work_type_choices(), 2).row(vec![any_button(&t!("dialog-any")), cancel_button()]);
+
+    SendMessageRequest::html(text).with_keyboard(keyboard)
+}
@@ -0,0 +1,221 @@
+pub mod browse;
+pub mod submit;
+
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::{
+    core::{models::WorkType, services::ConversationService},
+    error::AppError,
+    i18n::{self, Locale},
+    infrastructure::storage::FileStorage,
+    integrations::max::{Button, FileAttachment, InlineKeyboard, SendMessageRequest},
+    state::AppState,
+    t,
+};
+
+use super::{callbacks::CallbackAction, commands::format_work_type, error_text, ChatContext};
+
+pub use browse::start_browse;
+pub use submit::start_submit;
+
+/// Период очистки просроченных диалогов
+const PURGE_INTERVAL_SECS: u64 = 5 * 60;
+
+/// Значение кнопки «Любой» на шагах выбора
+const ANY: &str = "*";
+
+/// Сколько вариантов показывать кнопками на одном шаге
+const MAX_CHOICES: usize = 30;
+
+/// Состояние многошагового диалога, хранится в `bot_conversations.state`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "dialog", rename_all = "snake_case")]
+pub enum DialogState {
+    /// Подбор работ: специальность → год → тип → результаты
+    Browse {
+        step: browse::BrowseStep,
+        specialty: Option<String>,
+        year: Option<i32>,
+    },
+    /// Отправка работы в архив
+    Submit {
+        step: submit::SubmitStep,
+        draft: submit::SubmissionDraft,
+    },
+}
+
+/// Ответ пользователя на шаге диалога
+pub struct DialogInput<'a> {
+    pub text: &'a str,
+    pub file: Option<&'a FileAttachment>,
+}
+
+impl<'a> DialogInput<'a> {
+    pub fn text(text: &'a str) -> Self {
+        Self { text, file: None }
+    }
+}
+
+fn conversations(state: &AppState) -> ConversationService {
+    ConversationService::new(state.pool.clone(), Duration::seconds(state.bot_dialog_ttl_secs as i64))
+}
+
+/// Фоновая задача: периодически удаляет диалоги, срок ожидания которых истёк, вместе с уже
+/// загруженными в них файлами; останавливается по `shutdown`
+pub async fn purge_expired_task(pool: PgPool, storage: FileStorage, shutdown: CancellationToken) {
+    // TTL не важен для удаления — используется срок, сохранённый в каждой записи
+    let service = ConversationService::new(pool, Duration::zero());
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(PURGE_INTERVAL_SECS));
+
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = interval.tick() => {}
+        }
+        match service.purge_expired().await {
+            Ok(states) if states.is_empty() => {}
+            Ok(states) => {
+                info!("🧹 Удалено просроченных диалогов: {}", states.len());
+                for value in states {
+                    discard(value, &storage).await;
+                }
+            }
+            Err(e) => warn!("Ошибка очистки просроченных диалогов: {}", e),
+        }
+    }
+}
+
+fn is_any(input: &str) -> bool {
+    matches!(input.to_lowercase().as_str(), ANY | "-" | "любая" | "любой" | "все" | "any" | "all")
+}
+
+fn any_button(text: &str) -> Button {
+    Button::callback(text, CallbackAction::Dialog(ANY.to_string()).encode())
+}
+
+fn cancel_button() -> Button {
+    Button::callback(t!("dialog-cancel-button"), CallbackAction::Cancel.encode())
+}
+
+/// Клавиатура из вариантов ответа, по `per_row` кнопок в строке
+fn choices_keyboard<I>(choices: I, per_row: usize) -> InlineKeyboard
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    let buttons: Vec<Button> = choices
+        .into_iter()
+        .take(MAX_CHOICES)
+        .map(|(text, value)| Button::callback(text, CallbackAction::Dialog(value).encode()))
+        .collect();
+
+    let mut keyboard = InlineKeyboard::new();
+    for chunk in buttons.chunks(per_row) {
+        keyboard = keyboard.row(chunk.to_vec());
+    }
+    keyboard
+}
+
+fn work_type_choices() -> impl Iterator<Item = (String, String)> {
+    WorkType::ALL
+        .into_iter()
+        .map(|wt| (format_work_type(&wt), wt.as_str().to_string()))
+}
+
+/// Тип работы по коду (`essay`) или по названию на любом языке каталога (`эссе`, `Essay`)
+fn parse_work_type(input: &str) -> Option<WorkType> {
+    let input = input.to_lowercase();
+    WorkType::parse(&input).or_else(|| {
+        WorkType::ALL.into_iter().find(|wt| {
+            let key = format!("work-type-{}", wt.as_str());
+            Locale::ALL.into_iter().any(|locale| i18n::message(locale, &key, None).to_lowercase() == input)
+        })
+    })
+}
+
+/// Есть ли у пользователя незавершённый (в том числе просроченный) диалог в этом чате
+pub async fn has_conversation(ctx: &ChatContext, state: &AppState) -> bool {
+    match conversations(state).get(ctx.chat_id, ctx.user_id).await {
+        Ok(conversation) => conversation.is_some(),
+        Err(e) => {
+            warn!("Не удалось загрузить диалог chat_id={}: {}", ctx.chat_id, e);
+            false
+        }
+    }
+}
+
+async fn save(ctx: &ChatContext, dialog: &DialogState, state: &AppState) -> Result<(), AppError> {
+    let value = serde_json::to_value(dialog).map_err(|e| AppError::Internal(e.to_string()))?;
+    conversations(state).save(ctx.chat_id, ctx.user_id, &value).await.map(|_| ())
+}
+
+/// Завершение диалога; возвращает его последнее состояние
+async fn finish(ctx: &ChatContext, state: &AppState) -> Option<serde_json::Value> {
+    conversations(state)
+        .finish(ctx.chat_id, ctx.user_id)
+        .await
+        .unwrap_or_else(|e| {
+            warn!("Не удалось завершить диалог chat_id={}: {}", ctx.chat_id, e);
+            None
+        })
+}
+
+/// Завершение незаконченного диалога: файл, уже загруженный на шаге отправки работы, удаляется
+async fn abandon(ctx: &ChatContext, state: &AppState) {
+    if let Some(value) = finish(ctx, state).await {
+        discard(value, &state.storage).await;
+    }
+}
+
+/// Удаление файла, загруженного в брошенном диалоге
+async fn discard(value: serde_json::Value, storage: &FileStorage) {
+    if let Ok(DialogState::Submit { draft, .. }) = serde_json::from_value(value) {
+        submit::discard_upload(&draft, storage).await;
+    }
+}
+
+/// Команда /cancel
+pub async fn cancel(ctx: &ChatContext, state: &AppState) -> SendMessageRequest {
+    match conversations(state).finish(ctx.chat_id, ctx.user_id).await {
+        Ok(Some(value)) => {
+            discard(value, &state.storage).await;
+            SendMessageRequest::html(t!("dialog-cancelled"))
+        }
+        Ok(None) => SendMessageRequest::html(t!("dialog-none")),
+        Err(e) => SendMessageRequest::html(t!("error-generic", error = error_text(&e))),
+    }
+}
+
+/// Обработка ответа пользователя (текстом, файлом или кнопкой) на текущем шаге диалога
+pub async fn handle_input(input: DialogInput<'_>, ctx: &ChatContext, state: &AppState) -> SendMessageRequest {
+    let conversation = match conversations(state).get(ctx.chat_id, ctx.user_id).await {
+        Ok(Some(conversation)) => conversation,
+        Ok(None) => return SendMessageRequest::html(t!("dialog-finished")),
+        Err(e) => return SendMessageRequest::html(t!("error-generic", error = error_text(&e))),
+    };
+
+    if conversation.is_expired() {
+        abandon(ctx, state).await;
+        return SendMessageRequest::html(t!("dialog-expired"));
+    }
+
+    let dialog: DialogState = match serde_json::from_value(conversation.state) {
+        Ok(dialog) => dialog,
+        Err(e) => {
+            warn!("Повреждённое состояние диалога chat_id={}: {}", ctx.chat_id, e);
+            abandon(ctx, state).await;
+            return SendMessageRequest::html(t!("dialog-broken"));
+        }
+    };
+
+    let input = DialogInput { text: input.text.trim(), file: input.file };
+    match dialog {
+        DialogState::Browse { step, specialty, year } => {
+            browse::handle_step(step, specialty, year, input.text, ctx, state).await
+        }
+        DialogState::Submit { step, draft } => submit::handle_step(step, draft, input, ctx, state).await,
+    }
+}
@@ -0,0 +1,344 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::{
+    core::{
+        models::{WorkCreateDto, WorkStatus, WorkType},
+        services::WorkService,
+    },
+    infrastructure::storage::FileStorage,
+    integrations::max::{
+        formatting, Button, FileAttachment, InlineKeyboard, MaxApiError, MessageBuilder, SendMessageRequest,
+    },
+    state::AppState,
+    t,
+};
+
+use super::{
+    abandon, cancel_button, choices_keyboard, finish, parse_work_type, save, work_type_choices, DialogInput,
+    DialogState,
+};
+use crate::bot::{
+    callbacks::CallbackAction,
+    commands::{format_work_status, format_work_type},
+    error_text, ChatContext,
+};
+
+/// Допустимые расширения файлов работ
+const ALLOWED_EXTENSIONS: &[&str] = &["pdf", "doc", "docx", "odt", "rtf", "ppt", "pptx", "odp", "zip"];
+
+/// Значения кнопок на шаге подтверждения
+const SEND_FOR_REVIEW: &str = "submit";
+const SAVE_DRAFT: &str = "draft";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmitStep {
+    Title,
+    WorkType,
+    Specialty,
+    Year,
+    Supervisor,
+    File,
+    Confirm,
+}
+
+/// Данные работы, собранные на шагах диалога
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubmissionDraft {
+    pub author_name: String,
+    pub title: Option<String>,
+    pub work_type: Option<WorkType>,
+    pub specialty: Option<String>,
+    pub year: Option<i32>,
+    pub supervisor_name: Option<String>,
+    pub file_name: Option<String>,
+    /// Путь к файлу в хранилище: файл скачивается сразу, пока ссылка платформы действительна
+    pub file_path: Option<String>,
+}
+
+/// Команда /submit — начало отправки работы в архив
+pub async fn start_submit(ctx: &ChatContext, state: &AppState) -> SendMessageRequest {
+    let draft = SubmissionDraft { author_name: ctx.user_name.clone(), ..SubmissionDraft::default() };
+    // Новый диалог заменяет незаконченный
+    abandon(ctx, state).await;
+    let dialog = DialogState::Submit { step: SubmitStep::Title, draft };
+    if let Err(e) = save(ctx, &dialog, state).await {
+        return SendMessageRequest::html(t!("dialog-start-failed", error = error_text(&e)));
+    }
+
+    SendMessageRequest::html(t!("submit-start", author = formatting::escape(&ctx.user_name)))
+        .with_keyboard(InlineKeyboard::new().row(vec![cancel_button()]))
+}
+
+pub(super) async fn handle_step(
+    step: SubmitStep,
+    mut draft: SubmissionDraft,
+    input: DialogInput<'_>,
+    ctx: &ChatContext,
+    state: &AppState,
+) -> SendMessageRequest {
+    let text = input.text;
+
+    let (next, reply) = match step {
+        SubmitStep::Title => match non_empty(text, 500) {
+            Ok(title) => {
+                draft.title = Some(title);
+                (SubmitStep::WorkType, ask_work_type())
+            }
+            Err(e) => return retry(&e),
+        },
+        SubmitStep::WorkType => match parse_work_type(text) {
+            Some(work_type) => {
+                draft.work_type = Some(work_type);
+                (SubmitStep::Specialty, ask_specialty(state).await)
+            }
+            None => return ask_work_type(),
+        },
+        SubmitStep::Specialty => match non_empty(text, 200) {
+            Ok(specialty) => {
+                draft.specialty = Some(specialty);
+                (SubmitStep::Year, ask_year())
+            }
+            Err(e) => return retry(&e),
+        },
+        SubmitStep::Year => match text.parse::<i32>() {
+            Ok(year) if (1900..=2100).contains(&year) => {
+                draft.year = Some(year);
+                (SubmitStep::Supervisor, ask_supervisor())
+            }
+            _ => return retry(&t!("submit-year-invalid")),
+        },
+        SubmitStep::Supervisor => match non_empty(text, 300) {
+            Ok(supervisor) => {
+                draft.supervisor_name = Some(supervisor);
+                (SubmitStep::File, ask_file(state))
+            }
+            Err(e) => return retry(&e),
+        },
+        SubmitStep::File => match input.file {
+            Some(file) => {
+                if let Err(e) = check_file(file, state) {
+                    return retry(&e);
+                }
+                let file_path = match store_file(file, state).await {
+                    Ok(path) => path,
+                    Err(reply) => return reply,
+                };
+                draft.file_name = Some(file.filename.clone());
+                draft.file_path = Some(file_path);
+                (SubmitStep::Confirm, confirmation(&draft))
+            }
+            None => return ask_file(state),
+        },
+        SubmitStep::Confirm => {
+            let status = match text.to_lowercase().as_str() {
+                SEND_FOR_REVIEW | "отправить" | "send" => WorkStatus::Submitted,
+                SAVE_DRAFT | "черновик" => WorkStatus::Draft,
+                _ => return confirmation(&draft),
+            };
+
+            // Диалог завершается до создания работы: повторное нажатие кнопки или повторно
+            // доставленный callback его уже не найдут и работу не продублируют
+            if finish(ctx, state).await.is_none() {
+                return SendMessageRequest::html(t!("dialog-finished"));
+            }
+            return create_work(draft, status, ctx, state).await;
+        }
+    };
+
+    let dialog = DialogState::Submit { step: next, draft };
+    if let Err(e) = save(ctx, &dialog, state).await {
+        if let DialogState::Submit { ref draft, .. } = dialog {
+            discard_upload(draft, &state.storage).await;
+        }
+        return SendMessageRequest::html(t!("error-generic", error = error_text(&e)));
+    }
+
+    reply
+}
+
+fn non_empty(text: &str, max_len: usize) -> Result<String, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        Err(t!("submit-value-empty"))
+    } else if text.chars().count() > max_len {
+        Err(t!("submit-value-too-long"))
+    } else {
+        Ok(text.to_string())
+    }
+}
+
+fn retry(error: &str) -> SendMessageRequest {
+    SendMessageRequest::html(t!("submit-retry", error = error))
+        .with_keyboard(InlineKeyboard::new().row(vec![cancel_button()]))
+}
+
+fn ask_work_type() -> SendMessageRequest {
+    SendMessageRequest::html(t!("submit-work-type"))
+        .with_keyboard(choices_keyboard(work_type_choices(), 2).row(vec![cancel_button()]))
+}
+
+async fn ask_specialty(state: &AppState) -> SendMessageRequest {
+    let service = WorkService::new(state.pool.clone());
+    let specialties = service.list_specialties().await.unwrap_or_default();
+
+    SendMessageRequest::html(t!("submit-specialty"))
+        .with_keyboard(choices_keyboard(specialties.into_iter().map(|s| (s.clone(), s)), 1).row(vec![cancel_button()]))
+}
+
+fn ask_year() -> SendMessageRequest {
+    SendMessageRequest::html(t!("submit-year"))
+        .with_keyboard(InlineKeyboard::new().row(vec![cancel_button()]))
+}
+
+fn ask_supervisor() -> SendMessageRequest {
+    SendMessageRequest::html(t!("submit-supervisor"))
+        .with_keyboard(InlineKeyboard::new().row(vec![cancel_button()]))
+}
+
+fn ask_file(state: &AppState) -> SendMessageRequest {
+    SendMessageRequest::html(t!(
+        "submit-file",
+        formats = ALLOWED_EXTENSIONS.join(", "),
+        size = state.max_upload_size_mb
+    ))
+    .with_keyboard(InlineKeyboard::new().row(vec![cancel_button()]))
+}
+
+fn check_file(file: &FileAttachment, state: &AppState) -> Result<(), String> {
+    let extension = Path::new(&file.filename)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+
+    if !ALLOWED_EXTENSIONS.contains(&extension.as_str()) {
+        return Err(t!("submit-file-format", formats = ALLOWED_EXTENSIONS.join(", ")));
+    }
+    // Без размера файл не проверить до скачивания — такие вложения не принимаются
+    if file.size == 0 {
+        return Err(t!("submit-file-size-unknown"));
+    }
+    if file.size > max_upload_size(state) {
+        return Err(t!("submit-file-size", size = state.max_upload_size_mb));
+    }
+
+    Ok(())
+}
+
+fn max_upload_size(state: &AppState) -> u64 {
+    state.max_upload_size_mb * 1024 * 1024
+}
+
+/// Скачивание файла из МАКС в хранилище на шаге выбора файла, пока временная ссылка действительна.
+/// Размер проверяется ещё раз при скачивании: заявленному в вложении верить нельзя.
+async fn store_file(file: &FileAttachment, state: &AppState) -> Result<String, SendMessageRequest> {
+    let data = match state.max_api.download(&file.payload.url, max_upload_size(state)).await {
+        Ok(data) => data,
+        Err(MaxApiError::TooLarge { .. }) => {
+            return Err(SendMessageRequest::html(t!("submit-too-large", size = state.max_upload_size_mb)))
+        }
+        Err(e) => {
+            warn!("Не удалось скачать файл работы: {}", e);
+            return Err(SendMessageRequest::html(t!("submit-download-failed")));
+        }
+    };
+
+    state.storage.save(&file.filename, &data).await.map_err(|e| {
+        warn!("Не удалось сохранить файл работы: {}", e);
+        SendMessageRequest::html(t!("submit-store-failed"))
+    })
+}
+
+/// Удаление файла, загруженного в незавершённом диалоге
+pub(super) async fn discard_upload(draft: &SubmissionDraft, storage: &FileStorage) {
+    if let Some(ref path) = draft.file_path {
+        if let Err(e) = storage.delete(path).await {
+            warn!("Не удалось удалить файл {}: {}", path, e);
+        }
+    }
+}
+
+fn confirmation(draft: &SubmissionDraft) -> SendMessageRequest {
+    let text = MessageBuilder::new()
+        .raw(&t!("submit-confirm-title"))
+        .line()
+        .line()
+        .raw("📄 ")
+        .field(&t!("work-field-title"), draft.title.as_deref().unwrap_or_default())
+        .raw("📌 ")
+        .field(&t!("work-field-type"), &draft.work_type.as_ref().map(format_work_type).unwrap_or_default())
+        .raw("🎓 ")
+        .field(&t!("work-field-specialty"), draft.specialty.as_deref().unwrap_or_default())
+        .raw("👨‍🎓 ")
+        .field(&t!("work-field-author"), &draft.author_name)
+        .raw("👨‍🏫 ")
+        .field(&t!("work-field-supervisor"), draft.supervisor_name.as_deref().unwrap_or_default())
+        .raw("📅 ")
+        .field(&t!("work-field-year"), &draft.year.map(|y| y.to_string()).unwrap_or_default())
+        .raw("📎 ")
+        .field(&t!("work-field-file"), draft.file_name.as_deref().unwrap_or_default())
+        .build();
+
+    let keyboard = InlineKeyboard::new()
+        .row(vec![Button::callback(t!("submit-send-button"), CallbackAction::Dialog(SEND_FOR_REVIEW.to_string()).encode())])
+        .row(vec![Button::callback(t!("submit-draft-button"), CallbackAction::Dialog(SAVE_DRAFT.to_string()).encode())])
+        .row(vec![cancel_button()]);
+
+    SendMessageRequest::html(text.trim_end()).with_keyboard(keyboard)
+}
+
+/// Создание работы из файла, уже сохранённого в хранилище
+async fn create_work(
+    draft: SubmissionDraft,
+    status: WorkStatus,
+    ctx: &ChatContext,
+    state: &AppState,
+) -> SendMessageRequest {
+    let (Some(title), Some(work_type), Some(specialty), Some(year), Some(supervisor_name), Some(file_path)) = (
+        draft.title,
+        draft.work_type,
+        draft.specialty,
+        draft.year,
+        draft.supervisor_name,
+        draft.file_path,
+    ) else {
+        return SendMessageRequest::html(t!("submit-incomplete"));
+    };
+
+    let dto = WorkCreateDto {
+        title,
+        work_type,
+        specialty,
+        author_name: draft.author_name,
+        supervisor_name,
+        year,
+        annotation: None,
+        keywords: None,
+        file_path: file_path.clone(),
+        thumbnail_path: None,
+        status,
+        submitter_max_user_id: Some(ctx.user_id),
+    };
+
+    let service = WorkService::new(state.pool.clone());
+    match service.create(dto).await {
+        Ok(work) => {
+            info!("📥 Работа {} создана через бота пользователем {}", work.id, ctx.user_id);
+            SendMessageRequest::html(t!(
+                "submit-saved",
+                id = work.id.to_string(),
+                status = format_work_status(&work.status)
+            ))
+        }
+        Err(e) => {
+            if let Err(e) = state.storage.delete(&file_path).await {
+                warn!("Не удалось удалить файл {}: {}", file_path, e);
+            }
+            SendMessageRequest::html(t!("submit-save-failed", error = error_text(&e)))
+        }
+    }
+}
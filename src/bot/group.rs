@@ -0,0 +1,148 @@
+//! Бот в групповых чатах: отвечает только на обращённые к нему сообщения (`/search@бот`
+//! или упоминание), а поиск ограничивается специальностью, которую задали администраторы чата.
+
+use tracing::warn;
+
+use crate::{
+    core::services::ChatSettingsService,
+    error::AppError,
+    integrations::max::{
+        formatting,
+        models::{BotInfo, Markup, MessageBody},
+        MaxApiError, SendMessageRequest,
+    },
+    state::AppState,
+    t,
+};
+
+use super::{
+    analytics,
+    commands::{self, SearchFilter},
+    error_text, registry, ChatContext,
+};
+
+/// Значения аргумента /filter, сбрасывающие специальность чата
+const RESET_WORDS: &[&str] = &["off", "-", "нет", "сброс", "сбросить", "reset"];
+
+/// Текст сообщения группы, обращённого к боту, без упоминания бота.
+/// `None` — сообщение адресовано не боту, и отвечать на него не нужно.
+pub fn addressed_text(body: &MessageBody, bot: &BotInfo) -> Option<String> {
+    let text = body.text.as_deref().unwrap_or_default();
+
+    // Команда с именем бота: /search@archive_bot нейросети
+    if text.trim_start().starts_with('/') {
+        let head = text.split_whitespace().next().unwrap_or_default();
+        let (_, username) = head.split_once('@')?;
+        return is_bot_username(username, bot).then(|| text.trim().to_string());
+    }
+
+    // Упоминание из разметки сообщения
+    let mention = body.markup.iter().flatten().find_map(|markup| match markup {
+        Markup::UserMention { from, length, user_id, user_link } => {
+            let is_bot = *user_id == Some(bot.user_id)
+                || user_link.as_deref().is_some_and(|link| is_bot_username(link, bot));
+            is_bot.then_some((*from, *length))
+        }
+        Markup::Other => None,
+    });
+    if let Some((from, length)) = mention {
+        let rest: String = text.chars().take(from).chain(text.chars().skip(from + length)).collect();
+        return Some(rest.trim().to_string());
+    }
+
+    // Упоминание текстом: «@archive_bot нейросети»
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let position = words
+        .iter()
+        .position(|word| word.trim_end_matches([',', ':', '.', '!', '?']).strip_prefix('@').is_some_and(|u| is_bot_username(u, bot)))?;
+
+    let rest: Vec<&str> = words.iter().enumerate().filter(|(i, _)| *i != position).map(|(_, w)| *w).collect();
+    Some(rest.join(" "))
+}
+
+fn is_bot_username(username: &str, bot: &BotInfo) -> bool {
+    let username = username.trim_start_matches('@');
+    bot.username.as_deref().is_some_and(|own| own.eq_ignore_ascii_case(username))
+}
+
+/// Ответ на обращение к боту в группе. Упоминание без команды — справка или поиск по тексту.
+pub async fn dispatch(text: &str, ctx: &ChatContext, state: &AppState) -> SendMessageRequest {
+    if text.is_empty() {
+        analytics::record_command("help");
+        return commands::help(state);
+    }
+
+    if registry::parse(text).is_some() {
+        return commands::dispatch(text, ctx, state).await;
+    }
+
+    analytics::record_command("search");
+    let filter = with_chat_defaults(SearchFilter::by_query(text), ctx, state).await;
+    commands::search(&filter, 1, state).await
+}
+
+/// Фильтр поиска с учётом специальности группового чата по умолчанию
+pub async fn with_chat_defaults(mut filter: SearchFilter, ctx: &ChatContext, state: &AppState) -> SearchFilter {
+    if ctx.is_group && filter.specialty.is_none() {
+        filter.specialty = default_specialty(ctx, state).await;
+    }
+    filter
+}
+
+/// Специальность группового чата по умолчанию; в личных диалогах — `None`
+pub async fn default_specialty(ctx: &ChatContext, state: &AppState) -> Option<String> {
+    if !ctx.is_group {
+        return None;
+    }
+
+    let service = ChatSettingsService::new(state.pool.clone());
+    match service.default_specialty(ctx.chat_id).await {
+        Ok(specialty) => specialty,
+        Err(e) => {
+            warn!("Не удалось получить настройки чата {}: {}", ctx.chat_id, e);
+            None
+        }
+    }
+}
+
+/// Команда /filter — специальность чата по умолчанию; менять её могут только администраторы чата
+pub async fn filter(args: &str, ctx: &ChatContext, state: &AppState) -> SendMessageRequest {
+    if !ctx.is_group {
+        return SendMessageRequest::html(t!("chat-filter-group-only"));
+    }
+
+    if args.is_empty() {
+        return match default_specialty(ctx, state).await {
+            Some(specialty) => SendMessageRequest::html(t!("chat-filter-current", specialty = formatting::escape(&specialty))),
+            None => SendMessageRequest::html(t!("chat-filter-none")),
+        };
+    }
+
+    match is_chat_admin(ctx, state).await {
+        Ok(true) => {}
+        Ok(false) => return SendMessageRequest::html(t!("chat-filter-admins-only")),
+        Err(e) => {
+            let error = AppError::ServiceUnavailable(e.to_string());
+            return SendMessageRequest::html(t!("error-generic", error = error_text(&error)));
+        }
+    }
+
+    let specialty = (!RESET_WORDS.contains(&args.to_lowercase().as_str())).then_some(args);
+    let service = ChatSettingsService::new(state.pool.clone());
+    match service.set_default_specialty(ctx.chat_id, specialty, ctx.user_id).await {
+        Ok(settings) => match settings.default_specialty {
+            Some(specialty) => SendMessageRequest::html(t!("chat-filter-set", specialty = formatting::escape(&specialty))),
+            None => SendMessageRequest::html(t!("chat-filter-cleared")),
+        },
+        Err(e) => SendMessageRequest::html(t!("error-generic", error = error_text(&e))),
+    }
+}
+
+/// Является ли автор сообщения владельцем или администратором чата
+async fn is_chat_admin(ctx: &ChatContext, state: &AppState) -> Result<bool, MaxApiError> {
+    let members = state.max_api.get_chat_members_by_id(ctx.chat_id, &[ctx.user_id]).await?;
+    Ok(members
+        .members
+        .iter()
+        .any(|m| m.user_id == ctx.user_id && (m.is_admin || m.is_owner)))
+}
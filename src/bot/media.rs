@@ -0,0 +1,143 @@
+use std::path::Path;
+
+use tracing::{debug, warn};
+
+use crate::{
+    core::{
+        models::{CachedUpload, Work},
+        services::UploadCacheService,
+    },
+    integrations::max::{AttachmentRequest, MaxApiError, SendMessageRequest, UploadType},
+    state::AppState,
+};
+
+/// Файл лежит не в нашем хранилище, а доступен по внешней ссылке
+pub fn is_remote(path: &str) -> bool {
+    path.starts_with("http://") || path.starts_with("https://")
+}
+
+/// Файл работы как вложение МАКС (`None`, если файл недоступен)
+pub async fn work_file(work: &Work, state: &AppState) -> Option<AttachmentRequest> {
+    if is_remote(&work.file_path) {
+        return None;
+    }
+
+    upload_cached(UploadType::File, &work.file_path, &download_name(work), state).await
+}
+
+/// Обложка работы как вложение-изображение МАКС
+pub async fn work_thumbnail(work: &Work, state: &AppState) -> Option<AttachmentRequest> {
+    let path = work.thumbnail_path.as_deref().filter(|p| !is_remote(p))?;
+    let file_name = Path::new(path).file_name()?.to_str()?.to_string();
+
+    upload_cached(UploadType::Image, path, &file_name, state).await
+}
+
+/// Имя файла для пользователя: название работы и исходное расширение
+fn download_name(work: &Work) -> String {
+    let title: String = work
+        .title
+        .chars()
+        .map(|c| if matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') { '_' } else { c })
+        .take(100)
+        .collect();
+
+    match Path::new(&work.file_path).extension().and_then(|e| e.to_str()) {
+        Some(extension) => format!("{}.{}", title.trim(), extension),
+        None => title.trim().to_string(),
+    }
+}
+
+/// Загрузка файла из хранилища в МАКС; повторные запросы того же содержимого берут токен из кэша
+async fn upload_cached(
+    upload_type: UploadType,
+    relative_path: &str,
+    file_name: &str,
+    state: &AppState,
+) -> Option<AttachmentRequest> {
+    let data = match state.storage.read(relative_path).await {
+        Ok(data) => data,
+        Err(e) => {
+            warn!("Файл {} недоступен в хранилище: {}", relative_path, e);
+            return None;
+        }
+    };
+
+    let cache = UploadCacheService::new(state.pool.clone());
+    let hash = UploadCacheService::hash(&data);
+
+    match cache.get(&hash, upload_type.as_str()).await {
+        Ok(Some(token)) => {
+            debug!("♻️ Токен для {} взят из кэша", relative_path);
+            return Some(upload_type.attachment(token));
+        }
+        Ok(None) => {}
+        Err(e) => warn!("Ошибка чтения кэша загрузок: {}", e),
+    }
+
+    let token = match state.max_api.upload(upload_type, file_name, data).await {
+        Ok(token) => token,
+        Err(e) => {
+            warn!("Не удалось загрузить {} в МАКС: {}", relative_path, e);
+            return None;
+        }
+    };
+
+    if let Err(e) = cache.put(&hash, upload_type.as_str(), &token, relative_path, file_name).await {
+        warn!("Не удалось сохранить токен в кэш загрузок: {}", e);
+    }
+
+    Some(upload_type.attachment(token))
+}
+
+/// Отправка ответа бота. Если платформа отвергла токен вложения из кэша загрузок (истёк или отозван),
+/// запись кэша удаляется, файл загружается заново и сообщение отправляется ещё раз — один раз
+pub async fn send(
+    chat_id: i64,
+    user_id: Option<i64>,
+    request: &SendMessageRequest,
+    state: &AppState,
+) -> Result<(), MaxApiError> {
+    let error = match state.max_api.send(chat_id, user_id, request).await {
+        Err(e) if e.is_invalid_attachment() => e,
+        result => return result,
+    };
+
+    warn!("♻️ Платформа не приняла вложение ({}), загружаем файлы заново", error);
+    match reupload(request, state).await {
+        Some(request) => state.max_api.send(chat_id, user_id, &request).await,
+        None => Err(error),
+    }
+}
+
+/// Сообщение с заново загруженными файлами; `None`, если ни одного вложения из кэша в нём нет.
+/// Файл, который не удалось загрузить снова, из сообщения убирается
+async fn reupload(request: &SendMessageRequest, state: &AppState) -> Option<SendMessageRequest> {
+    let cache = UploadCacheService::new(state.pool.clone());
+    let mut attachments = Vec::with_capacity(request.attachments.len());
+    let mut evicted = false;
+
+    for attachment in &request.attachments {
+        let Some(token) = attachment.media_token() else {
+            attachments.push(attachment.clone());
+            continue;
+        };
+
+        match cache.evict(token).await {
+            Ok(Some(CachedUpload { upload_type, file_path: Some(path), file_name: Some(name) })) => {
+                evicted = true;
+                let Some(upload_type) = UploadType::parse(&upload_type) else { continue };
+                attachments.extend(upload_cached(upload_type, &path, &name, state).await);
+            }
+            // Источник неизвестен — запись удалена, файл загрузится при следующем запросе
+            Ok(Some(_)) => evicted = true,
+            Ok(None) => attachments.push(attachment.clone()),
+            Err(e) => {
+                warn!("Не удалось удалить токен из кэша загрузок: {}", e);
+                attachments.push(attachment.clone());
+            }
+        }
+    }
+
+    evicted.then(|| SendMessageRequest { attachments, ..request.clone() })
+}
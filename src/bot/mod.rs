@@ -0,0 +1,158 @@
+pub mod account;
+pub mod analytics;
+pub mod callbacks;
+pub mod commands;
+pub mod dialogs;
+pub mod group;
+pub mod media;
+pub mod notifier;
+pub mod registry;
+pub mod review;
+pub mod setup;
+pub mod subscriptions;
+
+use std::sync::Arc;
+use tracing::{error, info};
+
+use crate::{
+    error::AppError,
+    i18n::{self, Locale},
+    integrations::max::{
+        formatting,
+        models::{Recipient, Sender, Update},
+    },
+    state::AppState,
+};
+
+/// Чат и пользователь, от которых пришло обновление
+#[derive(Debug, Clone)]
+pub struct ChatContext {
+    pub chat_id: i64,
+    pub user_id: i64,
+    /// Имя пользователя из профиля МАКС
+    pub user_name: String,
+    /// Групповой чат: бот отвечает только на обращённые к нему сообщения
+    pub is_group: bool,
+}
+
+impl ChatContext {
+    fn new(recipient: &Recipient, user: &Sender) -> Self {
+        let user_name = match user.last_name.as_deref() {
+            Some(last_name) if !last_name.is_empty() => format!("{} {}", user.first_name, last_name),
+            _ => user.first_name.clone(),
+        };
+
+        Self { chat_id: recipient.chat_id, user_id: user.user_id, user_name, is_group: recipient.is_group() }
+    }
+}
+
+/// Текст ошибки для ответа в чат: подробности уходят в журнал, пользователь видит только
+/// `user_message()`, экранированный для HTML
+pub fn error_text(error: &AppError) -> String {
+    error!("❌ Ошибка обработки команды бота: {}", error);
+    formatting::escape(&error.user_message())
+}
+
+/// Обработка одного обновления от МАКС: ответы бота — на языке пользователя,
+/// по итогам обработки пишется событие аналитики
+pub async fn handle_update(state: Arc<AppState>, update: Update) -> Result<(), AppError> {
+    let locale = Locale::from_user_locale(update.user_locale());
+    let Some(source) = analytics::EventSource::from_update(&update) else {
+        return i18n::scope(locale, process_update(state.clone(), update)).await;
+    };
+
+    let processing = process_update(state.clone(), update);
+    i18n::scope(locale, analytics::track(&state, source, locale, processing)).await
+}
+
+async fn process_update(state: Arc<AppState>, update: Update) -> Result<(), AppError> {
+    let client = &state.max_api;
+
+    match update {
+        Update::MessageCreated { message, .. } => {
+            info!(
+                "📨 Сообщение от МАКС | chat_id: {}, user: {} {}",
+                message.recipient.chat_id,
+                message.sender.first_name,
+                message.sender.last_name.as_deref().unwrap_or_default()
+            );
+
+            let text = message.body.text.as_deref().unwrap_or_default().trim();
+            info!("💬 Текст сообщения: {:?}", text);
+
+            let ctx = ChatContext::new(&message.recipient, &message.sender);
+
+            // В группе — только сообщения, обращённые к боту, без упоминания самого бота
+            let addressed = if ctx.is_group {
+                let bot = client
+                    .bot_info()
+                    .await
+                    .map_err(|e| AppError::Internal(format!("Failed to get bot info: {}", e)))?;
+                group::addressed_text(&message.body, bot)
+            } else {
+                Some(text.to_string())
+            };
+
+            // Обычный текст или файл во время диалога — ответ на текущий шаг, команды обрабатываются как обычно.
+            // Диалог хранится для пары чат-пользователь, поэтому в группе его продолжает только начавший
+            // и только обращёнными к боту сообщениями: остальная переписка чата в диалог не попадает.
+            let continues_dialog = !text.starts_with('/') && (!ctx.is_group || addressed.is_some());
+            let reply = if continues_dialog && dialogs::has_conversation(&ctx, &state).await {
+                let input = dialogs::DialogInput { text: addressed.as_deref().unwrap_or(text), file: message.body.file() };
+                analytics::record_dialog();
+                dialogs::handle_input(input, &ctx, &state).await
+            } else {
+                match addressed {
+                    Some(text) if ctx.is_group => group::dispatch(&text, &ctx, &state).await,
+                    Some(text) => commands::dispatch(&text, &ctx, &state).await,
+                    None => {
+                        info!("⏭️ Сообщение в группе chat_id={} адресовано не боту", ctx.chat_id);
+                        analytics::skip();
+                        return Ok(());
+                    }
+                }
+            };
+
+            media::send(message.recipient.chat_id, message.recipient.user_id, &reply, &state)
+                .await
+                .map_err(|e| AppError::Internal(format!("Failed to send message: {}", e)))?;
+
+            info!("✅ Ответ отправлен пользователю chat_id={}", message.recipient.chat_id);
+        }
+        Update::MessageCallback { callback, message, .. } => {
+            info!(
+                "🔘 Нажатие кнопки | user_id: {}, payload: {:?}",
+                callback.user.user_id, callback.payload
+            );
+
+            let ctx = message.as_ref().map(|m| ChatContext::new(&m.recipient, &callback.user));
+
+            let outcome = callbacks::handle(callback.payload.as_deref().unwrap_or_default(), ctx.as_ref(), &state).await;
+
+            client
+                .answer_callback(&callback.callback_id, &outcome.answer)
+                .await
+                .map_err(|e| AppError::Internal(format!("Failed to answer callback: {}", e)))?;
+
+            // Дополнительное сообщение отправляем в тот же чат, где была нажата кнопка
+            if let (Some(follow_up), Some(message)) = (outcome.follow_up, message) {
+                media::send(message.recipient.chat_id, message.recipient.user_id, &follow_up, &state)
+                    .await
+                    .map_err(|e| AppError::Internal(format!("Failed to send message: {}", e)))?;
+            }
+        }
+        Update::BotStarted { chat_id, user, .. } => {
+            info!("👋 Бот запущен пользователем {} (chat_id={})", user.user_id, chat_id);
+
+            client
+                .send(chat_id, Some(user.user_id), &commands::start(&state))
+                .await
+                .map_err(|e| AppError::Internal(format!("Failed to send message: {}", e)))?;
+        }
+        Update::Unsupported => {
+            info!("⏭️ Обновление этого типа не обрабатывается");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::{
+    core::{
+        models::Work,
+        services::{SubscriptionService, WorkService},
+    },
+    error::AppError,
+    i18n::{self, Locale},
+    integrations::max::{formatting, Button, InlineKeyboard, MaxApiError, SendMessageRequest},
+    state::AppState,
+    t,
+};
+
+use super::{callbacks::CallbackAction, commands::format_work_type};
+
+/// Период проверки новых опубликованных работ
+const NOTIFY_INTERVAL_SECS: u64 = 60;
+
+/// Сколько работ разбирать за один проход
+const WORKS_PER_RUN: u32 = 50;
+
+/// Сколько работ перечислять в одном сообщении-дайджесте
+const WORKS_PER_DIGEST: usize = 10;
+
+/// Фоновая задача: рассылает подписчикам дайджесты о новых опубликованных работах.
+/// Останавливается по `shutdown`, но только между проходами: начатая рассылка доводится до конца.
+pub async fn notify_task(state: Arc<AppState>, shutdown: CancellationToken) {
+    let mut interval = tokio::time::interval(Duration::from_secs(NOTIFY_INTERVAL_SECS));
+
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = interval.tick() => {}
+        }
+        if let Err(e) = notify_pending(&state).await {
+            warn!("Ошибка рассылки уведомлений о новых работах: {}", e);
+        }
+    }
+}
+
+/// Один проход рассылки; возвращает число отправленных дайджестов
+pub async fn notify_pending(state: &AppState) -> Result<usize, AppError> {
+    let works_service = WorkService::new(state.pool.clone());
+    let subscriptions = SubscriptionService::new(state.pool.clone());
+
+    let works = works_service.list_unnotified(WORKS_PER_RUN).await?;
+    if works.is_empty() {
+        return Ok(0);
+    }
+
+    // Новые работы по чатам: в каждый чат уходит один дайджест, даже если совпало несколько подписок.
+    // Язык дайджеста — язык первой совпавшей подписки чата.
+    let mut digests: BTreeMap<i64, (Locale, Vec<&Work>)> = BTreeMap::new();
+    for work in &works {
+        for subscription in subscriptions.matching(work).await? {
+            let locale = Locale::parse(&subscription.locale).unwrap_or_default();
+            let (_, chat_works) = digests.entry(subscription.chat_id).or_insert_with(|| (locale, Vec::new()));
+            if !chat_works.iter().any(|w| w.id == work.id) {
+                chat_works.push(work);
+            }
+        }
+    }
+
+    // Отмечаем заранее: при сбое лучше пропустить уведомление, чем разослать его дважды
+    let ids: Vec<_> = works.iter().map(|w| w.id).collect();
+    works_service.mark_notified(&ids).await?;
+
+    // Частоту запросов и повторы при 429 ограничивает общий клиент МАКС
+    let mut sent = 0;
+    for (chat_id, (locale, chat_works)) in digests {
+        let message = i18n::scope(locale, async { digest(&chat_works) }).await;
+        match state.max_api.send(chat_id, None, &message).await {
+            Ok(()) => sent += 1,
+            Err(MaxApiError::BlockedByUser(_)) => {
+                info!("🚫 Бот недоступен в чате {}, подписки удалены", chat_id);
+                if let Err(e) = subscriptions.unsubscribe_all(chat_id).await {
+                    warn!("Не удалось удалить подписки чата {}: {}", chat_id, e);
+                }
+            }
+            Err(e) => warn!("Не удалось отправить дайджест в чат {}: {}", chat_id, e),
+        }
+    }
+
+    info!("🔔 Новых работ: {}, отправлено дайджестов: {}", works.len(), sent);
+    Ok(sent)
+}
+
+fn digest(works: &[&Work]) -> SendMessageRequest {
+    let mut text = format!("{}\n", t!("digest-title"));
+    let mut keyboard = InlineKeyboard::new();
+
+    for (i, work) in works.iter().take(WORKS_PER_DIGEST).enumerate() {
+        text.push_str(&format!(
+            "\n{}. {}\n{}, {} — {}\n🎓 {}\n",
+            i + 1,
+            formatting::bold(&work.title),
+            formatting::escape(&work.author_name),
+            work.year,
+            format_work_type(&work.work_type),
+            formatting::escape(&work.specialty)
+        ));
+        keyboard = keyboard.row(vec![Button::callback(
+            t!("button-details", n = i + 1),
+            CallbackAction::Work(work.id).encode(),
+        )]);
+    }
+
+    if works.len() > WORKS_PER_DIGEST {
+        text.push_str(&format!("\n{}", t!("digest-more", count = works.len() - WORKS_PER_DIGEST)));
+    }
+    text.push_str(&format!("\n\n{}", t!("digest-manage")));
+
+    SendMessageRequest::html(text).with_keyboard(keyboard)
+}
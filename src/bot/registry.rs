@@ -0,0 +1,112 @@
+use crate::{
+    i18n::{self, Locale},
+    integrations::max::models::BotCommand,
+};
+
+/// Описание команды бота: из него строятся разбор сообщений, справка и меню команд в МАКС.
+/// Тексты берутся из каталога: `command-<имя>` и, если есть аргументы, `command-<имя>-args`.
+#[derive(Debug)]
+pub struct CommandSpec {
+    /// Имя команды без `/`
+    pub name: &'static str,
+    /// Русские синонимы (`/поиск`)
+    pub aliases: &'static [&'static str],
+    /// Слова, которые работают как команда без `/` (всё сообщение целиком)
+    pub keywords: &'static [&'static str],
+    /// Принимает ли команда аргументы (показываются в справке)
+    pub takes_args: bool,
+    /// Команда для преподавателей и методистов — в меню не публикуется
+    pub reviewer_only: bool,
+}
+
+impl CommandSpec {
+    fn description(&self, locale: Locale) -> String {
+        i18n::message(locale, &format!("command-{}", self.name), None)
+    }
+}
+
+const fn command(name: &'static str) -> CommandSpec {
+    CommandSpec { name, aliases: &[], keywords: &[], takes_args: false, reviewer_only: false }
+}
+
+/// Все команды бота в порядке показа в справке
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec { keywords: &["привет", "hello"], ..command("start") },
+    CommandSpec { aliases: &["поиск"], takes_args: true, ..command("search") },
+    CommandSpec { aliases: &["обзор"], ..command("browse") },
+    CommandSpec { aliases: &["работа"], takes_args: true, ..command("work") },
+    CommandSpec { aliases: &["отправить"], ..command("submit") },
+    CommandSpec { aliases: &["мои"], ..command("my") },
+    CommandSpec { aliases: &["подписаться"], takes_args: true, ..command("subscribe") },
+    CommandSpec { aliases: &["подписки"], ..command("subscriptions") },
+    CommandSpec { aliases: &["отписаться"], ..command("unsubscribe") },
+    CommandSpec { aliases: &["отмена"], ..command("cancel") },
+    CommandSpec { takes_args: true, ..command("link") },
+    command("unlink"),
+    command("whoami"),
+    CommandSpec { aliases: &["фильтр"], takes_args: true, ..command("filter") },
+    CommandSpec { keywords: &["помощь", "help"], ..command("help") },
+    CommandSpec { reviewer_only: true, ..command("pending") },
+    CommandSpec { takes_args: true, reviewer_only: true, ..command("approve") },
+    CommandSpec { takes_args: true, reviewer_only: true, ..command("reject") },
+];
+
+/// Команда и её аргументы из текста сообщения. Имя бота после команды (`/search@bot`) отбрасывается —
+/// кому адресована команда в группе, проверяется до разбора.
+pub fn parse(text: &str) -> Option<(&'static CommandSpec, &str)> {
+    let text = text.trim();
+    let (head, args) = text
+        .split_once(char::is_whitespace)
+        .map(|(head, args)| (head, args.trim()))
+        .unwrap_or((text, ""));
+
+    match head.to_lowercase().strip_prefix('/') {
+        Some(name) => {
+            let name = name.split('@').next().unwrap_or_default();
+            COMMANDS
+            .iter()
+            .find(|c| c.name == name || c.aliases.contains(&name))
+                .map(|c| (c, args))
+        }
+        None => {
+            let text = text.to_lowercase();
+            COMMANDS.iter().find(|c| c.keywords.contains(&text.as_str())).map(|c| (c, ""))
+        }
+    }
+}
+
+/// Строка справки на языке текущего запроса: `/search <запрос> — описание`
+pub fn help_line(command: &CommandSpec) -> String {
+    let locale = i18n::current();
+    let description = command.description(locale);
+    if command.takes_args {
+        let args = i18n::message(locale, &format!("command-{}-args", command.name), None);
+        format!("/{} {} — {}", command.name, args, description)
+    } else {
+        format!("/{} — {}", command.name, description)
+    }
+}
+
+/// Меню команд для МАКС. Платформа хранит одно описание, поэтому оно двуязычное.
+pub fn menu_commands() -> Vec<BotCommand> {
+    COMMANDS
+        .iter()
+        .filter(|c| !c.reviewer_only)
+        .map(|c| BotCommand {
+            name: c.name.to_string(),
+            description: Some(format!(
+                "{} / {}",
+                capitalize(&c.description(Locale::Ru)),
+                capitalize(&c.description(Locale::En))
+            )),
+        })
+        .collect()
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
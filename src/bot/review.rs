@@ -0,0 +1,147 @@
+use uuid::Uuid;
+
+use crate::{
+    core::{
+        models::{User, WorkStatus},
+        services::WorkService,
+    },
+    integrations::max::{formatting, Button, InlineKeyboard, SendMessageRequest},
+    state::AppState,
+    t,
+};
+
+use super::{
+    account,
+    callbacks::CallbackAction,
+    commands::{format_work_status, format_work_type},
+    error_text,
+    ChatContext,
+};
+
+/// Сколько работ показывать в списках /my и /pending
+const LIST_LIMIT: u32 = 10;
+
+/// Пользователь с правом проверки работ или сообщение об отказе
+async fn reviewer(ctx: &ChatContext, state: &AppState) -> Result<User, SendMessageRequest> {
+    match account::linked_user(ctx, state).await {
+        Some(user) if user.role.can_review() => Ok(user),
+        Some(_) => Err(SendMessageRequest::html(t!("review-forbidden"))),
+        None => Err(SendMessageRequest::html(t!("review-link-first"))),
+    }
+}
+
+/// Команда /my — работы, присланные пользователем через бота
+pub async fn my_works(ctx: &ChatContext, state: &AppState) -> SendMessageRequest {
+    let service = WorkService::new(state.pool.clone());
+    let works = match service.list_by_submitter(ctx.user_id, LIST_LIMIT).await {
+        Ok(works) => works,
+        Err(e) => return SendMessageRequest::html(t!("error-generic", error = error_text(&e))),
+    };
+
+    if works.is_empty() {
+        return SendMessageRequest::html(t!("my-works-empty"));
+    }
+
+    let mut text = format!("{}\n", t!("my-works-title"));
+    let mut keyboard = InlineKeyboard::new();
+    for (i, work) in works.iter().enumerate() {
+        text.push_str(&format!(
+            "\n{}. {}\n{}, {} — {}\n",
+            i + 1,
+            formatting::bold(&work.title),
+            format_work_type(&work.work_type),
+            work.year,
+            format_work_status(&work.status)
+        ));
+        keyboard = keyboard.row(vec![Button::callback(
+            t!("button-details", n = i + 1),
+            CallbackAction::Work(work.id).encode(),
+        )]);
+    }
+
+    SendMessageRequest::html(text).with_keyboard(keyboard)
+}
+
+/// Команда /pending — работы, ожидающие проверки
+pub async fn pending(ctx: &ChatContext, state: &AppState) -> SendMessageRequest {
+    if let Err(reply) = reviewer(ctx, state).await {
+        return reply;
+    }
+
+    let service = WorkService::new(state.pool.clone());
+    let works = match service.list_by_status(WorkStatus::Submitted, LIST_LIMIT).await {
+        Ok(works) => works,
+        Err(e) => return SendMessageRequest::html(t!("error-generic", error = error_text(&e))),
+    };
+
+    if works.is_empty() {
+        return SendMessageRequest::html(t!("pending-empty"));
+    }
+
+    let mut text = format!("{}\n", t!("pending-title"));
+    let mut keyboard = InlineKeyboard::new();
+    for (i, work) in works.iter().enumerate() {
+        let n = i + 1;
+        text.push_str(&format!(
+            "\n{}. {}\n{}, {} — {}, {}\n",
+            n,
+            formatting::bold(&work.title),
+            formatting::escape(&work.author_name),
+            work.year,
+            formatting::escape(&work.specialty),
+            t!("pending-supervisor", name = formatting::escape(&work.supervisor_name))
+        ));
+        keyboard = keyboard.row(vec![
+            Button::callback(format!("📄 {}", n), CallbackAction::Work(work.id).encode()),
+            Button::callback(format!("✅ {}", n), CallbackAction::Review { id: work.id, approve: true }.encode()),
+            Button::callback(format!("❌ {}", n), CallbackAction::Review { id: work.id, approve: false }.encode()),
+        ]);
+    }
+
+    SendMessageRequest::html(text).with_keyboard(keyboard)
+}
+
+/// Команды /approve <ID> и /reject <ID>
+pub async fn review_command(id_str: &str, approve: bool, ctx: &ChatContext, state: &AppState) -> SendMessageRequest {
+    match Uuid::parse_str(id_str) {
+        Ok(id) => review(id, approve, ctx, state).await,
+        Err(_) => SendMessageRequest::html(t!("review-id-invalid")),
+    }
+}
+
+/// Публикация или отклонение работы, присланной на проверку
+pub async fn review(id: Uuid, approve: bool, ctx: &ChatContext, state: &AppState) -> SendMessageRequest {
+    let user = match reviewer(ctx, state).await {
+        Ok(user) => user,
+        Err(reply) => return reply,
+    };
+
+    let service = WorkService::new(state.pool.clone());
+    match service.get_by_id(id).await {
+        Ok(Some(work)) if work.status == WorkStatus::Submitted => {}
+        Ok(Some(work)) => {
+            return SendMessageRequest::html(t!(
+                "review-not-pending",
+                title = formatting::escape(&work.title),
+                status = format_work_status(&work.status)
+            ))
+        }
+        Ok(None) => return SendMessageRequest::html(t!("work-not-found-id", id = id.to_string())),
+        Err(e) => return SendMessageRequest::html(t!("error-generic", error = error_text(&e))),
+    }
+
+    let status = if approve { WorkStatus::Published } else { WorkStatus::Rejected };
+    match service.set_status(id, status).await {
+        Ok(Some(work)) => {
+            tracing::info!("📝 Работа {} переведена в статус {} пользователем {}", work.id, status.as_str(), user.username);
+            let title = formatting::escape(&work.title);
+            SendMessageRequest::html(if approve {
+                t!("review-published", title = title)
+            } else {
+                t!("review-rejected", title = title)
+            })
+        }
+        Ok(None) => SendMessageRequest::html(t!("work-not-found-id", id = id.to_string())),
+        Err(e) => SendMessageRequest::html(t!("error-generic", error = error_text(&e))),
+    }
+}
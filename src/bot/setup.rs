@@ -0,0 +1,88 @@
+use std::collections::BTreeSet;
+
+use tracing::{info, warn};
+
+use crate::{
+    config::Config,
+    integrations::max::{models::SubscribeRequest, MaxApiClient, MaxApiError},
+};
+
+use super::registry;
+
+/// Приводит настройки бота в МАКС в соответствие с конфигурацией при запуске.
+/// Ошибки только логируются: приложение должно стартовать и без доступа к платформе.
+pub async fn sync_on_startup(client: &MaxApiClient, config: &Config) {
+    if !config.max_sync_on_startup {
+        return;
+    }
+
+    match sync_commands(client).await {
+        Ok(true) => info!("📋 Меню команд бота обновлено"),
+        Ok(false) => info!("📋 Меню команд бота актуально"),
+        Err(e) => warn!("Не удалось обновить меню команд бота: {}", e),
+    }
+
+    let Some(url) = config.max_webhook_url.as_deref() else {
+        info!("ℹ️ max_webhook_url не задан, подписка на вебхук не проверяется");
+        return;
+    };
+
+    match sync_webhook(client, url, &config.max_webhook_update_types, config.max_webhook_secret.expose()).await {
+        Ok(true) => info!("🔗 Вебхук зарегистрирован: {}", url),
+        Ok(false) => info!("🔗 Вебхук актуален: {}", url),
+        Err(e) => warn!("Не удалось зарегистрировать вебхук {}: {}", url, e),
+    }
+}
+
+/// Публикует меню команд из реестра, если оно отличается от текущего. `true` — меню изменено.
+pub async fn sync_commands(client: &MaxApiClient) -> Result<bool, MaxApiError> {
+    let commands = registry::menu_commands();
+
+    let me = client.get_me().await?;
+    if me.commands.as_ref() == Some(&commands) {
+        return Ok(false);
+    }
+
+    client.set_commands(commands).await?;
+    Ok(true)
+}
+
+/// Оставляет ровно одну подписку: на `url` с типами обновлений `update_types`
+/// (пустой список — все типы). Чужие и устаревшие подписки удаляются. `true` — подписка изменена.
+/// Секрет платформа не возвращает, поэтому подписка отправляется заново даже без изменений:
+/// так новый `secret` вступает в силу и после его ротации.
+pub async fn sync_webhook(
+    client: &MaxApiClient,
+    url: &str,
+    update_types: &[String],
+    secret: &str,
+) -> Result<bool, MaxApiError> {
+    let wanted: BTreeSet<&str> = update_types.iter().map(String::as_str).collect();
+
+    let mut up_to_date = false;
+    for subscription in client.get_subscriptions().await? {
+        let current: BTreeSet<&str> = subscription
+            .update_types
+            .iter()
+            .flatten()
+            .map(String::as_str)
+            .collect();
+
+        if subscription.url == url && current == wanted && !up_to_date {
+            up_to_date = true;
+            continue;
+        }
+
+        info!("🗑️ Удаляю подписку на вебхук {}", subscription.url);
+        client.unsubscribe(&subscription.url).await?;
+    }
+
+    client
+        .subscribe(&SubscribeRequest {
+            url: url.to_string(),
+            update_types: update_types.to_vec(),
+            secret: Some(secret.to_string()),
+        })
+        .await?;
+    Ok(!up_to_date)
+}
@@ -0,0 +1,127 @@
+use uuid::Uuid;
+
+use crate::{
+    core::{
+        models::{Subscription, SubscriptionKind},
+        services::SubscriptionService,
+    },
+    i18n,
+    integrations::max::{formatting, Button, InlineKeyboard, SendMessageRequest},
+    state::AppState,
+    t,
+};
+
+use super::{callbacks::CallbackAction, error_text, ChatContext};
+
+/// Тип и значение подписки из аргументов команды.
+/// Без явного типа код специальности (начинается с цифры) — специальность, остальное — ключевое слово.
+fn parse_subscription(args: &str) -> Option<(SubscriptionKind, String)> {
+    let args = args.trim();
+    if args.is_empty() {
+        return None;
+    }
+
+    let (first, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+    let explicit = match first.to_lowercase().as_str() {
+        "specialty" | "специальность" => Some(SubscriptionKind::Specialty),
+        "tag" | "тег" => Some(SubscriptionKind::Tag),
+        "supervisor" | "руководитель" => Some(SubscriptionKind::Supervisor),
+        _ => None,
+    };
+
+    let (kind, value) = match explicit {
+        Some(kind) => (kind, rest.trim()),
+        None if args.starts_with(|c: char| c.is_ascii_digit()) => (SubscriptionKind::Specialty, args),
+        None => (SubscriptionKind::Tag, args),
+    };
+
+    let value = value.trim_start_matches('#').trim();
+    (!value.is_empty()).then(|| (kind, value.to_string()))
+}
+
+pub fn format_subscription(subscription: &Subscription) -> String {
+    let kind = t!(&format!("subscription-kind-{}", subscription.kind.as_str()));
+    t!("subscription-item", kind = kind, value = formatting::escape(&subscription.value))
+}
+
+/// Команда /subscribe
+pub async fn subscribe(args: &str, ctx: &ChatContext, state: &AppState) -> SendMessageRequest {
+    let Some((kind, value)) = parse_subscription(args) else {
+        return SendMessageRequest::html(t!("subscriptions-usage"));
+    };
+
+    let service = SubscriptionService::new(state.pool.clone());
+    match service.subscribe(ctx.chat_id, ctx.user_id, kind, &value, i18n::current()).await {
+        Ok(Some(subscription)) => {
+            SendMessageRequest::html(t!("subscribe-done", subscription = format_subscription(&subscription)))
+        }
+        Ok(None) => SendMessageRequest::html(t!("subscribe-exists")),
+        Err(e) => SendMessageRequest::html(t!("subscribe-failed", error = error_text(&e))),
+    }
+}
+
+/// Команда /subscriptions — список подписок чата с кнопками отписки
+pub async fn list(ctx: &ChatContext, state: &AppState) -> SendMessageRequest {
+    let service = SubscriptionService::new(state.pool.clone());
+    let subscriptions = match service.list(ctx.chat_id).await {
+        Ok(subscriptions) => subscriptions,
+        Err(e) => return SendMessageRequest::html(t!("error-generic", error = error_text(&e))),
+    };
+
+    if subscriptions.is_empty() {
+        return SendMessageRequest::html(format!("{}\n\n{}", t!("subscriptions-empty"), t!("subscriptions-usage")));
+    }
+
+    let mut text = format!("{}\n", t!("subscriptions-title"));
+    let mut keyboard = InlineKeyboard::new();
+    for (i, subscription) in subscriptions.iter().enumerate() {
+        text.push_str(&format!("\n{}. {}", i + 1, format_subscription(subscription)));
+        keyboard = keyboard.row(vec![Button::callback(
+            t!("subscriptions-unsubscribe-button", n = i + 1),
+            CallbackAction::Unsubscribe(subscription.id).encode(),
+        )]);
+    }
+    text.push_str(&format!("\n\n{}", t!("subscriptions-unsubscribe-all")));
+
+    SendMessageRequest::html(text).with_keyboard(keyboard)
+}
+
+/// Команда /unsubscribe: без аргументов — список подписок, «все» — удалить все, иначе — по значению
+pub async fn unsubscribe(args: &str, ctx: &ChatContext, state: &AppState) -> SendMessageRequest {
+    let args = args.trim();
+    if args.is_empty() {
+        return list(ctx, state).await;
+    }
+
+    let service = SubscriptionService::new(state.pool.clone());
+
+    if matches!(args.to_lowercase().as_str(), "all" | "все") {
+        return match service.unsubscribe_all(ctx.chat_id).await {
+            Ok(0) => SendMessageRequest::html(t!("unsubscribe-none")),
+            Ok(n) => SendMessageRequest::html(t!("unsubscribe-all-done", count = n)),
+            Err(e) => SendMessageRequest::html(t!("error-generic", error = error_text(&e))),
+        };
+    }
+
+    let value = parse_subscription(args).map(|(_, value)| value).unwrap_or_default().to_lowercase();
+    let found = match service.list(ctx.chat_id).await {
+        Ok(subscriptions) => subscriptions.into_iter().find(|s| s.value.to_lowercase() == value),
+        Err(e) => return SendMessageRequest::html(t!("error-generic", error = error_text(&e))),
+    };
+
+    match found {
+        Some(subscription) => unsubscribe_by_id(subscription.id, ctx, state).await,
+        None => SendMessageRequest::html(t!("unsubscribe-not-found")),
+    }
+}
+
+pub async fn unsubscribe_by_id(id: Uuid, ctx: &ChatContext, state: &AppState) -> SendMessageRequest {
+    let service = SubscriptionService::new(state.pool.clone());
+    match service.unsubscribe(id, ctx.chat_id).await {
+        Ok(Some(subscription)) => {
+            SendMessageRequest::html(t!("unsubscribe-done", subscription = format_subscription(&subscription)))
+        }
+        Ok(None) => SendMessageRequest::html(t!("unsubscribe-already")),
+        Err(e) => SendMessageRequest::html(t!("error-generic", error = error_text(&e))),
+    }
+}
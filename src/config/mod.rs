@@ -1,16 +1,395 @@
-use figment::{Figment, providers::{Env, Format, Yaml}};
+mod secret;
+
+pub use secret::Secret;
+
+use figment::{error::Kind, Figment, providers::{Env, Format, Yaml}};
 use serde::Deserialize;
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    infrastructure::{
+        database::PoolSettings,
+        logger::{LogFormat, LogRotation},
+    },
+    integrations::max::{MaxApiClient, MaxApiSettings},
+};
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct Config {
+    /// Адрес, на котором слушает сервер; в контейнере — `0.0.0.0`
+    #[serde(default = "default_bind_address")]
+    pub bind_address: IpAddr,
     pub port: u16,
-    pub database_url: String,
-    pub max_bot_token: String,
+    /// Сертификат (PEM) для HTTPS; задаётся вместе с `tls_key_path`, иначе сервер работает по HTTP
+    #[serde(default)]
+    pub tls_cert_path: Option<String>,
+    /// Закрытый ключ (PEM) к `tls_cert_path`
+    #[serde(default)]
+    pub tls_key_path: Option<String>,
+    /// Наибольший размер тела запроса к API, МБ
+    #[serde(default = "default_http_body_limit_mb")]
+    pub http_body_limit_mb: u64,
+    /// Сколько секунд при остановке ждать завершения начатых запросов и фоновых задач
+    #[serde(default = "default_shutdown_timeout_secs")]
+    pub shutdown_timeout_secs: u64,
+    /// Строка подключения к Postgres; содержит пароль, поэтому тоже секрет
+    pub database_url: Secret<String>,
+    /// Наибольшее число соединений в пуле
+    #[serde(default = "default_database_max_connections")]
+    pub database_max_connections: u32,
+    /// Сколько соединений держать открытыми без нагрузки
+    #[serde(default)]
+    pub database_min_connections: u32,
+    /// Ожидание свободного соединения из пула, секунды
+    #[serde(default = "default_database_acquire_timeout_secs")]
+    pub database_acquire_timeout_secs: u64,
+    /// Через сколько секунд закрывать простаивающее соединение; 0 — не закрывать
+    #[serde(default = "default_database_idle_timeout_secs")]
+    pub database_idle_timeout_secs: u64,
+    /// Наибольшее время выполнения SQL-запроса, секунды; 0 — без ограничения
+    #[serde(default = "default_database_statement_timeout_secs")]
+    pub database_statement_timeout_secs: u64,
+    /// Применять недостающие миграции при запуске; иначе с отставшей схемой приложение не запускается
+    #[serde(default)]
+    pub database_auto_migrate: bool,
+    pub max_bot_token: Secret<String>,
+    /// Секрет для подписи сессионных JWT
+    pub jwt_secret: Secret<String>,
     pub file_storage_path: String,
+    /// Мини-приложение МАКС для кнопки «Открыть» (ссылка или username бота)
+    #[serde(default)]
+    pub max_mini_app: Option<String>,
+    /// Время ожидания ответа пользователя в диалогах бота, секунды
+    #[serde(default = "default_bot_dialog_ttl_secs")]
+    pub bot_dialog_ttl_secs: u64,
+    /// Максимальный размер файла работы, присылаемого через бота, МБ
+    #[serde(default = "default_max_upload_size_mb")]
+    pub max_upload_size_mb: u64,
+    /// Сколько секунд после `auth_date` принимаются данные запуска мини-приложения
+    #[serde(default = "default_max_launch_data_ttl_secs")]
+    pub max_launch_data_ttl_secs: u64,
+    /// Адрес MAX Bot API (для тестов — локальный мок)
+    #[serde(default = "default_max_api_base_url")]
+    pub max_api_base_url: String,
+    /// Тайм-аут подключения к MAX Bot API, секунды
+    #[serde(default = "default_max_api_connect_timeout_secs")]
+    pub max_api_connect_timeout_secs: u64,
+    /// Тайм-аут запроса к MAX Bot API целиком, секунды
+    #[serde(default = "default_max_api_timeout_secs")]
+    pub max_api_timeout_secs: u64,
+    /// Лимит запросов к MAX Bot API в секунду
+    #[serde(default = "default_max_api_requests_per_second")]
+    pub max_api_requests_per_second: u32,
+    /// Повторы запроса при 429, 5xx и сетевых сбоях
+    #[serde(default = "default_max_api_max_retries")]
+    pub max_api_max_retries: u32,
+    /// Публичный адрес вебхука (`https://…/api/max/webhook`); не задан — подписка не трогается
+    #[serde(default)]
+    pub max_webhook_url: Option<String>,
+    /// Секрет подписки на вебхук: платформа присылает его в `X-Max-Bot-Api-Secret`,
+    /// обновления без него отклоняются. 5–256 символов: латиница, цифры, `_` и `-`
+    pub max_webhook_secret: Secret<String>,
+    /// Типы обновлений, на которые подписывается бот; пустой список — все типы
+    #[serde(default = "default_max_webhook_update_types")]
+    pub max_webhook_update_types: Vec<String>,
+    /// Сверять меню команд и подписку на вебхук с конфигурацией при запуске
+    #[serde(default = "default_max_sync_on_startup")]
+    pub max_sync_on_startup: bool,
+    /// Записывать обезличенные события бота для аналитики
+    #[serde(default = "default_bot_analytics_enabled")]
+    pub bot_analytics_enabled: bool,
+    /// Ключ HMAC для обезличивания пользователей в аналитике; отдельный от `jwt_secret`,
+    /// чтобы смена ключа подписи сессий не меняла обезличенные ID
+    pub analytics_salt: Secret<String>,
+    /// Уровни логирования в формате `EnvFilter`; переменная `RUST_LOG` имеет приоритет
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+    /// Формат журнала: `text` или `json`
+    #[serde(default)]
+    pub log_format: LogFormat,
+    /// Каталог для файлов журнала; не задан — журнал пишется только в stdout
+    #[serde(default)]
+    pub log_dir: Option<String>,
+    /// Ротация файлов журнала: `hourly`, `daily` или `never`
+    #[serde(default)]
+    pub log_rotation: LogRotation,
+    /// Сколько файлов журнала хранить; 0 — не удалять старые
+    #[serde(default = "default_log_max_files")]
+    pub log_max_files: usize,
+    /// Проверять доступность MAX Bot API в `/health/ready`
+    #[serde(default)]
+    pub health_check_max_api: bool,
+    /// Отдавать метрики Prometheus на `/metrics`
+    #[serde(default = "default_metrics_enabled")]
+    pub metrics_enabled: bool,
+    /// Токен, который Prometheus передаёт в `Authorization: Bearer`; не задан — без проверки
+    #[serde(default)]
+    pub metrics_token: Option<Secret<String>>,
+    /// Отдельный адрес для `/metrics` (например, `127.0.0.1:9100`); не задан — на основном порту
+    #[serde(default)]
+    pub metrics_bind_address: Option<SocketAddr>,
+}
+
+fn default_bind_address() -> IpAddr {
+    IpAddr::V4(Ipv4Addr::LOCALHOST)
+}
+
+fn default_http_body_limit_mb() -> u64 {
+    2
+}
+
+fn default_shutdown_timeout_secs() -> u64 {
+    30
+}
+
+fn default_database_max_connections() -> u32 {
+    PoolSettings::default().max_connections
+}
+
+fn default_database_acquire_timeout_secs() -> u64 {
+    PoolSettings::default().acquire_timeout.as_secs()
+}
+
+fn default_database_idle_timeout_secs() -> u64 {
+    PoolSettings::default().idle_timeout.map_or(0, |t| t.as_secs())
+}
+
+fn default_database_statement_timeout_secs() -> u64 {
+    PoolSettings::default().statement_timeout.map_or(0, |t| t.as_secs())
+}
+
+fn default_bot_dialog_ttl_secs() -> u64 {
+    15 * 60
+}
+
+fn default_max_upload_size_mb() -> u64 {
+    50
+}
+
+fn default_max_launch_data_ttl_secs() -> u64 {
+    24 * 60 * 60
+}
+
+fn default_max_api_base_url() -> String {
+    crate::integrations::max::DEFAULT_API_BASE_URL.to_string()
+}
+
+fn default_max_api_connect_timeout_secs() -> u64 {
+    5
+}
+
+fn default_max_api_timeout_secs() -> u64 {
+    30
+}
+
+fn default_max_api_requests_per_second() -> u32 {
+    25
+}
+
+fn default_max_api_max_retries() -> u32 {
+    3
+}
+
+fn default_max_webhook_update_types() -> Vec<String> {
+    ["message_created", "message_callback", "bot_started"].map(String::from).to_vec()
 }
 
-pub fn load() -> Result<Config, figment::Error> {
-    Figment::from(Yaml::file("Config.yaml"))
-        .merge(Env::prefixed("MAX_APP_"))
-        .extract()
-}
\ No newline at end of file
+fn default_max_sync_on_startup() -> bool {
+    true
+}
+
+fn default_bot_analytics_enabled() -> bool {
+    true
+}
+
+fn default_log_level() -> String {
+    "max_app=info,tower_http=info".to_string()
+}
+
+fn default_log_max_files() -> usize {
+    14
+}
+
+fn default_metrics_enabled() -> bool {
+    true
+}
+
+/// Секреты, которые можно передать файлом: ключ `<имя>_file` (`MAX_APP_<ИМЯ>_FILE`) — путь к файлу
+/// со значением, например `/run/secrets/max_bot_token` в Docker
+pub const SECRETS: [&str; 6] =
+    ["database_url", "max_bot_token", "jwt_secret", "max_webhook_secret", "analytics_salt", "metrics_token"];
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("Не удалось загрузить конфигурацию: {0}")]
+    Load(#[from] Box<figment::Error>),
+
+    #[error("Не задан секрет {0}: укажите MAX_APP_{upper} или путь к файлу с ним в MAX_APP_{upper}_FILE", upper = .0.to_uppercase())]
+    MissingSecret(String),
+
+    #[error("Секрет {0} задан и значением, и файлом ({0}_file) — оставьте что-то одно")]
+    SecretConflict(&'static str),
+
+    #[error("Некорректный путь к файлу секрета {name} в {name}_file (MAX_APP_{upper}_FILE): {source}", upper = .name.to_uppercase())]
+    SecretFilePath {
+        name: &'static str,
+        source: Box<figment::Error>,
+    },
+
+    #[error("Не удалось прочитать секрет {name} из {}: {source}", path.display())]
+    SecretFile {
+        name: &'static str,
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Некорректная конфигурация: {}", .0.join("; "))]
+    Invalid(Vec<String>),
+}
+
+impl Config {
+    /// Адрес основного сервера
+    pub fn server_address(&self) -> SocketAddr {
+        SocketAddr::new(self.bind_address, self.port)
+    }
+
+    /// Пути к сертификату и ключу, если включён HTTPS
+    pub fn tls(&self) -> Option<(&str, &str)> {
+        self.tls_cert_path.as_deref().zip(self.tls_key_path.as_deref())
+    }
+
+    /// Наибольший размер тела запроса, байты
+    pub fn http_body_limit(&self) -> usize {
+        (self.http_body_limit_mb * 1024 * 1024) as usize
+    }
+
+    /// Пул соединений с базой по конфигурации
+    pub fn pool_settings(&self) -> PoolSettings {
+        let seconds = |secs: u64| (secs > 0).then(|| Duration::from_secs(secs));
+        PoolSettings {
+            max_connections: self.database_max_connections,
+            min_connections: self.database_min_connections,
+            acquire_timeout: Duration::from_secs(self.database_acquire_timeout_secs),
+            idle_timeout: seconds(self.database_idle_timeout_secs),
+            statement_timeout: seconds(self.database_statement_timeout_secs),
+        }
+    }
+
+    /// Проверка значений, которые нельзя выразить типами; возвращает все ошибки сразу
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut errors = Vec::new();
+
+        for (name, value) in [
+            ("database_url", &self.database_url),
+            ("max_bot_token", &self.max_bot_token),
+            ("jwt_secret", &self.jwt_secret),
+            ("analytics_salt", &self.analytics_salt),
+        ] {
+            if value.expose().trim().is_empty() {
+                errors.push(format!("{} не может быть пустым", name));
+            }
+        }
+        let webhook_secret = self.max_webhook_secret.expose();
+        if !(5..=256).contains(&webhook_secret.len())
+            || !webhook_secret.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+        {
+            errors.push("max_webhook_secret: 5–256 символов, только латиница, цифры, `_` и `-`".to_string());
+        }
+        if self.database_max_connections == 0 {
+            errors.push("database_max_connections должно быть больше 0".to_string());
+        }
+        if self.database_min_connections > self.database_max_connections {
+            errors.push(format!(
+                "database_min_connections ({}) больше database_max_connections ({})",
+                self.database_min_connections, self.database_max_connections
+            ));
+        }
+        if self.database_acquire_timeout_secs == 0 {
+            errors.push("database_acquire_timeout_secs должно быть больше 0".to_string());
+        }
+        if self.http_body_limit_mb == 0 {
+            errors.push("http_body_limit_mb должно быть больше 0".to_string());
+        }
+        if self.shutdown_timeout_secs == 0 {
+            errors.push("shutdown_timeout_secs должно быть больше 0".to_string());
+        }
+
+        match (&self.tls_cert_path, &self.tls_key_path) {
+            (Some(cert), Some(key)) => {
+                for (name, path) in [("tls_cert_path", cert), ("tls_key_path", key)] {
+                    if !Path::new(path).is_file() {
+                        errors.push(format!("{}: файл {} не найден", name, path));
+                    }
+                }
+            }
+            (None, None) => {}
+            _ => errors.push("tls_cert_path и tls_key_path задаются только вместе".to_string()),
+        }
+
+        if self.port != 0 && self.metrics_bind_address == Some(self.server_address()) {
+            errors.push("metrics_bind_address совпадает с адресом основного сервера".to_string());
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(errors))
+        }
+    }
+
+    /// Клиент MAX Bot API с тайм-аутами и лимитами из конфигурации
+    pub fn max_api_client(&self) -> MaxApiClient {
+        MaxApiClient::with_settings(
+            self.max_bot_token.expose().clone(),
+            MaxApiSettings {
+                api_base_url: self.max_api_base_url.clone(),
+                connect_timeout: Duration::from_secs(self.max_api_connect_timeout_secs),
+                timeout: Duration::from_secs(self.max_api_timeout_secs),
+                requests_per_second: self.max_api_requests_per_second,
+                max_retries: self.max_api_max_retries,
+            },
+        )
+    }
+}
+
+pub fn load() -> Result<Config, ConfigError> {
+    load_from(Figment::from(Yaml::file("Config.yaml")).merge(Env::prefixed("MAX_APP_")))
+}
+
+/// Чтение и проверка конфигурации из произвольных источников
+pub fn load_from(figment: Figment) -> Result<Config, ConfigError> {
+    let figment = read_secret_files(figment)?;
+    let config: Config = figment.extract().map_err(|e| match e.kind {
+        Kind::MissingField(ref field) if SECRETS.contains(&field.as_ref()) => ConfigError::MissingSecret(field.to_string()),
+        _ => ConfigError::Load(Box::new(e)),
+    })?;
+    config.validate()?;
+    Ok(config)
+}
+
+/// Подставляет значения секретов из файлов `<имя>_file`; перевод строки в конце файла отбрасывается
+fn read_secret_files(mut figment: Figment) -> Result<Figment, ConfigError> {
+    for name in SECRETS {
+        let key = format!("{}_file", name);
+        if !figment.contains(&key) {
+            continue;
+        }
+        let path = figment
+            .extract_inner::<Option<PathBuf>>(&key)
+            .map_err(|source| ConfigError::SecretFilePath { name, source: Box::new(source) })?;
+        let Some(path) = path else {
+            continue;
+        };
+        if figment.contains(name) {
+            return Err(ConfigError::SecretConflict(name));
+        }
+
+        let value = std::fs::read_to_string(&path).map_err(|source| ConfigError::SecretFile { name, path, source })?;
+        figment = figment.merge((name, value.trim_end_matches(['\r', '\n']).to_string()));
+    }
+    Ok(figment)
+}
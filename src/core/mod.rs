@@ -1,3 +1,4 @@
 pub mod models;
 pub mod repositories;
-pub mod services;
\ No newline at end of file
+pub mod services;
+pub mod validation;
\ No newline at end of file
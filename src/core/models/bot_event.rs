@@ -0,0 +1,59 @@
+use serde::Serialize;
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Событие бота для записи в аналитику
+#[derive(Debug, Clone)]
+pub struct NewBotEvent {
+    /// Обезличенный идентификатор пользователя
+    pub user_hash: String,
+    pub kind: String,
+    pub command: Option<String>,
+    pub query: Option<String>,
+    pub result_count: Option<i32>,
+    pub latency_ms: i32,
+    pub locale: String,
+    pub chat_type: String,
+}
+
+/// Отчёт об использовании бота за период
+#[derive(Debug, Clone, Serialize)]
+pub struct BotAnalyticsReport {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub daily_active_users: Vec<DailyActiveUsers>,
+    pub top_queries: Vec<QueryStat>,
+    pub zero_result_queries: Vec<QueryStat>,
+    pub commands: Vec<CommandStat>,
+    /// Воронка: поиск → карточка работы → скачивание
+    pub funnel: Vec<FunnelStep>,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct DailyActiveUsers {
+    pub date: NaiveDate,
+    pub users: i64,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct QueryStat {
+    pub query: String,
+    pub count: i64,
+    pub users: i64,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct CommandStat {
+    pub command: String,
+    pub count: i64,
+    pub users: i64,
+    pub avg_latency_ms: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FunnelStep {
+    pub step: &'static str,
+    /// Пользователи, прошедшие этот и все предыдущие шаги
+    pub users: i64,
+    /// Доля от пользователей предыдущего шага; у первого шага — `None`
+    pub conversion: Option<f64>,
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+
+/// Настройки группового чата бота
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ChatSettings {
+    pub chat_id: i64,
+    /// Специальность, которой по умолчанию ограничены /search и /browse в чате
+    pub default_specialty: Option<String>,
+    /// Администратор чата, изменивший настройки последним
+    pub updated_by: i64,
+    pub updated_at: DateTime<Utc>,
+}
@@ -1,3 +1,15 @@
+pub mod bot_event;
+pub mod chat_settings;
+pub mod conversation;
+pub mod subscription;
+pub mod upload_cache;
+pub mod user;
 pub mod work;
 
-pub use work::{Work, WorkType, WorkCreateDto, WorkUpdateDto};
\ No newline at end of file
+pub use bot_event::{BotAnalyticsReport, CommandStat, DailyActiveUsers, FunnelStep, NewBotEvent, QueryStat};
+pub use chat_settings::ChatSettings;
+pub use conversation::Conversation;
+pub use subscription::{Subscription, SubscriptionKind};
+pub use upload_cache::CachedUpload;
+pub use user::{User, UserCreateDto, UserRole};
+pub use work::{Work, WorkStatus, WorkType, WorkCreateDto, WorkUpdateDto};
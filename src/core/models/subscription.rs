@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+/// Подписка чата бота на новые работы
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Subscription {
+    pub id: Uuid,
+    pub chat_id: i64,
+    /// Пользователь, оформивший подписку
+    pub user_id: i64,
+    pub kind: SubscriptionKind,
+    pub value: String,
+    /// Язык дайджестов (`ru`, `en`)
+    pub locale: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "subscription_kind", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum SubscriptionKind {
+    /// Специальность: код или начало названия
+    Specialty,
+    /// Ключевое слово работы
+    Tag,
+    /// Часть ФИО руководителя
+    Supervisor,
+}
+
+impl SubscriptionKind {
+    /// Значение ENUM `subscription_kind` в базе данных
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubscriptionKind::Specialty => "specialty",
+            SubscriptionKind::Tag => "tag",
+            SubscriptionKind::Supervisor => "supervisor",
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use sqlx::FromRow;
+
+/// Файл, токен которого удалён из кэша загрузок: из чего загрузить его заново
+#[derive(Debug, Clone, FromRow)]
+pub struct CachedUpload {
+    pub upload_type: String,
+    /// Путь в хранилище; нет у записей, сохранённых до появления колонки
+    pub file_path: Option<String>,
+    pub file_name: Option<String>,
+}
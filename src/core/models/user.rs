@@ -2,25 +2,54 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
+use validator::Validate;
+
+use crate::core::validation::not_blank;
+
+/// Самый короткий допустимый пароль
+pub const MIN_PASSWORD_LENGTH: u64 = 8;
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct User {
     pub id: Uuid,
     pub username: String,
+    #[serde(skip_serializing)]
     pub password_hash: String,
     pub role: UserRole,
     pub full_name: Option<String>,
     pub email: Option<String>,
     pub is_active: bool,
+    /// Привязанный аккаунт МАКС
+    pub max_user_id: Option<i64>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+/// Новый пользователь веб-приложения
+#[derive(Debug, Deserialize, Validate)]
+pub struct UserCreateDto {
+    #[validate(length(max = 100), custom = "not_blank")]
+    pub username: String,
+    #[validate(length(min = "MIN_PASSWORD_LENGTH", max = 1024))]
+    pub password: String,
+    pub role: UserRole,
+    #[validate(length(max = 300))]
+    pub full_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "user_role", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum UserRole {
     Admin,
     Methodist,
     Teacher,
     Student,
-}
\ No newline at end of file
+}
+
+impl UserRole {
+    /// Может ли пользователь публиковать и отклонять присланные работы
+    pub fn can_review(&self) -> bool {
+        matches!(self, UserRole::Admin | UserRole::Methodist | UserRole::Teacher)
+    }
+}
@@ -2,6 +2,9 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
+use validator::Validate;
+
+use crate::core::{models::User, validation::not_blank};
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Work {
@@ -17,13 +20,27 @@ pub struct Work {
     pub keywords: Option<String>,
     pub file_path: String,
     pub thumbnail_path: Option<String>,
+    pub status: WorkStatus,
+    /// Аккаунт МАКС, приславший работу через бота; в ответы API не попадает
+    #[serde(skip_serializing)]
+    pub submitter_max_user_id: Option<i64>,
     #[sqlx(rename = "created_at")]
     pub created_at: DateTime<Utc>,
     #[sqlx(rename = "updated_at")]
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+impl Work {
+    /// Опубликованная работа видна всем, остальные — только приславшему её через бота
+    /// (`max_user_id`) и проверяющим
+    pub fn is_visible_to(&self, max_user_id: Option<i64>, user: Option<&User>) -> bool {
+        self.status == WorkStatus::Published
+            || (max_user_id.is_some() && self.submitter_max_user_id == max_user_id)
+            || user.is_some_and(|u| u.role.can_review())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "work_type")]
 #[sqlx(rename_all = "lowercase")]
 pub enum WorkType {
@@ -37,29 +54,151 @@ pub enum WorkType {
     Other,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl WorkType {
+    pub const ALL: [WorkType; 8] = [
+        WorkType::Article,
+        WorkType::Competition,
+        WorkType::Essay,
+        WorkType::Report,
+        WorkType::Project,
+        WorkType::Presentation,
+        WorkType::Speech,
+        WorkType::Other,
+    ];
+
+    /// Значение ENUM `work_type` в базе данных
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkType::Article => "article",
+            WorkType::Competition => "competition",
+            WorkType::Essay => "essay",
+            WorkType::Report => "report",
+            WorkType::Project => "project",
+            WorkType::Presentation => "presentation",
+            WorkType::Speech => "speech",
+            WorkType::Other => "other",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|wt| wt.as_str() == value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "work_status")]
+#[sqlx(rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum WorkStatus {
+    /// Черновик, виден только автору
+    #[default]
+    Draft,
+    /// Отправлена на проверку
+    Submitted,
+    /// Опубликована в архиве
+    Published,
+    /// Отклонена при проверке
+    Rejected,
+}
+
+impl WorkStatus {
+    /// Значение ENUM `work_status` в базе данных
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkStatus::Draft => "draft",
+            WorkStatus::Submitted => "submitted",
+            WorkStatus::Published => "published",
+            WorkStatus::Rejected => "rejected",
+        }
+    }
+}
+
+/// Первый и последний допустимый год работы (как CHECK в таблице `works`)
+pub const MIN_YEAR: i32 = 1900;
+pub const MAX_YEAR: i32 = 2100;
+
+/// Новая работа. Статус и автора заявки задаёт только сервер: из JSON эти поля не читаются
+#[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct WorkCreateDto {
+    #[validate(length(max = 500), custom = "not_blank")]
     pub title: String,
     pub work_type: WorkType,
+    #[validate(length(max = 200), custom = "not_blank")]
     pub specialty: String,
+    #[validate(length(max = 300), custom = "not_blank")]
     pub author_name: String,
+    #[validate(length(max = 300), custom = "not_blank")]
     pub supervisor_name: String,
+    #[validate(range(min = "MIN_YEAR", max = "MAX_YEAR"))]
     pub year: i32,
     pub annotation: Option<String>,
     pub keywords: Option<String>,
+    #[validate(length(max = 1000), custom = "not_blank")]
     pub file_path: String,
+    #[validate(length(max = 1000))]
     pub thumbnail_path: Option<String>,
+    #[serde(skip_deserializing)]
+    pub status: WorkStatus,
+    #[serde(skip_deserializing)]
+    pub submitter_max_user_id: Option<i64>,
+}
+
+impl WorkCreateDto {
+    /// Значения без пробелов по краям; пустые необязательные поля — `None`
+    pub fn trimmed(self) -> Self {
+        Self {
+            title: trim(self.title),
+            specialty: trim(self.specialty),
+            author_name: trim(self.author_name),
+            supervisor_name: trim(self.supervisor_name),
+            annotation: self.annotation.map(trim).filter(|v| !v.is_empty()),
+            keywords: self.keywords.map(trim).filter(|v| !v.is_empty()),
+            file_path: trim(self.file_path),
+            thumbnail_path: self.thumbnail_path.map(trim).filter(|v| !v.is_empty()),
+            ..self
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Изменение работы: проверяются только переданные поля
+#[derive(Debug, Default, Serialize, Deserialize, Validate)]
 pub struct WorkUpdateDto {
+    #[validate(length(max = 500), custom = "not_blank")]
     pub title: Option<String>,
     pub work_type: Option<WorkType>,
+    #[validate(length(max = 200), custom = "not_blank")]
     pub specialty: Option<String>,
+    #[validate(length(max = 300), custom = "not_blank")]
     pub author_name: Option<String>,
+    #[validate(length(max = 300), custom = "not_blank")]
     pub supervisor_name: Option<String>,
+    #[validate(range(min = "MIN_YEAR", max = "MAX_YEAR"))]
     pub year: Option<i32>,
     pub annotation: Option<String>,
     pub keywords: Option<String>,
+    #[validate(length(max = 1000))]
     pub thumbnail_path: Option<String>,
+}
+
+impl WorkUpdateDto {
+    /// Переданные значения без пробелов по краям
+    pub fn trimmed(self) -> Self {
+        Self {
+            title: self.title.map(trim),
+            specialty: self.specialty.map(trim),
+            author_name: self.author_name.map(trim),
+            supervisor_name: self.supervisor_name.map(trim),
+            annotation: self.annotation.map(trim),
+            keywords: self.keywords.map(trim),
+            thumbnail_path: self.thumbnail_path.map(trim),
+            ..self
+        }
+    }
+}
+
+fn trim(value: String) -> String {
+    match value.trim() {
+        trimmed if trimmed.len() == value.len() => value,
+        trimmed => trimmed.to_string(),
+    }
 }
\ No newline at end of file
@@ -0,0 +1,109 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use crate::core::models::{CommandStat, DailyActiveUsers, NewBotEvent, QueryStat};
+
+pub struct BotEventRepository {
+    pool: PgPool,
+}
+
+impl BotEventRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(&self, event: &NewBotEvent) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO bot_events (user_hash, kind, command, query, result_count, latency_ms, locale, chat_type)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+        )
+        .bind(&event.user_hash)
+        .bind(&event.kind)
+        .bind(&event.command)
+        .bind(&event.query)
+        .bind(event.result_count)
+        .bind(event.latency_ms)
+        .bind(&event.locale)
+        .bind(&event.chat_type)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Уникальные пользователи по дням (UTC)
+    pub async fn daily_active_users(&self, since: DateTime<Utc>) -> Result<Vec<DailyActiveUsers>, sqlx::Error> {
+        sqlx::query_as(
+            "SELECT (created_at AT TIME ZONE 'UTC')::date AS date, COUNT(DISTINCT user_hash) AS users
+             FROM bot_events
+             WHERE created_at >= $1
+             GROUP BY 1
+             ORDER BY 1",
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Самые частые запросы; `zero_results` — только запросы, по которым ничего не нашлось
+    pub async fn top_queries(
+        &self,
+        since: DateTime<Utc>,
+        zero_results: bool,
+        limit: i64,
+    ) -> Result<Vec<QueryStat>, sqlx::Error> {
+        sqlx::query_as(
+            "SELECT query, COUNT(*) AS count, COUNT(DISTINCT user_hash) AS users
+             FROM bot_events
+             WHERE created_at >= $1 AND query IS NOT NULL AND (NOT $2 OR result_count = 0)
+             GROUP BY query
+             ORDER BY count DESC, query
+             LIMIT $3",
+        )
+        .bind(since)
+        .bind(zero_results)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn commands(&self, since: DateTime<Utc>) -> Result<Vec<CommandStat>, sqlx::Error> {
+        sqlx::query_as(
+            "SELECT command, COUNT(*) AS count, COUNT(DISTINCT user_hash) AS users,
+                    AVG(latency_ms)::float8 AS avg_latency_ms
+             FROM bot_events
+             WHERE created_at >= $1 AND command IS NOT NULL
+             GROUP BY command
+             ORDER BY count DESC, command",
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Пользователи на шагах воронки: каждый шаг — набор команд, шаги считаются нарастающим итогом
+    pub async fn funnel(&self, since: DateTime<Utc>, steps: &[&[&str]]) -> Result<Vec<i64>, sqlx::Error> {
+        let mut users = Vec::with_capacity(steps.len());
+        for i in 0..steps.len() {
+            // Пользователь прошёл шаг, если за период выполнил команды этого и всех предыдущих шагов
+            let conditions = (0..=i)
+                .map(|j| format!("bool_or(command = ANY(${}))", j + 2))
+                .collect::<Vec<_>>()
+                .join(" AND ");
+            let sql = format!(
+                "SELECT COUNT(*) FROM (
+                     SELECT user_hash FROM bot_events WHERE created_at >= $1
+                     GROUP BY user_hash HAVING {}
+                 ) AS reached",
+                conditions
+            );
+
+            let mut query = sqlx::query_scalar(&sql).bind(since);
+            for step in &steps[..=i] {
+                query = query.bind(step.to_vec());
+            }
+            users.push(query.fetch_one(&self.pool).await?);
+        }
+
+        Ok(users)
+    }
+}
@@ -0,0 +1,44 @@
+use sqlx::PgPool;
+use crate::core::models::ChatSettings;
+
+pub struct ChatSettingsRepository {
+    pool: PgPool,
+}
+
+impl ChatSettingsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get(&self, chat_id: i64) -> Result<Option<ChatSettings>, sqlx::Error> {
+        sqlx::query_as(
+            "SELECT chat_id, default_specialty, updated_by, updated_at FROM bot_chat_settings WHERE chat_id = $1",
+        )
+        .bind(chat_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Установка или сброс (`None`) специальности чата по умолчанию
+    pub async fn set_default_specialty(
+        &self,
+        chat_id: i64,
+        specialty: Option<&str>,
+        updated_by: i64,
+    ) -> Result<ChatSettings, sqlx::Error> {
+        sqlx::query_as(
+            "INSERT INTO bot_chat_settings (chat_id, default_specialty, updated_by)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (chat_id) DO UPDATE
+             SET default_specialty = EXCLUDED.default_specialty,
+                 updated_by = EXCLUDED.updated_by,
+                 updated_at = NOW()
+             RETURNING chat_id, default_specialty, updated_by, updated_at",
+        )
+        .bind(chat_id)
+        .bind(specialty)
+        .bind(updated_by)
+        .fetch_one(&self.pool)
+        .await
+    }
+}
@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use crate::core::models::Conversation;
+
+pub struct ConversationRepository {
+    pool: PgPool,
+}
+
+impl ConversationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Получение диалога (включая просроченные)
+    pub async fn get(&self, chat_id: i64, user_id: i64) -> Result<Option<Conversation>, sqlx::Error> {
+        let conversation = sqlx::query_as(
+            "SELECT chat_id, user_id, state, expires_at, created_at, updated_at
+             FROM bot_conversations WHERE chat_id = $1 AND user_id = $2",
+        )
+        .bind(chat_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(conversation)
+    }
+
+    /// Сохранение состояния диалога с новым сроком жизни
+    pub async fn upsert(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        state: &serde_json::Value,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Conversation, sqlx::Error> {
+        let conversation = sqlx::query_as(
+            r#"
+            INSERT INTO bot_conversations (chat_id, user_id, state, expires_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (chat_id, user_id) DO UPDATE
+            SET state = EXCLUDED.state,
+                expires_at = EXCLUDED.expires_at,
+                updated_at = NOW()
+            RETURNING chat_id, user_id, state, expires_at, created_at, updated_at
+            "#,
+        )
+        .bind(chat_id)
+        .bind(user_id)
+        .bind(state)
+        .bind(expires_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(conversation)
+    }
+
+    /// Удаление диалога; возвращает его последнее состояние
+    pub async fn delete(&self, chat_id: i64, user_id: i64) -> Result<Option<serde_json::Value>, sqlx::Error> {
+        let state = sqlx::query_scalar(
+            "DELETE FROM bot_conversations WHERE chat_id = $1 AND user_id = $2 RETURNING state",
+        )
+        .bind(chat_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(state)
+    }
+
+    /// Удаление всех просроченных диалогов; возвращает их последние состояния
+    pub async fn delete_expired(&self) -> Result<Vec<serde_json::Value>, sqlx::Error> {
+        let states = sqlx::query_scalar(
+            "DELETE FROM bot_conversations WHERE expires_at <= NOW() RETURNING state",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(states)
+    }
+}
@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub struct LinkCodeRepository {
+    pool: PgPool,
+}
+
+impl LinkCodeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Код, выданный пользователю веб-приложения (вводится в боте)
+    pub async fn create_for_user(&self, code_hash: &str, user_id: Uuid, expires_at: DateTime<Utc>) -> Result<(), sqlx::Error> {
+        // У пользователя может быть только один действующий код
+        sqlx::query("DELETE FROM max_link_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("INSERT INTO max_link_codes (code_hash, user_id, expires_at) VALUES ($1, $2, $3)")
+            .bind(code_hash)
+            .bind(user_id)
+            .bind(expires_at)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Код, выданный ботом пользователю МАКС (вводится в веб-приложении)
+    pub async fn create_for_max_user(&self, code_hash: &str, max_user_id: i64, expires_at: DateTime<Utc>) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM max_link_codes WHERE max_user_id = $1")
+            .bind(max_user_id)
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("INSERT INTO max_link_codes (code_hash, max_user_id, expires_at) VALUES ($1, $2, $3)")
+            .bind(code_hash)
+            .bind(max_user_id)
+            .bind(expires_at)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Погашение действующего кода веб-приложения, возвращает ID пользователя
+    pub async fn consume_user_code(&self, code_hash: &str) -> Result<Option<Uuid>, sqlx::Error> {
+        let user_id = sqlx::query_scalar(
+            "DELETE FROM max_link_codes
+             WHERE code_hash = $1 AND user_id IS NOT NULL AND expires_at > NOW()
+             RETURNING user_id",
+        )
+        .bind(code_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(user_id)
+    }
+
+    /// Погашение действующего кода бота, возвращает ID пользователя МАКС
+    pub async fn consume_max_code(&self, code_hash: &str) -> Result<Option<i64>, sqlx::Error> {
+        let max_user_id = sqlx::query_scalar(
+            "DELETE FROM max_link_codes
+             WHERE code_hash = $1 AND max_user_id IS NOT NULL AND expires_at > NOW()
+             RETURNING max_user_id",
+        )
+        .bind(code_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(max_user_id)
+    }
+}
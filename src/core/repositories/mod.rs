@@ -1,3 +1,17 @@
+pub mod bot_event_repo;
+pub mod chat_settings_repo;
+pub mod conversation_repo;
+pub mod link_code_repo;
+pub mod subscription_repo;
+pub mod upload_cache_repo;
+pub mod user_repo;
 pub mod work_repo;
 
-pub use work_repo::WorkRepository;
\ No newline at end of file
+pub use bot_event_repo::BotEventRepository;
+pub use chat_settings_repo::ChatSettingsRepository;
+pub use conversation_repo::ConversationRepository;
+pub use link_code_repo::LinkCodeRepository;
+pub use subscription_repo::SubscriptionRepository;
+pub use upload_cache_repo::UploadCacheRepository;
+pub use user_repo::UserRepository;
+pub use work_repo::WorkRepository;
@@ -0,0 +1,111 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::models::{Subscription, SubscriptionKind};
+
+const SUBSCRIPTION_COLUMNS: &str = "id, chat_id, user_id, kind, value, locale, created_at";
+
+pub struct SubscriptionRepository {
+    pool: PgPool,
+}
+
+impl SubscriptionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Создание подписки; `None`, если такая подписка в чате уже есть
+    pub async fn create(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        kind: SubscriptionKind,
+        value: &str,
+        locale: &str,
+    ) -> Result<Option<Subscription>, sqlx::Error> {
+        let subscription = sqlx::query_as(&format!(
+            "INSERT INTO bot_subscriptions (chat_id, user_id, kind, value, locale)
+             VALUES ($1, $2, $3::subscription_kind, $4, $5)
+             ON CONFLICT DO NOTHING
+             RETURNING {}",
+            SUBSCRIPTION_COLUMNS
+        ))
+        .bind(chat_id)
+        .bind(user_id)
+        .bind(kind.as_str())
+        .bind(value)
+        .bind(locale)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(subscription)
+    }
+
+    /// Подписки чата в порядке оформления
+    pub async fn list_by_chat(&self, chat_id: i64) -> Result<Vec<Subscription>, sqlx::Error> {
+        let subscriptions = sqlx::query_as(&format!(
+            "SELECT {} FROM bot_subscriptions WHERE chat_id = $1 ORDER BY created_at",
+            SUBSCRIPTION_COLUMNS
+        ))
+        .bind(chat_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(subscriptions)
+    }
+
+    pub async fn count_by_chat(&self, chat_id: i64) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM bot_subscriptions WHERE chat_id = $1")
+            .bind(chat_id)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    /// Удаление подписки чата по ID
+    pub async fn delete(&self, id: Uuid, chat_id: i64) -> Result<Option<Subscription>, sqlx::Error> {
+        let subscription = sqlx::query_as(&format!(
+            "DELETE FROM bot_subscriptions WHERE id = $1 AND chat_id = $2 RETURNING {}",
+            SUBSCRIPTION_COLUMNS
+        ))
+        .bind(id)
+        .bind(chat_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(subscription)
+    }
+
+    /// Удаление всех подписок чата
+    pub async fn delete_by_chat(&self, chat_id: i64) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM bot_subscriptions WHERE chat_id = $1")
+            .bind(chat_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Подписки, под которые подходит работа. Все аргументы — в нижнем регистре, как и значения подписок.
+    /// Специальность сравнивается по началу строки, руководитель — по вхождению, ключевые слова — точно.
+    pub async fn find_matching(
+        &self,
+        specialty: &str,
+        supervisor_name: &str,
+        tags: &[String],
+    ) -> Result<Vec<Subscription>, sqlx::Error> {
+        let subscriptions = sqlx::query_as(&format!(
+            "SELECT {} FROM bot_subscriptions
+             WHERE (kind = 'specialty' AND strpos($1, value) = 1)
+                OR (kind = 'supervisor' AND strpos($2, value) > 0)
+                OR (kind = 'tag' AND value = ANY($3))
+             ORDER BY chat_id, created_at",
+            SUBSCRIPTION_COLUMNS
+        ))
+        .bind(specialty)
+        .bind(supervisor_name)
+        .bind(tags)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(subscriptions)
+    }
+}
@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::core::models::CachedUpload;
+
+pub struct UploadCacheRepository {
+    pool: PgPool,
+}
+
+impl UploadCacheRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Получение токена, загруженного не раньше `not_before`
+    pub async fn get(
+        &self,
+        file_hash: &str,
+        upload_type: &str,
+        not_before: DateTime<Utc>,
+    ) -> Result<Option<String>, sqlx::Error> {
+        let token = sqlx::query_scalar(
+            "SELECT token FROM max_upload_cache
+             WHERE file_hash = $1 AND upload_type = $2 AND created_at >= $3",
+        )
+        .bind(file_hash)
+        .bind(upload_type)
+        .bind(not_before)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(token)
+    }
+
+    /// Сохранение (или замена) токена для файла
+    pub async fn put(
+        &self,
+        file_hash: &str,
+        upload_type: &str,
+        token: &str,
+        file_path: &str,
+        file_name: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO max_upload_cache (file_hash, upload_type, token, file_path, file_name)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (file_hash, upload_type) DO UPDATE
+            SET token = EXCLUDED.token, file_path = EXCLUDED.file_path, file_name = EXCLUDED.file_name, created_at = NOW()
+            "#,
+        )
+        .bind(file_hash)
+        .bind(upload_type)
+        .bind(token)
+        .bind(file_path)
+        .bind(file_name)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Удаление записей с токеном; возвращает, откуда был загружен файл
+    pub async fn delete_by_token(&self, token: &str) -> Result<Option<CachedUpload>, sqlx::Error> {
+        let upload = sqlx::query_as(
+            "DELETE FROM max_upload_cache WHERE token = $1 RETURNING upload_type, file_path, file_name",
+        )
+        .bind(token)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(upload)
+    }
+}
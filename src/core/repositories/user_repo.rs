@@ -0,0 +1,108 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::models::{User, UserRole};
+
+const USER_COLUMNS: &str = "id, username, password_hash, role, full_name, email, is_active, max_user_id, created_at, updated_at";
+
+pub struct UserRepository {
+    pool: PgPool,
+}
+
+impl UserRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Создание пользователя с уже вычисленным хэшем пароля
+    pub async fn create(
+        &self,
+        username: &str,
+        password_hash: &str,
+        role: UserRole,
+        full_name: Option<&str>,
+    ) -> Result<User, sqlx::Error> {
+        let user = sqlx::query_as(&format!(
+            "INSERT INTO users (username, password_hash, role, full_name) VALUES ($1, $2, $3, $4) RETURNING {}",
+            USER_COLUMNS
+        ))
+        .bind(username)
+        .bind(password_hash)
+        .bind(role)
+        .bind(full_name)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(user)
+    }
+
+    /// Новый хэш пароля; `None`, если пользователя нет
+    pub async fn set_password_hash(&self, username: &str, password_hash: &str) -> Result<Option<User>, sqlx::Error> {
+        let user = sqlx::query_as(&format!(
+            "UPDATE users SET password_hash = $1, updated_at = NOW() WHERE username = $2 RETURNING {}",
+            USER_COLUMNS
+        ))
+        .bind(password_hash)
+        .bind(username)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(user)
+    }
+
+    /// Получение пользователя по ID
+    pub async fn get_by_id(&self, id: Uuid) -> Result<Option<User>, sqlx::Error> {
+        let user = sqlx::query_as(&format!("SELECT {} FROM users WHERE id = $1", USER_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(user)
+    }
+
+    /// Получение пользователя по логину
+    pub async fn get_by_username(&self, username: &str) -> Result<Option<User>, sqlx::Error> {
+        let user = sqlx::query_as(&format!("SELECT {} FROM users WHERE username = $1", USER_COLUMNS))
+            .bind(username)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(user)
+    }
+
+    /// Получение пользователя по привязанному аккаунту МАКС
+    pub async fn get_by_max_user_id(&self, max_user_id: i64) -> Result<Option<User>, sqlx::Error> {
+        let user = sqlx::query_as(&format!("SELECT {} FROM users WHERE max_user_id = $1", USER_COLUMNS))
+            .bind(max_user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(user)
+    }
+
+    /// Привязка (или отвязка при `None`) аккаунта МАКС.
+    /// Аккаунт МАКС снимается с любого другого пользователя, к которому был привязан.
+    pub async fn set_max_user_id(&self, id: Uuid, max_user_id: Option<i64>) -> Result<Option<User>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        if let Some(max_user_id) = max_user_id {
+            sqlx::query("UPDATE users SET max_user_id = NULL, updated_at = NOW() WHERE max_user_id = $1 AND id <> $2")
+                .bind(max_user_id)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        let user = sqlx::query_as(&format!(
+            "UPDATE users SET max_user_id = $1, updated_at = NOW() WHERE id = $2 RETURNING {}",
+            USER_COLUMNS
+        ))
+        .bind(max_user_id)
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(user)
+    }
+}
@@ -1,6 +1,6 @@
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 use uuid::Uuid;
-use crate::core::models::{Work, WorkCreateDto, WorkUpdateDto, WorkType};
+use crate::core::models::{Work, WorkCreateDto, WorkStatus, WorkUpdateDto, WorkType};
 
 pub struct WorkRepository {
     pool: PgPool,
@@ -13,6 +13,31 @@ impl WorkRepository {
 
     /// Создание новой работы
     pub async fn create(&self, dto: &WorkCreateDto) -> Result<Work, sqlx::Error> {
+        Self::insert(&self.pool, dto).await
+    }
+
+    /// Создание нескольких работ одной транзакцией: при ошибке не сохраняется ни одна.
+    /// Опубликованные работы сразу отмечаются разосланными.
+    pub async fn create_all_notified(&self, dtos: &[WorkCreateDto]) -> Result<Vec<Work>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let mut works = Vec::with_capacity(dtos.len());
+        for dto in dtos {
+            works.push(Self::insert(&mut *tx, dto).await?);
+        }
+
+        let published: Vec<Uuid> = works.iter().filter(|w| w.status == WorkStatus::Published).map(|w| w.id).collect();
+        sqlx::query("UPDATE works SET notified_at = NOW() WHERE id = ANY($1)")
+            .bind(&published)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(works)
+    }
+
+    async fn insert<'e>(executor: impl PgExecutor<'e>, dto: &WorkCreateDto) -> Result<Work, sqlx::Error> {
         let work_type_str = match dto.work_type {
             WorkType::Article => "article",
             WorkType::Competition => "competition",
@@ -28,11 +53,12 @@ impl WorkRepository {
             r#"
             INSERT INTO works (
                 title, work_type, specialty, author_name, supervisor_name, 
-                year, annotation, keywords, file_path, thumbnail_path
+                year, annotation, keywords, file_path, thumbnail_path,
+                status, submitter_max_user_id
             )
-            VALUES ($1, $2::work_type, $3, $4, $5, $6, $7, $8, $9, $10)
+            VALUES ($1, $2::work_type, $3, $4, $5, $6, $7, $8, $9, $10, $11::work_status, $12)
             RETURNING id, title, work_type, specialty, author_name, supervisor_name, 
-                      year, annotation, keywords, file_path, thumbnail_path, created_at, updated_at
+                      year, annotation, keywords, file_path, thumbnail_path, status, submitter_max_user_id, created_at, updated_at
             "#,
         )
         .bind(&dto.title)
@@ -45,7 +71,9 @@ impl WorkRepository {
         .bind(&dto.keywords)
         .bind(&dto.file_path)
         .bind(&dto.thumbnail_path)
-        .fetch_one(&self.pool)
+        .bind(dto.status.as_str())
+        .bind(dto.submitter_max_user_id)
+        .fetch_one(executor)
         .await?;
 
         Ok(work)
@@ -55,7 +83,7 @@ impl WorkRepository {
     pub async fn get_by_id(&self, id: Uuid) -> Result<Option<Work>, sqlx::Error> {
         let work = sqlx::query_as(
             "SELECT id, title, work_type, specialty, author_name, supervisor_name, 
-                    year, annotation, keywords, file_path, thumbnail_path, created_at, updated_at 
+                    year, annotation, keywords, file_path, thumbnail_path, status, submitter_max_user_id, created_at, updated_at 
              FROM works WHERE id = $1",
         )
         .bind(id)
@@ -65,7 +93,7 @@ impl WorkRepository {
         Ok(work)
     }
 
-    /// Поиск работ с фильтрами
+    /// Поиск опубликованных работ с фильтрами
     pub async fn search(
         &self,
         query: Option<&str>,
@@ -75,16 +103,15 @@ impl WorkRepository {
         limit: i64,
         offset: i64,
     ) -> Result<Vec<Work>, sqlx::Error> {
-        let mut sql = String::from("SELECT id, title, work_type, specialty, author_name, supervisor_name, year, annotation, keywords, file_path, thumbnail_path, created_at, updated_at FROM works WHERE 1=1");
+        let mut sql = String::from("SELECT id, title, work_type, specialty, author_name, supervisor_name, year, annotation, keywords, file_path, thumbnail_path, status, submitter_max_user_id, created_at, updated_at FROM works WHERE status = 'published'");
         let mut args = Vec::new();
         let mut param_index = 1;
 
         // Поиск по полнотекстовому индексу
         if let Some(q) = query {
-            // Преобразуем запрос в формат tsquery: заменяем пробелы на &
-            let ts_query = q.replace(' ', " & ");
-            sql.push_str(&format!(" AND search_vector @@ to_tsquery('russian', ${})", param_index));
-            args.push(ts_query);
+            // websearch_to_tsquery принимает произвольный текст: лишние пробелы и скобки не ломают запрос
+            sql.push_str(&format!(" AND search_vector @@ websearch_to_tsquery('russian', ${})", param_index));
+            args.push(q.to_string());
             param_index += 1;
         }
 
@@ -104,7 +131,8 @@ impl WorkRepository {
 
         // Фильтр по году
         if let Some(y) = year {
-            sql.push_str(&format!(" AND year = ${}", param_index));
+            // Аргументы передаются строками, поэтому год приводится к числу явно
+            sql.push_str(&format!(" AND year = ${}::int", param_index));
             args.push(y.to_string());
             param_index += 1;
         }
@@ -124,10 +152,10 @@ impl WorkRepository {
         Ok(works)
     }
 
-    /// Получение уникальных специальностей
+    /// Получение уникальных специальностей опубликованных работ
     pub async fn list_specialties(&self) -> Result<Vec<String>, sqlx::Error> {
         let specialties = sqlx::query_scalar(
-            "SELECT DISTINCT specialty FROM works ORDER BY specialty",
+            "SELECT DISTINCT specialty FROM works WHERE status = 'published' ORDER BY specialty",
         )
         .fetch_all(&self.pool)
         .await?;
@@ -135,6 +163,20 @@ impl WorkRepository {
         Ok(specialties)
     }
 
+    /// Получение годов, за которые есть работы (опционально — по специальности)
+    pub async fn list_years(&self, specialty: Option<&str>) -> Result<Vec<i32>, sqlx::Error> {
+        let years = sqlx::query_scalar(
+            "SELECT DISTINCT year FROM works
+             WHERE status = 'published' AND ($1::text IS NULL OR specialty ILIKE '%' || $1 || '%')
+             ORDER BY year DESC",
+        )
+        .bind(specialty)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(years)
+    }
+
     /// Обновление работы (простой и надёжный вариант с COALESCE)
     pub async fn update(&self, id: Uuid, dto: &WorkUpdateDto) -> Result<Option<Work>, sqlx::Error> {
         // Преобразуем тип работы в строку для привязки
@@ -165,7 +207,7 @@ impl WorkRepository {
                 updated_at = NOW()
             WHERE id = $10
             RETURNING id, title, work_type, specialty, author_name, supervisor_name, 
-                      year, annotation, keywords, file_path, thumbnail_path, created_at, updated_at
+                      year, annotation, keywords, file_path, thumbnail_path, status, submitter_max_user_id, created_at, updated_at
             "#,
         )
         .bind(dto.title.as_ref())
@@ -184,6 +226,109 @@ impl WorkRepository {
         Ok(work)
     }
 
+    /// Изменение статуса работы
+    pub async fn set_status(&self, id: Uuid, status: WorkStatus) -> Result<Option<Work>, sqlx::Error> {
+        let work = sqlx::query_as(
+            r#"
+            UPDATE works
+            SET status = $1::work_status, updated_at = NOW()
+            WHERE id = $2
+            RETURNING id, title, work_type, specialty, author_name, supervisor_name,
+                      year, annotation, keywords, file_path, thumbnail_path, status, submitter_max_user_id, created_at, updated_at
+            "#,
+        )
+        .bind(status.as_str())
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(work)
+    }
+
+    /// Работы, присланные пользователем МАКС (в любом статусе)
+    pub async fn list_by_submitter(&self, max_user_id: i64, limit: i64) -> Result<Vec<Work>, sqlx::Error> {
+        let works = sqlx::query_as(
+            "SELECT id, title, work_type, specialty, author_name, supervisor_name,
+                    year, annotation, keywords, file_path, thumbnail_path, status, submitter_max_user_id, created_at, updated_at
+             FROM works WHERE submitter_max_user_id = $1
+             ORDER BY created_at DESC LIMIT $2",
+        )
+        .bind(max_user_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(works)
+    }
+
+    /// Работы в указанном статусе, старые первыми
+    pub async fn list_by_status(&self, status: WorkStatus, limit: i64) -> Result<Vec<Work>, sqlx::Error> {
+        let works = sqlx::query_as(
+            "SELECT id, title, work_type, specialty, author_name, supervisor_name,
+                    year, annotation, keywords, file_path, thumbnail_path, status, submitter_max_user_id, created_at, updated_at
+             FROM works WHERE status = $1::work_status
+             ORDER BY created_at ASC LIMIT $2",
+        )
+        .bind(status.as_str())
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(works)
+    }
+
+    /// Опубликованные работы, о которых ещё не разосланы уведомления
+    pub async fn list_unnotified(&self, limit: i64) -> Result<Vec<Work>, sqlx::Error> {
+        let works = sqlx::query_as(
+            "SELECT id, title, work_type, specialty, author_name, supervisor_name,
+                    year, annotation, keywords, file_path, thumbnail_path, status, submitter_max_user_id, created_at, updated_at
+             FROM works WHERE status = 'published' AND notified_at IS NULL
+             ORDER BY created_at ASC LIMIT $1",
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(works)
+    }
+
+    /// Все работы (или только в указанном статусе), старые первыми
+    pub async fn list_all(&self, status: Option<WorkStatus>) -> Result<Vec<Work>, sqlx::Error> {
+        let works = sqlx::query_as(
+            "SELECT id, title, work_type, specialty, author_name, supervisor_name,
+                    year, annotation, keywords, file_path, thumbnail_path, status, submitter_max_user_id, created_at, updated_at
+             FROM works WHERE $1::work_status IS NULL OR status = $1::work_status
+             ORDER BY created_at ASC",
+        )
+        .bind(status.map(|s| s.as_str()))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(works)
+    }
+
+    /// Пересчёт полнотекстового индекса: триггер `update_search_vector` заполняет
+    /// `search_vector` заново при любом UPDATE строки
+    pub async fn rebuild_search_vectors(&self) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("UPDATE works SET search_vector = NULL")
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Отметка о рассылке уведомлений
+    pub async fn mark_notified(&self, ids: &[Uuid]) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE works SET notified_at = NOW() WHERE id = ANY($1)",
+        )
+        .bind(ids)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
     /// Удаление работы
     pub async fn delete(&self, id: Uuid) -> Result<bool, sqlx::Error> {
         let result = sqlx::query(
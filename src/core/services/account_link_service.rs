@@ -0,0 +1,110 @@
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    core::{
+        models::User,
+        repositories::{LinkCodeRepository, UserRepository},
+    },
+    error::AppError,
+};
+
+/// Время действия кода привязки
+const CODE_TTL_MINUTES: i64 = 10;
+const CODE_LENGTH: usize = 8;
+/// Алфавит без похожих символов (0/O, 1/I)
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// Выданный одноразовый код привязки
+#[derive(Debug, Serialize)]
+pub struct LinkCode {
+    pub code: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Привязка аккаунтов МАКС к пользователям архива
+pub struct AccountLinkService {
+    codes: LinkCodeRepository,
+    users: UserRepository,
+}
+
+impl AccountLinkService {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            codes: LinkCodeRepository::new(pool.clone()),
+            users: UserRepository::new(pool),
+        }
+    }
+
+    fn generate_code() -> String {
+        let mut rng = rand::thread_rng();
+        (0..CODE_LENGTH)
+            .map(|_| CODE_ALPHABET[rng.gen_range(0..CODE_ALPHABET.len())] as char)
+            .collect()
+    }
+
+    /// Хэш кода без учёта регистра, пробелов и дефисов
+    fn hash_code(code: &str) -> String {
+        let normalized: String = code
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-')
+            .flat_map(char::to_uppercase)
+            .collect();
+        hex::encode(Sha256::digest(normalized.as_bytes()))
+    }
+
+    /// Код для пользователя веб-приложения, который он введёт в боте командой /link
+    pub async fn issue_web_code(&self, user_id: Uuid) -> Result<LinkCode, AppError> {
+        let code = Self::generate_code();
+        let expires_at = Utc::now() + Duration::minutes(CODE_TTL_MINUTES);
+        self.codes.create_for_user(&Self::hash_code(&code), user_id, expires_at).await?;
+        Ok(LinkCode { code, expires_at })
+    }
+
+    /// Код для пользователя МАКС, который он введёт в веб-приложении
+    pub async fn issue_bot_code(&self, max_user_id: i64) -> Result<LinkCode, AppError> {
+        let code = Self::generate_code();
+        let expires_at = Utc::now() + Duration::minutes(CODE_TTL_MINUTES);
+        self.codes.create_for_max_user(&Self::hash_code(&code), max_user_id, expires_at).await?;
+        Ok(LinkCode { code, expires_at })
+    }
+
+    /// Привязка по коду из веб-приложения; `None` — код неверный или истёк
+    pub async fn link_by_web_code(&self, code: &str, max_user_id: i64) -> Result<Option<User>, AppError> {
+        let Some(user_id) = self.codes.consume_user_code(&Self::hash_code(code)).await? else {
+            return Ok(None);
+        };
+        let user = self.users.set_max_user_id(user_id, Some(max_user_id)).await?;
+        Ok(user)
+    }
+
+    /// Привязка по коду из бота; `None` — код неверный или истёк
+    pub async fn link_by_bot_code(&self, code: &str, user_id: Uuid) -> Result<Option<User>, AppError> {
+        let Some(max_user_id) = self.codes.consume_max_code(&Self::hash_code(code)).await? else {
+            return Ok(None);
+        };
+        let user = self.users.set_max_user_id(user_id, Some(max_user_id)).await?;
+        Ok(user)
+    }
+
+    /// Отвязка аккаунта МАКС; `false`, если он не был привязан
+    pub async fn unlink(&self, max_user_id: i64) -> Result<bool, AppError> {
+        match self.users.get_by_max_user_id(max_user_id).await? {
+            Some(user) => {
+                self.users.set_max_user_id(user.id, None).await?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Активный пользователь архива, привязанный к аккаунту МАКС
+    pub async fn find_by_max_user(&self, max_user_id: i64) -> Result<Option<User>, AppError> {
+        let user = self.users.get_by_max_user_id(max_user_id).await?;
+        Ok(user.filter(|u| u.is_active))
+    }
+}
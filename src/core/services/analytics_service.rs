@@ -0,0 +1,81 @@
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+use crate::{
+    core::{
+        models::{BotAnalyticsReport, FunnelStep, NewBotEvent},
+        repositories::BotEventRepository,
+    },
+    error::AppError,
+};
+
+/// Максимальная длина сохраняемого поискового запроса
+const MAX_QUERY_LENGTH: usize = 200;
+/// Сколько запросов показывать в отчёте
+const TOP_QUERIES_LIMIT: i64 = 20;
+/// Самый длинный период отчёта, дни
+pub const MAX_REPORT_DAYS: u32 = 365;
+
+/// Шаги воронки и команды, которые их засчитывают
+const FUNNEL: &[(&str, &[&str])] = &[
+    ("search", &["search", "browse", "search_page"]),
+    ("work", &["work"]),
+    ("download", &["download"]),
+];
+
+pub struct AnalyticsService {
+    repo: BotEventRepository,
+}
+
+impl AnalyticsService {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            repo: BotEventRepository::new(pool),
+        }
+    }
+
+    /// Запись события; запрос приводится к нижнему регистру и обрезается
+    pub async fn record(&self, mut event: NewBotEvent) -> Result<(), AppError> {
+        event.query = event.query.as_deref().and_then(normalize_query);
+        self.repo.create(&event).await?;
+        Ok(())
+    }
+
+    /// Отчёт за последние `days` дней; период проверяет обработчик запроса
+    pub async fn bot_report(&self, days: u32) -> Result<BotAnalyticsReport, AppError> {
+        let to = Utc::now();
+        let from = to - Duration::days(days as i64);
+
+        let steps: Vec<&[&str]> = FUNNEL.iter().map(|(_, commands)| *commands).collect();
+        let reached = self.repo.funnel(from, &steps).await?;
+        let funnel = FUNNEL
+            .iter()
+            .zip(&reached)
+            .enumerate()
+            .map(|(i, ((step, _), &users))| FunnelStep {
+                step,
+                users,
+                conversion: i
+                    .checked_sub(1)
+                    .map(|prev| reached[prev])
+                    .map(|prev| if prev == 0 { 0.0 } else { users as f64 / prev as f64 }),
+            })
+            .collect();
+
+        Ok(BotAnalyticsReport {
+            from,
+            to,
+            daily_active_users: self.repo.daily_active_users(from).await?,
+            top_queries: self.repo.top_queries(from, false, TOP_QUERIES_LIMIT).await?,
+            zero_result_queries: self.repo.top_queries(from, true, TOP_QUERIES_LIMIT).await?,
+            commands: self.repo.commands(from).await?,
+            funnel,
+        })
+    }
+}
+
+/// Запрос в нижнем регистре с одиночными пробелами; пустой — `None`
+fn normalize_query(query: &str) -> Option<String> {
+    let query = query.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    let query: String = query.chars().take(MAX_QUERY_LENGTH).collect();
+    (!query.is_empty()).then_some(query)
+}
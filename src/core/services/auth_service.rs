@@ -0,0 +1,180 @@
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use chrono::{Duration, Utc};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    config::Secret,
+    core::{
+        models::{user::MIN_PASSWORD_LENGTH, User, UserCreateDto, UserRole},
+        repositories::UserRepository,
+    },
+    error::AppError,
+    t,
+    integrations::max::launch_data::LaunchData,
+};
+
+/// Время жизни сессионного токена
+const SESSION_TTL_HOURS: i64 = 24;
+
+/// Содержимое сессионного JWT
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    /// ID пользователя; нет у пользователя МАКС, не привязавшего аккаунт
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<UserRole>,
+    /// ID пользователя МАКС для сессий, полученных из мини-приложения
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_user_id: Option<i64>,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+/// Владелец сессии: пользователь архива, пользователь МАКС или оба сразу
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub user: Option<User>,
+    /// ID пользователя МАКС: из токена мини-приложения или из привязки аккаунта
+    pub max_user_id: Option<i64>,
+}
+
+pub struct AuthService {
+    users: UserRepository,
+    jwt_secret: Secret<String>,
+}
+
+impl AuthService {
+    pub fn new(pool: PgPool, jwt_secret: Secret<String>) -> Self {
+        Self {
+            users: UserRepository::new(pool),
+            jwt_secret,
+        }
+    }
+
+    /// Хэш пароля в формате PHC (Argon2id)
+    pub fn hash_password(password: &str) -> Result<String, AppError> {
+        let salt = SaltString::generate(&mut OsRng);
+        Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| AppError::Internal(format!("Password hashing failed: {}", e)))
+    }
+
+    fn verify_password(password: &str, password_hash: &str) -> bool {
+        PasswordHash::new(password_hash)
+            .map(|hash| Argon2::default().verify_password(password.as_bytes(), &hash).is_ok())
+            .unwrap_or(false)
+    }
+
+    /// Новый пользователь; занятый логин — `Conflict`
+    pub async fn create_user(&self, dto: UserCreateDto) -> Result<User, AppError> {
+        dto.validate()?;
+
+        let username = dto.username.trim();
+        let password_hash = Self::hash_password(&dto.password)?;
+        match self.users.create(username, &password_hash, dto.role, dto.full_name.as_deref()).await {
+            Ok(user) => Ok(user),
+            Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+                Err(AppError::Conflict(t!("error-username-taken", username = username.to_string())))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Замена пароля пользователя; прежние сессии остаются действительными до истечения
+    pub async fn reset_password(&self, username: &str, password: &str) -> Result<User, AppError> {
+        if (password.chars().count() as u64) < MIN_PASSWORD_LENGTH {
+            return Err(AppError::invalid_field(
+                "password",
+                t!("validation-length-min", min = MIN_PASSWORD_LENGTH),
+            ));
+        }
+
+        let password_hash = Self::hash_password(password)?;
+        self.users.set_password_hash(username, &password_hash).await?.ok_or(AppError::NotFound)
+    }
+
+    /// Вход по логину и паролю, возвращает пользователя и сессионный токен
+    pub async fn login(&self, username: &str, password: &str) -> Result<(User, String), AppError> {
+        let user = self
+            .users
+            .get_by_username(username)
+            .await?
+            .filter(|u| u.is_active && Self::verify_password(password, &u.password_hash))
+            .ok_or(AppError::Unauthorized)?;
+
+        let token = self.issue_token(&user)?;
+        Ok((user, token))
+    }
+
+    /// Вход из мини-приложения МАКС по проверенным данным запуска.
+    /// Если аккаунт МАКС привязан, сессия выдаётся от имени пользователя архива.
+    pub async fn login_max(&self, launch: &LaunchData) -> Result<(Option<User>, String), AppError> {
+        let user = self
+            .users
+            .get_by_max_user_id(launch.user.id)
+            .await?
+            .filter(|u| u.is_active);
+
+        let token = self.encode_claims(user.as_ref(), Some(launch.user.id))?;
+        Ok((user, token))
+    }
+
+    pub fn issue_token(&self, user: &User) -> Result<String, AppError> {
+        self.encode_claims(Some(user), None)
+    }
+
+    fn encode_claims(&self, user: Option<&User>, max_user_id: Option<i64>) -> Result<String, AppError> {
+        let now = Utc::now();
+        let claims = Claims {
+            sub: user.map(|u| u.id),
+            role: user.map(|u| u.role),
+            max_user_id,
+            iat: now.timestamp(),
+            exp: (now + Duration::hours(SESSION_TTL_HOURS)).timestamp(),
+        };
+
+        encode(&Header::default(), &claims, &EncodingKey::from_secret(self.jwt_secret.expose().as_bytes()))
+            .map_err(|e| AppError::Internal(format!("JWT encoding failed: {}", e)))
+    }
+
+    pub fn verify_token(&self, token: &str) -> Result<Claims, AppError> {
+        decode::<Claims>(token, &DecodingKey::from_secret(self.jwt_secret.expose().as_bytes()), &Validation::default())
+            .map(|data| data.claims)
+            .map_err(|_| AppError::Unauthorized)
+    }
+
+    /// Действующий пользователь по токену; сессия пользователя МАКС без привязки сюда не подходит
+    pub async fn authenticate(&self, token: &str) -> Result<User, AppError> {
+        self.session(token).await?.user.ok_or(AppError::Unauthorized)
+    }
+
+    /// Сессия по токену, в том числе пользователя МАКС, не привязавшего аккаунт
+    pub async fn session(&self, token: &str) -> Result<Session, AppError> {
+        let claims = self.verify_token(token)?;
+        let user = match claims.sub {
+            Some(user_id) => Some(
+                self.users
+                    .get_by_id(user_id)
+                    .await?
+                    .filter(|u| u.is_active)
+                    .ok_or(AppError::Unauthorized)?,
+            ),
+            None => None,
+        };
+
+        let max_user_id = claims.max_user_id.or(user.as_ref().and_then(|u| u.max_user_id));
+        if user.is_none() && max_user_id.is_none() {
+            return Err(AppError::Unauthorized);
+        }
+        Ok(Session { user, max_user_id })
+    }
+}
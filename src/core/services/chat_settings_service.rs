@@ -0,0 +1,45 @@
+use sqlx::PgPool;
+use crate::{
+    core::{models::ChatSettings, repositories::ChatSettingsRepository},
+    error::AppError,
+    t,
+};
+
+const MAX_SPECIALTY_LENGTH: usize = 300;
+
+pub struct ChatSettingsService {
+    repo: ChatSettingsRepository,
+}
+
+impl ChatSettingsService {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            repo: ChatSettingsRepository::new(pool),
+        }
+    }
+
+    /// Специальность, которой по умолчанию ограничен поиск в чате
+    pub async fn default_specialty(&self, chat_id: i64) -> Result<Option<String>, AppError> {
+        let settings = self.repo.get(chat_id).await?;
+        Ok(settings.and_then(|s| s.default_specialty))
+    }
+
+    /// Установка специальности чата по умолчанию; `None` снимает ограничение
+    pub async fn set_default_specialty(
+        &self,
+        chat_id: i64,
+        specialty: Option<&str>,
+        updated_by: i64,
+    ) -> Result<ChatSettings, AppError> {
+        let specialty = specialty.map(str::trim);
+        if specialty == Some("") {
+            return Err(AppError::ValidationError(t!("validation-specialty-empty")));
+        }
+        if specialty.is_some_and(|s| s.chars().count() > MAX_SPECIALTY_LENGTH) {
+            return Err(AppError::ValidationError(t!("validation-chat-specialty-too-long")));
+        }
+
+        let settings = self.repo.set_default_specialty(chat_id, specialty, updated_by).await?;
+        Ok(settings)
+    }
+}
@@ -0,0 +1,45 @@
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+use crate::core::{
+    models::Conversation,
+    repositories::ConversationRepository,
+};
+
+pub struct ConversationService {
+    repo: ConversationRepository,
+    ttl: Duration,
+}
+
+impl ConversationService {
+    /// `ttl` — время жизни диалога с момента последнего шага
+    pub fn new(pool: PgPool, ttl: Duration) -> Self {
+        Self {
+            repo: ConversationRepository::new(pool),
+            ttl,
+        }
+    }
+
+    pub async fn get(&self, chat_id: i64, user_id: i64) -> Result<Option<Conversation>, crate::error::AppError> {
+        let conversation = self.repo.get(chat_id, user_id).await?;
+        Ok(conversation)
+    }
+
+    /// Сохраняет состояние и продлевает срок жизни диалога
+    pub async fn save(&self, chat_id: i64, user_id: i64, state: &serde_json::Value) -> Result<Conversation, crate::error::AppError> {
+        let expires_at = Utc::now() + self.ttl;
+        let conversation = self.repo.upsert(chat_id, user_id, state, expires_at).await?;
+        Ok(conversation)
+    }
+
+    /// Удаляет диалог и возвращает его последнее состояние
+    pub async fn finish(&self, chat_id: i64, user_id: i64) -> Result<Option<serde_json::Value>, crate::error::AppError> {
+        let state = self.repo.delete(chat_id, user_id).await?;
+        Ok(state)
+    }
+
+    /// Удаляет просроченные диалоги и возвращает их последние состояния
+    pub async fn purge_expired(&self) -> Result<Vec<serde_json::Value>, crate::error::AppError> {
+        let purged = self.repo.delete_expired().await?;
+        Ok(purged)
+    }
+}
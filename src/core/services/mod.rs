@@ -1,3 +1,17 @@
+pub mod account_link_service;
+pub mod analytics_service;
+pub mod auth_service;
+pub mod chat_settings_service;
+pub mod conversation_service;
+pub mod subscription_service;
+pub mod upload_cache_service;
 pub mod work_service;
 
-pub use work_service::WorkService;
\ No newline at end of file
+pub use account_link_service::AccountLinkService;
+pub use analytics_service::AnalyticsService;
+pub use auth_service::AuthService;
+pub use chat_settings_service::ChatSettingsService;
+pub use conversation_service::ConversationService;
+pub use subscription_service::SubscriptionService;
+pub use upload_cache_service::UploadCacheService;
+pub use work_service::WorkService;
@@ -0,0 +1,89 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::{
+    core::{
+        models::{Subscription, SubscriptionKind, Work},
+        repositories::SubscriptionRepository,
+    },
+    error::AppError,
+    i18n::Locale,
+    t,
+};
+
+/// Сколько подписок можно оформить в одном чате
+pub const MAX_SUBSCRIPTIONS_PER_CHAT: i64 = 20;
+const MAX_VALUE_LENGTH: usize = 300;
+
+pub struct SubscriptionService {
+    repo: SubscriptionRepository,
+}
+
+impl SubscriptionService {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            repo: SubscriptionRepository::new(pool),
+        }
+    }
+
+    /// Новая подписка; `None`, если такая уже оформлена. Дайджесты по ней приходят на языке `locale`.
+    /// Значение хранится в нижнем регистре — сравнение с работами не зависит от локали БД.
+    pub async fn subscribe(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        kind: SubscriptionKind,
+        value: &str,
+        locale: Locale,
+    ) -> Result<Option<Subscription>, AppError> {
+        let value = value.trim().to_lowercase();
+        if value.is_empty() {
+            return Err(AppError::ValidationError(t!("validation-subscription-empty")));
+        }
+        if value.chars().count() > MAX_VALUE_LENGTH {
+            return Err(AppError::ValidationError(t!("validation-subscription-too-long")));
+        }
+        if self.repo.count_by_chat(chat_id).await? >= MAX_SUBSCRIPTIONS_PER_CHAT {
+            return Err(AppError::ValidationError(t!(
+                "validation-subscription-limit",
+                count = MAX_SUBSCRIPTIONS_PER_CHAT
+            )));
+        }
+
+        let subscription = self.repo.create(chat_id, user_id, kind, &value, locale.as_str()).await?;
+        Ok(subscription)
+    }
+
+    pub async fn list(&self, chat_id: i64) -> Result<Vec<Subscription>, AppError> {
+        let subscriptions = self.repo.list_by_chat(chat_id).await?;
+        Ok(subscriptions)
+    }
+
+    pub async fn unsubscribe(&self, id: Uuid, chat_id: i64) -> Result<Option<Subscription>, AppError> {
+        let subscription = self.repo.delete(id, chat_id).await?;
+        Ok(subscription)
+    }
+
+    pub async fn unsubscribe_all(&self, chat_id: i64) -> Result<u64, AppError> {
+        let deleted = self.repo.delete_by_chat(chat_id).await?;
+        Ok(deleted)
+    }
+
+    /// Подписки, которым нужно сообщить о работе
+    pub async fn matching(&self, work: &Work) -> Result<Vec<Subscription>, AppError> {
+        let tags = Self::tags(work.keywords.as_deref().unwrap_or_default());
+        let subscriptions = self
+            .repo
+            .find_matching(&work.specialty.to_lowercase(), &work.supervisor_name.to_lowercase(), &tags)
+            .await?;
+        Ok(subscriptions)
+    }
+
+    /// Ключевые слова работы в нижнем регистре (через запятую или точку с запятой)
+    fn tags(keywords: &str) -> Vec<String> {
+        keywords
+            .split([',', ';'])
+            .map(|tag| tag.trim().trim_start_matches('#').to_lowercase())
+            .filter(|tag| !tag.is_empty())
+            .collect()
+    }
+}
@@ -0,0 +1,48 @@
+use chrono::{Duration, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use crate::core::{models::CachedUpload, repositories::UploadCacheRepository};
+
+/// Сколько дней считать токен загруженного файла действительным
+const TOKEN_TTL_DAYS: i64 = 30;
+
+pub struct UploadCacheService {
+    repo: UploadCacheRepository,
+}
+
+impl UploadCacheService {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            repo: UploadCacheRepository::new(pool),
+        }
+    }
+
+    /// SHA-256 содержимого файла в hex
+    pub fn hash(data: &[u8]) -> String {
+        hex::encode(Sha256::digest(data))
+    }
+
+    pub async fn get(&self, file_hash: &str, upload_type: &str) -> Result<Option<String>, crate::error::AppError> {
+        let not_before = Utc::now() - Duration::days(TOKEN_TTL_DAYS);
+        let token = self.repo.get(file_hash, upload_type, not_before).await?;
+        Ok(token)
+    }
+
+    pub async fn put(
+        &self,
+        file_hash: &str,
+        upload_type: &str,
+        token: &str,
+        file_path: &str,
+        file_name: &str,
+    ) -> Result<(), crate::error::AppError> {
+        self.repo.put(file_hash, upload_type, token, file_path, file_name).await?;
+        Ok(())
+    }
+
+    /// Забыть токен, который платформа больше не принимает
+    pub async fn evict(&self, token: &str) -> Result<Option<CachedUpload>, crate::error::AppError> {
+        let upload = self.repo.delete_by_token(token).await?;
+        Ok(upload)
+    }
+}
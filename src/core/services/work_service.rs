@@ -1,9 +1,12 @@
 use crate::core::{
-    models::{Work, WorkCreateDto, WorkUpdateDto},
+    models::{Work, WorkCreateDto, WorkStatus, WorkUpdateDto},
     repositories::WorkRepository,
 };
+use crate::infrastructure::metrics;
 use sqlx::PgPool;
+use std::time::Instant;
 use uuid::Uuid;
+use validator::Validate;
 
 pub struct WorkService {
     repo: WorkRepository,
@@ -17,27 +20,20 @@ impl WorkService {
     }
 
     pub async fn create(&self, dto: WorkCreateDto) -> Result<Work, crate::error::AppError> {
-        // Валидация
-        if dto.title.trim().is_empty() {
-            return Err(crate::error::AppError::ValidationError("Название работы не может быть пустым".to_string()));
-        }
-        if dto.specialty.trim().is_empty() {
-            return Err(crate::error::AppError::ValidationError("Специальность не может быть пустой".to_string()));
-        }
-        if dto.author_name.trim().is_empty() {
-            return Err(crate::error::AppError::ValidationError("Имя автора не может быть пустым".to_string()));
-        }
-        if dto.supervisor_name.trim().is_empty() {
-            return Err(crate::error::AppError::ValidationError("Имя руководителя не может быть пустым".to_string()));
-        }
-        if dto.year < 1900 || dto.year > 2100 {
-            return Err(crate::error::AppError::ValidationError("Год должен быть в диапазоне 1900-2100".to_string()));
-        }
+        let dto = dto.trimmed();
+        dto.validate()?;
 
         let work = self.repo.create(&dto).await?;
         Ok(work)
     }
 
+    /// Создание нескольких работ одной транзакцией; проверять записи должен вызывающий.
+    /// Опубликованные работы сразу отмечаются разосланными, чтобы не попасть в дайджест.
+    pub async fn create_all_notified(&self, dtos: &[WorkCreateDto]) -> Result<Vec<Work>, crate::error::AppError> {
+        let works = self.repo.create_all_notified(dtos).await?;
+        Ok(works)
+    }
+
     pub async fn get_by_id(&self, id: Uuid) -> Result<Option<Work>, crate::error::AppError> {
         let work = self.repo.get_by_id(id).await?;
         Ok(work)
@@ -53,7 +49,9 @@ impl WorkService {
         limit: u32,
     ) -> Result<Vec<Work>, crate::error::AppError> {
         let offset = ((page - 1) * limit) as i64;
+        let started = Instant::now();
         let works = self.repo.search(query, specialty, work_type, year, limit as i64, offset).await?;
+        metrics::record_search(started.elapsed());
         Ok(works)
     }
 
@@ -62,11 +60,54 @@ impl WorkService {
         Ok(specialties)
     }
 
+    pub async fn list_years(&self, specialty: Option<&str>) -> Result<Vec<i32>, crate::error::AppError> {
+        let years = self.repo.list_years(specialty).await?;
+        Ok(years)
+    }
+
     pub async fn update(&self, id: Uuid, dto: WorkUpdateDto) -> Result<Option<Work>, crate::error::AppError> {
+        let dto = dto.trimmed();
+        dto.validate()?;
         let work = self.repo.update(id, &dto).await?;
         Ok(work)
     }
 
+    pub async fn set_status(&self, id: Uuid, status: WorkStatus) -> Result<Option<Work>, crate::error::AppError> {
+        let work = self.repo.set_status(id, status).await?;
+        Ok(work)
+    }
+
+    pub async fn list_by_submitter(&self, max_user_id: i64, limit: u32) -> Result<Vec<Work>, crate::error::AppError> {
+        let works = self.repo.list_by_submitter(max_user_id, limit as i64).await?;
+        Ok(works)
+    }
+
+    pub async fn list_by_status(&self, status: WorkStatus, limit: u32) -> Result<Vec<Work>, crate::error::AppError> {
+        let works = self.repo.list_by_status(status, limit as i64).await?;
+        Ok(works)
+    }
+
+    pub async fn list_all(&self, status: Option<WorkStatus>) -> Result<Vec<Work>, crate::error::AppError> {
+        let works = self.repo.list_all(status).await?;
+        Ok(works)
+    }
+
+    /// Пересчёт поискового индекса всех работ; возвращает число работ
+    pub async fn rebuild_search_index(&self) -> Result<u64, crate::error::AppError> {
+        let rebuilt = self.repo.rebuild_search_vectors().await?;
+        Ok(rebuilt)
+    }
+
+    pub async fn list_unnotified(&self, limit: u32) -> Result<Vec<Work>, crate::error::AppError> {
+        let works = self.repo.list_unnotified(limit as i64).await?;
+        Ok(works)
+    }
+
+    pub async fn mark_notified(&self, ids: &[Uuid]) -> Result<u64, crate::error::AppError> {
+        let marked = self.repo.mark_notified(ids).await?;
+        Ok(marked)
+    }
+
     pub async fn delete(&self, id: Uuid) -> Result<bool, crate::error::AppError> {
         let deleted = self.repo.delete(id).await?;
         Ok(deleted)
@@ -0,0 +1,22 @@
+//! Правила проверки DTO для `#[validate(custom = "...")]`.
+//! Ограничения длины в DTO совпадают с размерами VARCHAR в схеме БД.
+
+use validator::ValidationError;
+
+use crate::core::models::WorkType;
+
+/// Строка не пустая и состоит не только из пробелов
+pub fn not_blank(value: &str) -> Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        return Err(ValidationError::new("blank"));
+    }
+    Ok(())
+}
+
+/// Значение ENUM `work_type` (для параметров, которые приходят строкой)
+pub fn work_type(value: &str) -> Result<(), ValidationError> {
+    match WorkType::parse(value) {
+        Some(_) => Ok(()),
+        None => Err(ValidationError::new("work_type")),
+    }
+}
@@ -1,5 +1,13 @@
-use axum::{http::StatusCode, response::{IntoResponse, Response}};
-use tracing::error;
+use axum::{
+    http::{header::RETRY_AFTER, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use tracing::{error, warn};
+use validator::{ValidationError, ValidationErrors, ValidationErrorsKind};
+
+use crate::{api::middleware::current_request_id, t};
 
 #[derive(Debug, thiserror::Error)]
 pub enum AppError {
@@ -20,29 +28,204 @@ pub enum AppError {
     
     #[error("Ошибка валидации: {0}")]
     ValidationError(String),
+
+    /// Ошибки в отдельных полях запроса
+    #[error("Ошибка валидации: {}", describe_fields(.0))]
+    InvalidFields(Vec<FieldError>),
+
+    /// Ресурс уже существует или изменён параллельно
+    #[error("Конфликт: {0}")]
+    Conflict(String),
+
+    /// Тело запроса больше допустимого, лимит в байтах
+    #[error("Слишком большой запрос (лимит {0} байт)")]
+    PayloadTooLarge(u64),
+
+    /// Превышен лимит запросов; можно повторить через указанное число секунд
+    #[error("Слишком много запросов")]
+    TooManyRequests(Option<u64>),
+
+    /// Неподдерживаемый тип содержимого
+    #[error("Неподдерживаемый тип содержимого: {0}")]
+    UnsupportedMediaType(String),
+
+    /// Зависимость (база данных, МАКС, хранилище) временно недоступна
+    #[error("Сервис недоступен: {0}")]
+    ServiceUnavailable(String),
     
     #[error("Ошибка базы данных: {0}")]
     DatabaseError(#[from] sqlx::Error),
 }
 
-impl IntoResponse for AppError {
-    fn into_response(self) -> Response {
-        let (status, error_message) = match self {
-            AppError::Internal(msg) => {
-                error!("Внутренняя ошибка: {}", msg);
-                (StatusCode::INTERNAL_SERVER_ERROR, "Внутренняя ошибка сервера".to_string())
+/// Ошибка в поле запроса
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+fn describe_fields(fields: &[FieldError]) -> String {
+    fields.iter().map(|f| format!("{}: {}", f.field, f.message)).collect::<Vec<_>>().join("; ")
+}
+
+/// Тело ответа с ошибкой: `{"error": {...}}`
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: ErrorBody,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorBody {
+    /// Машиночитаемый код ошибки, например `not_found`
+    pub code: &'static str,
+    /// Текст для пользователя на языке запроса
+    pub message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<FieldError>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl AppError {
+    /// Ошибка валидации одного поля
+    pub fn invalid_field(field: impl Into<String>, message: impl Into<String>) -> Self {
+        AppError::InvalidFields(vec![FieldError { field: field.into(), message: message.into() }])
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            AppError::Internal(_) | AppError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::BadRequest(_) | AppError::ValidationError(_) | AppError::InvalidFields(_) => {
+                StatusCode::BAD_REQUEST
             }
-            AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
-            AppError::Unauthorized => (StatusCode::UNAUTHORIZED, "Требуется авторизация".to_string()),
-            AppError::Forbidden => (StatusCode::FORBIDDEN, "Доступ запрещён".to_string()),
-            AppError::NotFound => (StatusCode::NOT_FOUND, "Ресурс не найден".to_string()),
-            AppError::ValidationError(msg) => (StatusCode::BAD_REQUEST, msg),
-            AppError::DatabaseError(e) => {
-                error!("Ошибка базы данных: {:?}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, "Ошибка базы данных".to_string())
+            AppError::Unauthorized => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden => StatusCode::FORBIDDEN,
+            AppError::NotFound => StatusCode::NOT_FOUND,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            AppError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            AppError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+
+    /// Машиночитаемый код ошибки для клиентов API
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Internal(_) => "internal_error",
+            AppError::BadRequest(_) => "bad_request",
+            AppError::Unauthorized => "unauthorized",
+            AppError::Forbidden => "forbidden",
+            AppError::NotFound => "not_found",
+            AppError::ValidationError(_) | AppError::InvalidFields(_) => "validation_failed",
+            AppError::Conflict(_) => "conflict",
+            AppError::PayloadTooLarge(_) => "payload_too_large",
+            AppError::TooManyRequests(_) => "too_many_requests",
+            AppError::UnsupportedMediaType(_) => "unsupported_media_type",
+            AppError::ServiceUnavailable(_) => "service_unavailable",
+            AppError::DatabaseError(_) => "database_error",
+        }
+    }
+
+    /// Текст ошибки для пользователя на языке текущего запроса, без внутренних подробностей
+    pub fn user_message(&self) -> String {
+        match self {
+            AppError::Internal(_) => t!("error-internal"),
+            AppError::BadRequest(msg) | AppError::ValidationError(msg) | AppError::Conflict(msg) => msg.clone(),
+            AppError::InvalidFields(fields) => match fields.as_slice() {
+                [field] => field.message.clone(),
+                _ => t!("error-validation"),
+            },
+            AppError::Unauthorized => t!("error-unauthorized"),
+            AppError::Forbidden => t!("error-forbidden"),
+            AppError::NotFound => t!("error-not-found"),
+            AppError::PayloadTooLarge(limit) => t!("error-payload-too-large", limit = limit.div_ceil(1024 * 1024)),
+            AppError::TooManyRequests(_) => t!("error-too-many-requests"),
+            AppError::UnsupportedMediaType(content_type) => {
+                t!("error-unsupported-media-type", content_type = content_type.clone())
             }
+            AppError::ServiceUnavailable(_) => t!("error-service-unavailable"),
+            AppError::DatabaseError(_) => t!("error-database"),
+        }
+    }
+}
+
+/// Все ошибки `validator` — по одной на поле, вложенные поля через точку: `authors[0].name`
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        let mut fields = Vec::new();
+        collect_field_errors(&errors, "", &mut fields);
+        fields.sort_by(|a, b| a.field.cmp(&b.field));
+        AppError::InvalidFields(fields)
+    }
+}
+
+fn collect_field_errors(errors: &ValidationErrors, prefix: &str, out: &mut Vec<FieldError>) {
+    for (field, kind) in errors.errors() {
+        let path = if prefix.is_empty() { field.to_string() } else { format!("{}.{}", prefix, field) };
+        match kind {
+            ValidationErrorsKind::Field(errors) => {
+                out.extend(errors.iter().map(|e| FieldError { field: path.clone(), message: validation_message(e) }));
+            }
+            ValidationErrorsKind::Struct(nested) => collect_field_errors(nested, &path, out),
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect_field_errors(nested, &format!("{}[{}]", path, index), out);
+                }
+            }
+        }
+    }
+}
+
+/// Текст ошибки правила на языке запроса; собственное сообщение правила имеет приоритет
+fn validation_message(error: &ValidationError) -> String {
+    if let Some(ref message) = error.message {
+        return message.to_string();
+    }
+
+    // Границы `range` хранятся как f64 — 1900.0 показываем как 1900
+    let param = |name: &str| {
+        error.params.get(name).map(|v| match v.as_f64() {
+            Some(n) if n.fract() == 0.0 => format!("{}", n as i64),
+            _ => v.to_string(),
+        })
+    };
+    match (error.code.as_ref(), param("min"), param("max")) {
+        ("blank", _, _) => t!("validation-blank"),
+        ("length", Some(min), Some(max)) => t!("validation-length-range", min = min, max = max),
+        ("length", None, Some(max)) => t!("validation-length-max", max = max),
+        ("length", Some(min), None) => t!("validation-length-min", min = min),
+        ("range", Some(min), Some(max)) => t!("validation-range", min = min, max = max),
+        ("work_type", _, _) => t!("validation-work-type"),
+        _ => t!("validation-invalid"),
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        match &self {
+            AppError::Internal(msg) => error!("Внутренняя ошибка: {}", msg),
+            AppError::DatabaseError(e) => error!("Ошибка базы данных: {:?}", e),
+            AppError::ServiceUnavailable(reason) => warn!("Сервис недоступен: {}", reason),
+            _ => {}
+        }
+
+        let body = ErrorResponse {
+            error: ErrorBody {
+                code: self.code(),
+                message: self.user_message(),
+                details: match &self {
+                    AppError::InvalidFields(fields) => fields.clone(),
+                    _ => Vec::new(),
+                },
+                request_id: current_request_id(),
+            },
         };
 
-        (status, error_message).into_response()
+        let mut response = (self.status(), Json(body)).into_response();
+        if let AppError::TooManyRequests(Some(secs)) = self {
+            response.headers_mut().insert(RETRY_AFTER, HeaderValue::from(secs));
+        }
+        response
     }
-}
\ No newline at end of file
+}
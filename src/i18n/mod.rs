@@ -0,0 +1,148 @@
+//! Каталог сообщений (Fluent) на русском и английском.
+//!
+//! Язык текущего запроса хранится в task-local переменной: вебхук бота выставляет его по
+//! `user_locale`, HTTP API — по заголовку `Accept-Language`. Вне этих областей используется русский.
+
+use std::{future::Future, sync::OnceLock};
+
+use fluent_bundle::{concurrent::FluentBundle, FluentResource};
+use tracing::warn;
+use unic_langid::LanguageIdentifier;
+
+pub use fluent_bundle::FluentArgs;
+
+const RU_FTL: &str = include_str!("../../locales/ru.ftl");
+const EN_FTL: &str = include_str!("../../locales/en.ftl");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Locale {
+    #[default]
+    Ru,
+    En,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::Ru, Locale::En];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::Ru => "ru",
+            Locale::En => "en",
+        }
+    }
+
+    /// Язык по тегу вида `en`, `en-US`, `ru_RU`; неподдерживаемые языки — `None`
+    pub fn parse(tag: &str) -> Option<Locale> {
+        let language = tag.trim().split(['-', '_']).next()?.to_lowercase();
+        match language.as_str() {
+            "ru" => Some(Locale::Ru),
+            "en" => Some(Locale::En),
+            _ => None,
+        }
+    }
+
+    /// Язык пользователя МАКС; неизвестный или не переданный — русский
+    pub fn from_user_locale(user_locale: Option<&str>) -> Locale {
+        user_locale.and_then(Locale::parse).unwrap_or_default()
+    }
+
+    /// Самый предпочтительный поддерживаемый язык из заголовка `Accept-Language`
+    pub fn from_accept_language(header: &str) -> Locale {
+        let mut best: Option<(f32, Locale)> = None;
+
+        for item in header.split(',') {
+            let mut parts = item.split(';');
+            let Some(locale) = parts.next().and_then(Locale::parse) else {
+                continue;
+            };
+            let quality = parts
+                .find_map(|p| p.trim().strip_prefix("q="))
+                .and_then(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            if quality > 0.0 && best.is_none_or(|(q, _)| quality > q) {
+                best = Some((quality, locale));
+            }
+        }
+
+        best.map(|(_, locale)| locale).unwrap_or_default()
+    }
+
+    fn bundle(&self) -> &'static FluentBundle<FluentResource> {
+        static RU: OnceLock<FluentBundle<FluentResource>> = OnceLock::new();
+        static EN: OnceLock<FluentBundle<FluentResource>> = OnceLock::new();
+
+        match self {
+            Locale::Ru => RU.get_or_init(|| build_bundle(*self, RU_FTL)),
+            Locale::En => EN.get_or_init(|| build_bundle(*self, EN_FTL)),
+        }
+    }
+}
+
+fn build_bundle(locale: Locale, source: &'static str) -> FluentBundle<FluentResource> {
+    let language: LanguageIdentifier = locale.as_str().parse().expect("Некорректный код языка");
+    let resource = FluentResource::try_new(source.to_string())
+        .unwrap_or_else(|(_, errors)| panic!("Ошибки в каталоге {}: {:?}", locale.as_str(), errors));
+
+    let mut bundle = FluentBundle::new_concurrent(vec![language]);
+    // Без символов изоляции Unicode вокруг подстановок — они мешают в HTML-сообщениях
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(resource)
+        .unwrap_or_else(|errors| panic!("Повторяющиеся ключи в каталоге {}: {:?}", locale.as_str(), errors));
+    bundle
+}
+
+tokio::task_local! {
+    static CURRENT: Locale;
+}
+
+/// Язык текущего запроса или обновления бота
+pub fn current() -> Locale {
+    CURRENT.try_with(|locale| *locale).unwrap_or_default()
+}
+
+/// Выполняет `future` с заданным языком сообщений
+pub async fn scope<F: Future>(locale: Locale, future: F) -> F::Output {
+    CURRENT.scope(locale, future).await
+}
+
+/// Есть ли сообщение с таким ключом в каталоге языка
+pub fn has_message(locale: Locale, key: &str) -> bool {
+    locale.bundle().has_message(key)
+}
+
+/// Сообщение из каталога. Если в языке нет ключа — берётся русский вариант, если нет и его — сам ключ.
+pub fn message(locale: Locale, key: &str, args: Option<&FluentArgs>) -> String {
+    let found = locale
+        .bundle()
+        .get_message(key)
+        .map(|m| (locale, m))
+        .or_else(|| Locale::Ru.bundle().get_message(key).map(|m| (Locale::Ru, m)));
+
+    let Some((locale, pattern)) = found.and_then(|(locale, m)| m.value().map(|p| (locale, p))) else {
+        warn!("В каталоге нет сообщения {}", key);
+        return key.to_string();
+    };
+
+    let mut errors = Vec::new();
+    let text = locale.bundle().format_pattern(pattern, args, &mut errors);
+    if !errors.is_empty() {
+        warn!("Ошибки подстановки в сообщении {}: {:?}", key, errors);
+    }
+    text.into_owned()
+}
+
+/// Сообщение из каталога на языке текущего запроса:
+/// `t!("search-results", filter = text)`
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::message($crate::i18n::current(), $key, None)
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = $crate::i18n::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::message($crate::i18n::current(), $key, Some(&args))
+    }};
+}
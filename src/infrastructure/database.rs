@@ -1,13 +1,138 @@
+use chrono::{DateTime, Utc};
+use sqlx::migrate::{MigrateError, Migrator};
 use sqlx::postgres::{PgPoolOptions, PgConnectOptions};
 use sqlx::PgPool;
+use std::collections::HashMap;
 use std::time::Duration;
+use tracing::{info, warn};
+
+/// Миграции из каталога `migrations`, встроенные в бинарник
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// Размер пула и тайм-ауты подключений к базе
+#[derive(Debug, Clone)]
+pub struct PoolSettings {
+    pub max_connections: u32,
+    /// Сколько соединений держать открытыми даже без нагрузки
+    pub min_connections: u32,
+    /// Сколько ждать свободного соединения из пула
+    pub acquire_timeout: Duration,
+    /// Через сколько закрывать простаивающее соединение; `None` — не закрывать
+    pub idle_timeout: Option<Duration>,
+    /// `statement_timeout` сессии Postgres; `None` — запросы не ограничены по времени
+    pub statement_timeout: Option<Duration>,
+}
+
+impl Default for PoolSettings {
+    fn default() -> Self {
+        Self {
+            max_connections: 10,
+            min_connections: 0,
+            acquire_timeout: Duration::from_secs(3),
+            idle_timeout: Some(Duration::from_secs(10 * 60)),
+            statement_timeout: Some(Duration::from_secs(30)),
+        }
+    }
+}
+
+pub async fn connect(database_url: &str, settings: &PoolSettings) -> Result<PgPool, sqlx::Error> {
+    let mut options = database_url.parse::<PgConnectOptions>()?;
+    if let Some(timeout) = settings.statement_timeout {
+        options = options.options([("statement_timeout", timeout.as_millis().to_string())]);
+    }
 
-pub async fn connect(database_url: &str) -> Result<PgPool, sqlx::Error> {
-    let options = database_url.parse::<PgConnectOptions>()?;
-    
     PgPoolOptions::new()
-        .max_connections(10)
-        .acquire_timeout(Duration::from_secs(3))
+        .max_connections(settings.max_connections)
+        .min_connections(settings.min_connections)
+        .acquire_timeout(settings.acquire_timeout)
+        .idle_timeout(settings.idle_timeout)
         .connect_with(options)
         .await
-}
\ No newline at end of file
+}
+
+/// Встроенная миграция и её состояние в базе
+#[derive(Debug, Clone)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    /// Когда применена; `None` — ещё не применена
+    pub applied_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SchemaError {
+    #[error("Схема базы устарела: не применены миграции {0:?}. Выполните `max_app-admin migrate up` или включите `database_auto_migrate`")]
+    Outdated(Vec<i64>),
+
+    #[error("Не удалось применить миграции: {0}")]
+    Migrate(#[from] MigrateError),
+
+    #[error("Ошибка базы данных: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+/// Состояние всех встроенных миграций по порядку версий
+pub async fn migration_status(pool: &PgPool) -> Result<Vec<MigrationStatus>, sqlx::Error> {
+    let applied = applied_migrations(pool).await?;
+
+    Ok(MIGRATOR
+        .iter()
+        .map(|m| MigrationStatus {
+            version: m.version,
+            description: m.description.to_string(),
+            applied_at: applied.get(&m.version).copied(),
+        })
+        .collect())
+}
+
+/// Версии встроенных миграций, ещё не применённых к базе
+pub async fn pending_migrations(pool: &PgPool) -> Result<Vec<i64>, sqlx::Error> {
+    let status = migration_status(pool).await?;
+    Ok(status.into_iter().filter(|m| m.applied_at.is_none()).map(|m| m.version).collect())
+}
+
+/// Применяет недостающие миграции; возвращает версии, которые были применены сейчас
+pub async fn migrate(pool: &PgPool) -> Result<Vec<i64>, SchemaError> {
+    let pending = pending_migrations(pool).await?;
+    MIGRATOR.run(pool).await?;
+    for version in &pending {
+        info!("🗄️ Применена миграция {}", version);
+    }
+    Ok(pending)
+}
+
+/// Проверка схемы при запуске: с `auto_migrate` недостающие миграции применяются,
+/// без него отставшая схема — ошибка, и приложение не запускается
+pub async fn ensure_schema(pool: &PgPool, auto_migrate: bool) -> Result<(), SchemaError> {
+    let applied = applied_migrations(pool).await?;
+    let unknown: Vec<i64> = applied.keys().filter(|v| MIGRATOR.iter().all(|m| m.version != **v)).copied().collect();
+    if !unknown.is_empty() {
+        warn!("⚠️ В базе есть миграции, неизвестные этой версии приложения: {:?}", unknown);
+    }
+
+    if auto_migrate {
+        migrate(pool).await?;
+        return Ok(());
+    }
+
+    let pending = pending_migrations(pool).await?;
+    if pending.is_empty() {
+        Ok(())
+    } else {
+        Err(SchemaError::Outdated(pending))
+    }
+}
+
+/// Успешно применённые миграции из `_sqlx_migrations`; таблицы ещё нет — пусто
+async fn applied_migrations(pool: &PgPool) -> Result<HashMap<i64, DateTime<Utc>>, sqlx::Error> {
+    let table_exists: bool = sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+        .fetch_one(pool)
+        .await?;
+    if !table_exists {
+        return Ok(HashMap::new());
+    }
+
+    let rows: Vec<(i64, DateTime<Utc>)> =
+        sqlx::query_as("SELECT version, installed_on FROM _sqlx_migrations WHERE success").fetch_all(pool).await?;
+    Ok(rows.into_iter().collect())
+}
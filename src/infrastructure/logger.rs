@@ -0,0 +1,212 @@
+//! Логирование: текст или JSON, файлы с ротацией и маскирование секретов в каждой строке журнала.
+
+use std::{borrow::Cow, io, sync::{Arc, OnceLock}};
+
+use axum::http::Request;
+use regex::Regex;
+use serde::Deserialize;
+use tracing::{info_span, Span, Subscriber};
+use tracing_appender::{
+    non_blocking::WorkerGuard,
+    rolling::{self, RollingFileAppender},
+};
+use tracing_subscriber::{
+    fmt::{self, MakeWriter},
+    layer::SubscriberExt,
+    registry::LookupSpan,
+    util::{SubscriberInitExt, TryInitError},
+    EnvFilter, Layer,
+};
+
+use crate::{api::middleware::REQUEST_ID_HEADER, config::Config};
+
+/// Чем заменяются секреты в журнале
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Секреты короче не ищутся по значению — слишком много ложных совпадений
+const MIN_SECRET_LENGTH: usize = 8;
+
+/// Формат строк журнала
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Для человека: цветной текст в консоли
+    #[default]
+    Text,
+    /// Одна JSON-запись на строку — для сборщиков логов
+    Json,
+}
+
+/// Как часто начинать новый файл журнала
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRotation {
+    Hourly,
+    #[default]
+    Daily,
+    Never,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LoggerError {
+    #[error("Не удалось открыть файл журнала: {0}")]
+    File(#[from] rolling::InitError),
+
+    #[error("Логирование уже настроено: {0}")]
+    Init(#[from] TryInitError),
+}
+
+/// Настраивает глобальный подписчик `tracing` по конфигурации.
+/// `RUST_LOG` имеет приоритет над `log_level`. Пока жив возвращённый guard, записи дописываются в файл.
+pub fn init(config: &Config) -> Result<Option<WorkerGuard>, LoggerError> {
+    let secrets = [
+        Some(&config.max_bot_token),
+        Some(&config.jwt_secret),
+        Some(&config.max_webhook_secret),
+        Some(&config.analytics_salt),
+        config.metrics_token.as_ref(),
+    ];
+    let redactor = Arc::new(Redactor::new(secrets.into_iter().flatten().map(|s| s.expose().clone())));
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&config.log_level));
+
+    let (file, guard) = match config.log_dir {
+        Some(ref dir) => {
+            let rotation = match config.log_rotation {
+                LogRotation::Hourly => rolling::Rotation::HOURLY,
+                LogRotation::Daily => rolling::Rotation::DAILY,
+                LogRotation::Never => rolling::Rotation::NEVER,
+            };
+            let mut builder = RollingFileAppender::builder()
+                .rotation(rotation)
+                .filename_prefix("max_app")
+                .filename_suffix("log");
+            if config.log_max_files > 0 {
+                builder = builder.max_log_files(config.log_max_files);
+            }
+            let (writer, guard) = tracing_appender::non_blocking(builder.build(dir)?);
+            (Some(layer(config.log_format, Redacting::new(writer, redactor.clone()), false)), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(layer(config.log_format, Redacting::new(io::stdout, redactor), true))
+        .with(file)
+        .try_init()?;
+
+    Ok(guard)
+}
+
+/// Слой вывода журнала в выбранном формате
+pub fn layer<S, W>(format: LogFormat, writer: W, ansi: bool) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    match format {
+        LogFormat::Text => fmt::layer().with_ansi(ansi).with_writer(writer).boxed(),
+        LogFormat::Json => fmt::layer()
+            .json()
+            .flatten_event(true)
+            .with_current_span(true)
+            .with_span_list(false)
+            .with_writer(writer)
+            .boxed(),
+    }
+}
+
+/// Span HTTP-запроса для `TraceLayer`: метод, путь без query-строки и идентификатор запроса
+pub fn http_span<B>(request: &Request<B>) -> Span {
+    let request_id = request.headers().get(REQUEST_ID_HEADER).and_then(|v| v.to_str().ok()).unwrap_or_default();
+    info_span!("http", method = %request.method(), path = %request.uri().path(), request_id = %request_id)
+}
+
+/// Маскирует секреты: известные значения (токен бота, секрет JWT) и значения полей с «опасными» именами
+pub struct Redactor {
+    secrets: Vec<String>,
+}
+
+impl Redactor {
+    pub fn new(secrets: impl IntoIterator<Item = String>) -> Self {
+        let mut secrets: Vec<String> = secrets.into_iter().filter(|s| s.len() >= MIN_SECRET_LENGTH).collect();
+        // Длинные первыми, чтобы секрет, содержащий другой, не маскировался по частям
+        secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+        Self { secrets }
+    }
+
+    pub fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut text = Cow::Borrowed(text);
+
+        for secret in &self.secrets {
+            if text.contains(secret.as_str()) {
+                text = Cow::Owned(text.replace(secret.as_str(), REDACTED));
+            }
+        }
+
+        for (pattern, replacement) in patterns() {
+            if let Cow::Owned(replaced) = pattern.replace_all(&text, *replacement) {
+                text = Cow::Owned(replaced);
+            }
+        }
+
+        text
+    }
+}
+
+/// `password=…`, `"token": "…"`, `Authorization: Bearer …`, пароль в строке подключения
+fn patterns() -> &'static [(Regex, &'static str)] {
+    static PATTERNS: OnceLock<Vec<(Regex, &'static str)>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        [
+            (
+                r#"(?i)((?:authorization|password|passwd|secret|token|init_data)\w*\\?"?\s*[:=]\s*\\?"?)(?:bearer\s+)?[^\s"\\,;&}]+"#,
+                "${1}[REDACTED]",
+            ),
+            (r"(?i)(bearer\s+)[\w\-.~+/]+=*", "${1}[REDACTED]"),
+            (r"(://[^:/@\s]+:)[^@\s]+@", "${1}[REDACTED]@"),
+        ]
+        .into_iter()
+        .map(|(pattern, replacement)| (Regex::new(pattern).expect("некорректный шаблон маскирования"), replacement))
+        .collect()
+    })
+}
+
+/// Обёртка над `MakeWriter`, которая пропускает каждую запись журнала через [`Redactor`]
+#[derive(Clone)]
+pub struct Redacting<M> {
+    inner: M,
+    redactor: Arc<Redactor>,
+}
+
+impl<M> Redacting<M> {
+    pub fn new(inner: M, redactor: Arc<Redactor>) -> Self {
+        Self { inner, redactor }
+    }
+}
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for Redacting<M> {
+    type Writer = RedactingWriter<'a, M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter { inner: self.inner.make_writer(), redactor: &self.redactor }
+    }
+}
+
+/// `fmt` отдаёт запись целиком одним вызовом `write`, поэтому секрет не разрывается между вызовами
+pub struct RedactingWriter<'a, W> {
+    inner: W,
+    redactor: &'a Redactor,
+}
+
+impl<W: io::Write> io::Write for RedactingWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let text = String::from_utf8_lossy(buf);
+        self.inner.write_all(self.redactor.redact(&text).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,103 @@
+//! Метрики Prometheus: HTTP-запросы, пул БД, поиск, вызовы MAX Bot API, вебхук и хранилище.
+//!
+//! Метрики пишутся через фасад `metrics` в глобальный рекордер; [`install`] ставит его один раз на процесс.
+
+use std::{
+    sync::OnceLock,
+    time::Duration,
+};
+
+use ::metrics::{counter, gauge, histogram};
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use sqlx::PgPool;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+use super::storage::FileStorage;
+
+/// Период пересчёта размера хранилища
+const STORAGE_SIZE_INTERVAL_SECS: u64 = 5 * 60;
+
+/// Границы корзин гистограмм длительности, секунды
+const DURATION_BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+/// Подпись маршрута для запросов, не попавших ни в один маршрут: иначе каждый
+/// случайный адрес стал бы отдельной серией
+pub const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Рекордер Prometheus для всего процесса; повторные вызовы возвращают тот же handle
+pub fn install() -> PrometheusHandle {
+    static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+    HANDLE
+        .get_or_init(|| {
+            PrometheusBuilder::new()
+                .set_buckets(DURATION_BUCKETS)
+                .expect("пустой список корзин гистограммы")
+                .install_recorder()
+                .expect("Не удалось установить рекордер метрик")
+        })
+        .clone()
+}
+
+/// Обработанный HTTP-запрос; `route` — шаблон маршрута (`/api/works/:id`), а не фактический путь
+pub fn record_http_request(method: &str, route: &str, status: u16, elapsed: Duration) {
+    counter!("http_requests_total", "method" => method.to_string(), "route" => route.to_string(), "status" => status.to_string())
+        .increment(1);
+    histogram!("http_request_duration_seconds", "method" => method.to_string(), "route" => route.to_string())
+        .record(elapsed.as_secs_f64());
+}
+
+/// Поиск работ в базе (из API и из бота)
+pub fn record_search(elapsed: Duration) {
+    histogram!("search_query_duration_seconds").record(elapsed.as_secs_f64());
+}
+
+/// Попытка вызова MAX Bot API; `outcome` — `success` или вид ошибки
+pub fn record_max_api_call(endpoint: &str, outcome: &'static str) {
+    counter!("max_api_requests_total", "endpoint" => endpoint.to_string(), "outcome" => outcome).increment(1);
+}
+
+/// Обновление, пришедшее на вебхук
+pub fn record_webhook_update(update_type: &'static str) {
+    counter!("max_webhook_updates_total", "update_type" => update_type).increment(1);
+}
+
+/// Путь метода MAX Bot API без идентификаторов: `/chats/-5001/members` → `/chats/:id/members`
+pub fn max_api_endpoint(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.trim_start_matches('-').parse::<u64>() {
+            Ok(_) => ":id",
+            Err(_) => segment,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Состояние пула снимается в момент запроса `/metrics`
+pub fn record_snapshot(pool: &PgPool) {
+    let size = pool.size();
+    let idle = pool.num_idle() as u32;
+    gauge!("db_pool_connections", "state" => "idle").set(idle as f64);
+    gauge!("db_pool_connections", "state" => "active").set(size.saturating_sub(idle) as f64);
+    gauge!("db_pool_max_connections").set(pool.options().get_max_connections() as f64);
+}
+
+/// Размер хранилища: обходит все файлы, поэтому считается фоновой задачей, а не на каждый запрос `/metrics`
+pub async fn record_storage_size(storage: &FileStorage) {
+    match storage.total_size().await {
+        Ok(bytes) => gauge!("storage_bytes").set(bytes as f64),
+        Err(e) => warn!("Не удалось посчитать размер хранилища {:?}: {}", storage.root(), e),
+    }
+}
+
+/// Фоновая задача: периодически пересчитывает размер хранилища; останавливается по `shutdown`
+pub async fn storage_size_task(storage: FileStorage, shutdown: CancellationToken) {
+    let mut interval = tokio::time::interval(Duration::from_secs(STORAGE_SIZE_INTERVAL_SECS));
+
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = interval.tick() => record_storage_size(&storage).await,
+        }
+    }
+}
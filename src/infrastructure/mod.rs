@@ -1,3 +1,6 @@
 pub mod database;
 pub mod logger;
-pub mod storage;
\ No newline at end of file
+pub mod metrics;
+pub mod shutdown;
+pub mod storage;
+pub mod thumbnails;
\ No newline at end of file
@@ -0,0 +1,58 @@
+//! Плавная остановка: по SIGTERM/SIGINT сервер перестаёт принимать соединения и дожидается начатых
+//! запросов, фоновые задачи доделывают текущий проход и выходят.
+
+use std::io;
+
+use axum::Router;
+use axum_server::{tls_rustls::RustlsConfig, Handle};
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+/// Ждёт SIGINT (Ctrl+C) или SIGTERM (остановка контейнера)
+pub async fn signal() {
+    let interrupt = async {
+        tokio::signal::ctrl_c().await.expect("Не удалось установить обработчик SIGINT");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Не удалось установить обработчик SIGTERM")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = interrupt => info!("🛑 Получен SIGINT, останавливаемся"),
+        _ = terminate => info!("🛑 Получен SIGTERM, останавливаемся"),
+    }
+}
+
+/// Сервер на готовом сокете; после отмены `shutdown` новые соединения не принимаются,
+/// а future завершается, когда ответят все начатые запросы
+pub async fn serve(
+    listener: TcpListener,
+    app: Router,
+    tls: Option<RustlsConfig>,
+    shutdown: CancellationToken,
+) -> io::Result<()> {
+    match tls {
+        Some(tls) => {
+            let handle = Handle::new();
+            let stop = handle.clone();
+            tokio::spawn(async move {
+                shutdown.cancelled().await;
+                // Срок ожидания задаёт вызывающий код, общий для сервера и фоновых задач
+                stop.graceful_shutdown(None);
+            });
+            axum_server::from_tcp_rustls(listener.into_std()?, tls)
+                .handle(handle)
+                .serve(app.into_make_service())
+                .await
+        }
+        None => axum::serve(listener, app).with_graceful_shutdown(shutdown.cancelled_owned()).await,
+    }
+}
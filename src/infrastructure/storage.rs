@@ -0,0 +1,114 @@
+use std::path::{Component, Path, PathBuf};
+use chrono::{Datelike, Utc};
+use tokio::fs;
+use uuid::Uuid;
+
+/// Локальное файловое хранилище работ (`file_storage_path` из конфигурации)
+#[derive(Debug, Clone)]
+pub struct FileStorage {
+    root: PathBuf,
+}
+
+impl FileStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Сохраняет файл под уникальным именем и возвращает путь относительно корня хранилища
+    pub async fn save(&self, original_name: &str, data: &[u8]) -> std::io::Result<String> {
+        let extension = Path::new(original_name)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| format!(".{}", e.to_lowercase()))
+            .unwrap_or_default();
+
+        let relative = format!("works/{}/{}{}", Utc::now().year(), Uuid::new_v4(), extension);
+        let full_path = self.root.join(&relative);
+
+        if let Some(parent) = full_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::write(&full_path, data).await?;
+
+        Ok(relative)
+    }
+
+    /// Абсолютный путь к файлу; `None`, если путь выходит за пределы хранилища
+    pub fn resolve(&self, relative: &str) -> Option<PathBuf> {
+        let relative = Path::new(relative);
+        let is_safe = relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+
+        is_safe.then(|| self.root.join(relative))
+    }
+
+    pub async fn read(&self, relative: &str) -> std::io::Result<Vec<u8>> {
+        match self.resolve(relative) {
+            Some(path) => fs::read(path).await,
+            None => Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "path outside storage")),
+        }
+    }
+
+    pub async fn delete(&self, relative: &str) -> std::io::Result<()> {
+        match self.resolve(relative) {
+            Some(path) => fs::remove_file(path).await,
+            None => Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "path outside storage")),
+        }
+    }
+
+    /// Можно ли писать в хранилище: создаёт и удаляет пробный файл в корне
+    pub async fn check_writable(&self) -> std::io::Result<()> {
+        fs::create_dir_all(&self.root).await?;
+        let probe = self.root.join(format!(".write-check-{}", Uuid::new_v4()));
+        fs::write(&probe, b"ok").await?;
+        fs::remove_file(&probe).await
+    }
+
+    /// Запись файла по заданному относительному пути (перезаписывает существующий)
+    pub async fn write(&self, relative: &str, data: &[u8]) -> std::io::Result<()> {
+        let Some(path) = self.resolve(relative) else {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "path outside storage"));
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::write(path, data).await
+    }
+
+    /// Все файлы хранилища: путь относительно корня (через `/`) и размер в байтах
+    pub async fn list_files(&self) -> std::io::Result<Vec<(String, u64)>> {
+        let mut files = Vec::new();
+        let mut pending = vec![self.root.clone()];
+
+        while let Some(dir) = pending.pop() {
+            let mut entries = match fs::read_dir(&dir).await {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            };
+            while let Some(entry) = entries.next_entry().await? {
+                let metadata = entry.metadata().await?;
+                let path = entry.path();
+                if metadata.is_dir() {
+                    pending.push(path);
+                } else if let Ok(relative) = path.strip_prefix(&self.root) {
+                    let relative = relative.components().filter_map(|c| c.as_os_str().to_str()).collect::<Vec<_>>();
+                    files.push((relative.join("/"), metadata.len()));
+                }
+            }
+        }
+
+        files.sort();
+        Ok(files)
+    }
+
+    /// Суммарный размер файлов в хранилище, байты; пока ничего не сохранено — 0
+    pub async fn total_size(&self) -> std::io::Result<u64> {
+        Ok(self.list_files().await?.iter().map(|(_, size)| size).sum())
+    }
+}
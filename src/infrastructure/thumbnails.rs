@@ -0,0 +1,25 @@
+//! Обложки работ: уменьшенная копия изображения в JPEG.
+
+use std::io::Cursor;
+
+use image::{ImageError, ImageOutputFormat};
+
+/// Наибольшая сторона обложки, пиксели
+pub const THUMBNAIL_SIZE: u32 = 320;
+
+/// Качество JPEG для обложек
+const JPEG_QUALITY: u8 = 85;
+
+/// Путь обложки работы в хранилище
+pub fn thumbnail_path(work_id: uuid::Uuid) -> String {
+    format!("thumbnails/{}.jpg", work_id)
+}
+
+/// Обложка из файла-изображения; для PDF и документов — ошибка формата
+pub fn generate(data: &[u8]) -> Result<Vec<u8>, ImageError> {
+    let image = image::load_from_memory(data)?.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE);
+
+    let mut jpeg = Cursor::new(Vec::new());
+    image.to_rgb8().write_to(&mut jpeg, ImageOutputFormat::Jpeg(JPEG_QUALITY))?;
+    Ok(jpeg.into_inner())
+}
@@ -1,65 +1,417 @@
-use reqwest::Client;
-use serde::Serialize;
-use tracing::{info, error, debug};
+use std::{sync::Arc, time::Duration};
+
+use rand::Rng;
+use reqwest::{multipart, Client, Method, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use tracing::{info, error, debug, warn};
 use serde_json;
+use tokio::sync::OnceCell;
+
+use crate::{config::Secret, infrastructure::metrics};
+
+use super::{
+    error::MaxApiError,
+    models::{
+        BotCommand, BotInfo, BotPatch, CallbackAnswer, Chat, ChatAction, ChatActionRequest, ChatMembersList,
+        SendMessageRequest, SimpleQueryResult, SubscribeRequest, UploadEndpoint, UploadType, WebhookSubscription,
+        WebhookSubscriptionList,
+    },
+    rate_limiter::RateLimiter,
+};
+
+/// Адрес MAX Bot API по умолчанию
+pub const DEFAULT_API_BASE_URL: &str = "https://platform-api.max.ru";
 
+/// Сколько раз повторять отправку, пока платформа обрабатывает загруженное вложение
+const ATTACHMENT_NOT_READY_RETRIES: u32 = 3;
+
+/// Начальная пауза экспоненциальной задержки между повторами
+const BACKOFF_BASE_MS: u64 = 500;
+/// Максимальная пауза между повторами
+const BACKOFF_MAX_MS: u64 = 30_000;
+
+/// Параметры HTTP-клиента МАКС
+#[derive(Debug, Clone)]
+pub struct MaxApiSettings {
+    /// Адрес API без завершающего `/` (в тестах — локальный мок)
+    pub api_base_url: String,
+    pub connect_timeout: Duration,
+    /// Тайм-аут всего запроса, включая чтение ответа
+    pub timeout: Duration,
+    /// Не больше стольких запросов в секунду (платформа допускает до 30)
+    pub requests_per_second: u32,
+    /// Повторы при 429, 5xx и сетевых сбоях
+    pub max_retries: u32,
+}
+
+impl Default for MaxApiSettings {
+    fn default() -> Self {
+        Self {
+            api_base_url: DEFAULT_API_BASE_URL.to_string(),
+            connect_timeout: Duration::from_secs(5),
+            timeout: Duration::from_secs(30),
+            requests_per_second: 25,
+            max_retries: 3,
+        }
+    }
+}
+
+/// Клиент MAX Bot API. Клонируется дёшево: копии разделяют пул соединений и лимит запросов.
 #[derive(Debug, Clone)]
 pub struct MaxApiClient {
-    auth_token: String,
+    auth_token: Secret<String>,
     api_base_url: String,
     http_client: Client,
-}
-
-#[derive(Debug, Serialize)]
-pub struct SendMessageRequest {
-    pub format: String,
-    pub text: String,
+    limiter: Arc<RateLimiter>,
+    max_retries: u32,
+    /// Профиль бота из `GET /me`: запрашивается один раз и не меняется, пока работает приложение
+    bot_info: Arc<OnceCell<BotInfo>>,
 }
 
 impl MaxApiClient {
     pub fn new(auth_token: String) -> Self {
+        Self::with_settings(auth_token, MaxApiSettings::default())
+    }
+
+    pub fn with_settings(auth_token: String, settings: MaxApiSettings) -> Self {
+        let http_client = Client::builder()
+            .connect_timeout(settings.connect_timeout)
+            .timeout(settings.timeout)
+            .build()
+            .expect("Не удалось создать HTTP-клиент МАКС");
+
         Self {
-            auth_token,
-            api_base_url: "https://platform-api.max.ru".to_string(),
-            http_client: Client::new(),
+            auth_token: auth_token.into(),
+            api_base_url: settings.api_base_url.trim_end_matches('/').to_string(),
+            http_client,
+            limiter: Arc::new(RateLimiter::new(settings.requests_per_second)),
+            max_retries: settings.max_retries,
+            bot_info: Arc::new(OnceCell::new()),
         }
     }
 
-    pub async fn send_message(&self, chat_id: i64, user_id: i64, text: &str) -> Result<(), Box<dyn std::error::Error>> {
-        // Формируем запрос БЕЗ recipient в теле
-        let request = SendMessageRequest {
-            format: "html".to_string(),
-            text: text.to_string(),
-        };
+    /// Выполнение запроса с ограничением частоты и повторами при временных ошибках.
+    /// `build` вызывается на каждую попытку, т.к. запрос нельзя отправить дважды.
+    /// `endpoint` — метка в метриках; `None` — путь метода API без идентификаторов.
+    /// Для адресов файлов и загрузки метка задаётся явно, иначе число меток не ограничено.
+    async fn execute<F>(&self, endpoint: Option<&'static str>, build: F) -> Result<Response, MaxApiError>
+    where
+        F: Fn() -> RequestBuilder,
+    {
+        let mut attempt = 0;
+        loop {
+            self.limiter.acquire().await;
+
+            let request = build().build()?;
+            let endpoint = endpoint
+                .map(str::to_string)
+                .unwrap_or_else(|| metrics::max_api_endpoint(request.url().path()));
+
+            let error = match self.http_client.execute(request).await {
+                Ok(response) if response.status().is_success() => {
+                    metrics::record_max_api_call(&endpoint, "success");
+                    return Ok(response);
+                }
+                Ok(response) => MaxApiError::from_response(response).await,
+                Err(e) => MaxApiError::from(e),
+            };
+            metrics::record_max_api_call(&endpoint, error.kind());
+
+            if !error.is_retryable() || attempt >= self.max_retries {
+                return Err(error);
+            }
+
+            let delay = match error {
+                MaxApiError::RateLimited { retry_after: Some(retry_after) } => retry_after,
+                _ => Self::backoff(attempt),
+            };
+            attempt += 1;
+            warn!("⏳ {}, повтор {} из {} через {:?}", error, attempt, self.max_retries, delay);
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Запрос к методу API с токеном бота
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.http_client
+            .request(method, format!("{}{}", self.api_base_url, path))
+            .header("Authorization", self.auth_token.expose())
+    }
 
+    /// Запрос к методу API и разбор JSON-ответа
+    async fn call<T: DeserializeOwned>(&self, build: impl Fn() -> RequestBuilder) -> Result<T, MaxApiError> {
+        let response = self.execute(None, build).await?;
+        let body = response.bytes().await?;
+        serde_json::from_slice(&body).map_err(|e| MaxApiError::InvalidResponse(e.to_string()))
+    }
+
+    /// Запрос к методу, который отвечает `{"success": ...}`
+    async fn call_simple(&self, build: impl Fn() -> RequestBuilder) -> Result<(), MaxApiError> {
+        let result: SimpleQueryResult = self.call(build).await?;
+        if result.success {
+            Ok(())
+        } else {
+            Err(MaxApiError::BadRequest {
+                status: reqwest::StatusCode::OK,
+                code: None,
+                message: result.message.unwrap_or_else(|| "success: false".to_string()),
+            })
+        }
+    }
+
+    /// Экспоненциальная задержка со случайной добавкой, чтобы повторы не шли волной
+    fn backoff(attempt: u32) -> Duration {
+        let exponential = BACKOFF_BASE_MS.saturating_mul(1 << attempt.min(16)).min(BACKOFF_MAX_MS);
+        let jitter = rand::thread_rng().gen_range(0..=exponential / 4);
+        Duration::from_millis(exponential + jitter)
+    }
+
+    /// Отправка простого текстового сообщения в формате HTML
+    pub async fn send_message(&self, chat_id: i64, user_id: Option<i64>, text: &str) -> Result<(), MaxApiError> {
+        self.send(chat_id, user_id, &SendMessageRequest::html(text)).await
+    }
+
+    /// Отправка сообщения с вложениями (например, inline-клавиатурой).
+    /// Слишком длинный текст уходит несколькими сообщениями, вложения — с последним.
+    pub async fn send(&self, chat_id: i64, user_id: Option<i64>, request: &SendMessageRequest) -> Result<(), MaxApiError> {
+        for part in request.split() {
+            self.send_part(chat_id, user_id, &part).await?;
+        }
+        Ok(())
+    }
+
+    async fn send_part(&self, chat_id: i64, user_id: Option<i64>, request: &SendMessageRequest) -> Result<(), MaxApiError> {
         // Логируем отправляемый JSON
-        let request_json = serde_json::to_string_pretty(&request)?;
-        debug!("📤 Отправляемый запрос в МАКС:\n{}", request_json);
+        if let Ok(request_json) = serde_json::to_string_pretty(request) {
+            debug!("📤 Отправляемый запрос в МАКС:\n{}", request_json);
+        }
 
         // chat_id и user_id передаются в URL как query-параметры!
-        let url = format!(
-            "{}/messages?chat_id={}&user_id={}",
-            self.api_base_url,
-            chat_id,
-            user_id
-        );
-
-        let response = self.http_client
-            .post(&url)
-            .header("Authorization", &self.auth_token)
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
-            .await?;
-
-        let status = response.status();
-        if status.is_success() {
-            info!("✅ Сообщение отправлено chat_id={}", chat_id);
+        let mut query = vec![("chat_id", chat_id)];
+        if let Some(user_id) = user_id {
+            query.push(("user_id", user_id));
+        }
+
+        let mut attempt = 0;
+        loop {
+            let result = self
+                .execute(None, || self.request(Method::POST, "/messages").query(&query).json(request))
+                .await;
+
+            match result {
+                Ok(_) => {
+                    info!("✅ Сообщение отправлено chat_id={}", chat_id);
+                    return Ok(());
+                }
+                // Только что загруженный файл ещё обрабатывается платформой — ждём и повторяем
+                Err(e) if e.code() == Some("attachment.not.ready") && attempt < ATTACHMENT_NOT_READY_RETRIES => {
+                    attempt += 1;
+                    warn!("⏳ Вложение ещё не готово, повтор {} из {}", attempt, ATTACHMENT_NOT_READY_RETRIES);
+                    tokio::time::sleep(Duration::from_secs(attempt as u64)).await;
+                }
+                Err(e) => {
+                    error!("❌ Ошибка отправки сообщения: {}", e);
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    /// Ответ на нажатие callback-кнопки
+    pub async fn answer_callback(&self, callback_id: &str, answer: &CallbackAnswer) -> Result<(), MaxApiError> {
+        // Ответ на callback — одно сообщение, поэтому длинный текст обрезается
+        let answer = CallbackAnswer {
+            message: answer.message.as_ref().map(SendMessageRequest::truncated),
+            ..answer.clone()
+        };
+        if let Ok(request_json) = serde_json::to_string_pretty(&answer) {
+            debug!("📤 Ответ на callback {}:\n{}", callback_id, request_json);
+        }
+
+        self.call_simple(|| {
+            self.request(Method::POST, "/answers")
+                .query(&[("callback_id", callback_id)])
+                .json(&answer)
+        })
+        .await
+        .inspect_err(|e| error!("❌ Ошибка ответа на callback: {}", e))?;
+
+        debug!("✅ Ответ на callback {} отправлен", callback_id);
+        Ok(())
+    }
+
+    /// Скачивание файла из вложения по временной ссылке платформы, не больше `max_size` байт.
+    /// Тело читается по частям и скачивание прерывается, как только лимит превышен.
+    pub async fn download(&self, url: &str, max_size: u64) -> Result<Vec<u8>, MaxApiError> {
+        debug!("📥 Скачивание вложения: {}", url);
+
+        // Ссылка уже подписана платформой — токен бота сюда не передаём
+        let mut response = self
+            .execute(Some("download"), || self.http_client.get(url))
+            .await
+            .inspect_err(|e| error!("❌ Ошибка скачивания вложения: {}", e))?;
+
+        let too_large = MaxApiError::TooLarge { limit: max_size };
+        if response.content_length().is_some_and(|length| length > max_size) {
+            return Err(too_large);
+        }
+
+        let mut data = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if (data.len() + chunk.len()) as u64 > max_size {
+                return Err(too_large);
+            }
+            data.extend_from_slice(&chunk);
+        }
+        Ok(data)
+    }
+
+    /// Адрес для загрузки файла указанного типа
+    pub async fn upload_endpoint(&self, upload_type: UploadType) -> Result<UploadEndpoint, MaxApiError> {
+        self.call(|| self.request(Method::POST, "/uploads").query(&[("type", upload_type.as_str())]))
+            .await
+            .inspect_err(|e| error!("❌ Ошибка получения адреса загрузки: {}", e))
+    }
+
+    /// Загрузка файла на платформу, возвращает токен для вложения
+    pub async fn upload(&self, upload_type: UploadType, file_name: &str, data: Vec<u8>) -> Result<String, MaxApiError> {
+        let endpoint = self.upload_endpoint(upload_type).await?;
+
+        debug!("📤 Загрузка {} ({} байт) на {}", file_name, data.len(), endpoint.url);
+
+        let response = self
+            .execute(Some("upload"), || {
+                let form = multipart::Form::new()
+                    .part("data", multipart::Part::bytes(data.clone()).file_name(file_name.to_string()));
+                self.http_client.post(&endpoint.url).multipart(form)
+            })
+            .await
+            .inspect_err(|e| error!("❌ Ошибка загрузки файла: {}", e))?;
+
+        let body: serde_json::Value = response.json().await?;
+
+        // Файлы возвращают {"token": ...}, изображения — {"photos": {"<id>": {"token": ...}}}
+        let token = endpoint
+            .token
+            .or_else(|| body.get("token").and_then(|t| t.as_str()).map(str::to_string))
+            .or_else(|| {
+                body.get("photos")
+                    .and_then(|p| p.as_object())
+                    .and_then(|p| p.values().next())
+                    .and_then(|p| p.get("token"))
+                    .and_then(|t| t.as_str())
+                    .map(str::to_string)
+            });
+
+        match token {
+            Some(token) => {
+                info!("✅ Файл {} загружен в МАКС", file_name);
+                Ok(token)
+            }
+            None => Err(MaxApiError::InvalidResponse(format!("no token in upload response {}", body))),
+        }
+    }
+
+    /// Изменение отправленного сообщения; вложения заменяются целиком
+    pub async fn edit_message(&self, message_id: &str, request: &SendMessageRequest) -> Result<(), MaxApiError> {
+        let request = request.truncated();
+        self.call_simple(|| {
+            self.request(Method::PUT, "/messages")
+                .query(&[("message_id", message_id)])
+                .json(&request)
+        })
+        .await
+    }
+
+    pub async fn delete_message(&self, message_id: &str) -> Result<(), MaxApiError> {
+        self.call_simple(|| self.request(Method::DELETE, "/messages").query(&[("message_id", message_id)]))
+            .await
+    }
+
+    /// Показ действия бота в чате, например «печатает…»
+    pub async fn send_action(&self, chat_id: i64, action: ChatAction) -> Result<(), MaxApiError> {
+        let path = format!("/chats/{}/actions", chat_id);
+        self.call_simple(|| self.request(Method::POST, &path).json(&ChatActionRequest { action }))
+            .await
+    }
+
+    /// Информация о текущем боте
+    pub async fn get_me(&self) -> Result<BotInfo, MaxApiError> {
+        self.call(|| self.request(Method::GET, "/me")).await
+    }
+
+    /// Проверка доступности платформы: один запрос `GET /me` без ограничения частоты и повторов,
+    /// чтобы проверка готовности не зависала на медленной платформе и не расходовала лимит бота
+    pub async fn ping(&self) -> Result<(), MaxApiError> {
+        let response = self.request(Method::GET, "/me").send().await?;
+        if response.status().is_success() {
             Ok(())
         } else {
-            let error_text = response.text().await?;
-            error!("❌ Ошибка отправки сообщения: {} - {}", status, error_text);
-            Err(format!("MAX API error: {} - {}", status, error_text).into())
+            Err(MaxApiError::from_response(response).await)
         }
     }
-}
\ No newline at end of file
+
+    /// Профиль бота (ID и имя для упоминаний); запрашивается при первом обращении
+    pub async fn bot_info(&self) -> Result<&BotInfo, MaxApiError> {
+        self.bot_info.get_or_try_init(|| self.get_me()).await
+    }
+
+    /// Изменение имени, описания или команд бота
+    pub async fn edit_me(&self, patch: &BotPatch) -> Result<BotInfo, MaxApiError> {
+        self.call(|| self.request(Method::PATCH, "/me").json(patch)).await
+    }
+
+    /// Замена списка команд в меню бота
+    pub async fn set_commands(&self, commands: Vec<BotCommand>) -> Result<BotInfo, MaxApiError> {
+        self.edit_me(&BotPatch { commands: Some(commands), ..BotPatch::default() }).await
+    }
+
+    pub async fn get_chat(&self, chat_id: i64) -> Result<Chat, MaxApiError> {
+        let path = format!("/chats/{}", chat_id);
+        self.call(|| self.request(Method::GET, &path)).await
+    }
+
+    /// Страница участников чата; `marker` — курсор из предыдущего ответа
+    pub async fn get_chat_members(
+        &self,
+        chat_id: i64,
+        marker: Option<i64>,
+        count: u32,
+    ) -> Result<ChatMembersList, MaxApiError> {
+        let path = format!("/chats/{}/members", chat_id);
+        self.call(|| {
+            let request = self.request(Method::GET, &path).query(&[("count", count)]);
+            match marker {
+                Some(marker) => request.query(&[("marker", marker)]),
+                None => request,
+            }
+        })
+        .await
+    }
+
+    /// Участники чата с указанными ID
+    pub async fn get_chat_members_by_id(&self, chat_id: i64, user_ids: &[i64]) -> Result<ChatMembersList, MaxApiError> {
+        let path = format!("/chats/{}/members", chat_id);
+        let user_ids = user_ids.iter().map(i64::to_string).collect::<Vec<_>>().join(",");
+        self.call(|| self.request(Method::GET, &path).query(&[("user_ids", &user_ids)]))
+            .await
+    }
+
+    /// Текущие подписки бота на вебхук
+    pub async fn get_subscriptions(&self) -> Result<Vec<WebhookSubscription>, MaxApiError> {
+        let list: WebhookSubscriptionList = self.call(|| self.request(Method::GET, "/subscriptions")).await?;
+        Ok(list.subscriptions)
+    }
+
+    /// Подписка на обновления через вебхук
+    pub async fn subscribe(&self, request: &SubscribeRequest) -> Result<(), MaxApiError> {
+        self.call_simple(|| self.request(Method::POST, "/subscriptions").json(request))
+            .await
+    }
+
+    /// Отписка вебхука по адресу
+    pub async fn unsubscribe(&self, url: &str) -> Result<(), MaxApiError> {
+        self.call_simple(|| self.request(Method::DELETE, "/subscriptions").query(&[("url", url)]))
+            .await
+    }
+}
@@ -0,0 +1,127 @@
+use std::time::Duration;
+
+use reqwest::{header::RETRY_AFTER, Response, StatusCode};
+use serde::Deserialize;
+
+/// Коды ошибок платформы, означающие, что писать в чат больше нельзя
+const BLOCKED_CODES: &[&str] = &["chat.denied", "chat.not.found", "dialog.suspended"];
+
+/// Самая долгая пауза из `Retry-After`, которую мы готовы выждать
+const MAX_RETRY_AFTER_SECS: u64 = 60;
+
+/// Ошибка обращения к MAX Bot API
+#[derive(Debug, thiserror::Error)]
+pub enum MaxApiError {
+    /// Неверный или отозванный токен бота
+    #[error("MAX API authentication failed: {0}")]
+    Unauthorized(String),
+
+    /// Превышен лимит запросов; `retry_after` — пауза, которую просит платформа
+    #[error("MAX API rate limit exceeded")]
+    RateLimited { retry_after: Option<Duration> },
+
+    /// Бот заблокирован пользователем, удалён из чата или чат больше не существует
+    #[error("MAX chat is unavailable for the bot: {0}")]
+    BlockedByUser(String),
+
+    /// Платформа отклонила запрос
+    #[error("MAX API rejected request ({status}): {message}")]
+    BadRequest {
+        status: StatusCode,
+        code: Option<String>,
+        message: String,
+    },
+
+    /// Сбой на стороне платформы (5xx)
+    #[error("MAX API server error ({status}): {message}")]
+    Server { status: StatusCode, message: String },
+
+    /// Сетевая ошибка или тайм-аут
+    #[error("MAX API request failed: {0}")]
+    Transport(#[from] reqwest::Error),
+
+    /// Ответ не удалось разобрать
+    #[error("unexpected MAX API response: {0}")]
+    InvalidResponse(String),
+
+    /// Скачиваемый файл больше допустимого размера
+    #[error("MAX attachment is larger than {limit} bytes")]
+    TooLarge { limit: u64 },
+}
+
+/// Тело ответа платформы с ошибкой: `{"code": "...", "message": "..."}`
+#[derive(Debug, Default, Deserialize)]
+struct ErrorBody {
+    code: Option<String>,
+    message: Option<String>,
+}
+
+impl MaxApiError {
+    /// Ошибка из неуспешного ответа платформы
+    pub async fn from_response(response: Response) -> Self {
+        let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .map(|secs| Duration::from_secs(secs.min(MAX_RETRY_AFTER_SECS)));
+        let text = response.text().await.unwrap_or_default();
+
+        Self::from_parts(status, &text, retry_after)
+    }
+
+    pub fn from_parts(status: StatusCode, body: &str, retry_after: Option<Duration>) -> Self {
+        let ErrorBody { code, message } = serde_json::from_str(body).unwrap_or_default();
+        let message = message.or_else(|| code.clone()).unwrap_or_else(|| body.to_string());
+
+        if code.as_deref().is_some_and(|c| BLOCKED_CODES.contains(&c)) {
+            return MaxApiError::BlockedByUser(message);
+        }
+
+        match status {
+            StatusCode::UNAUTHORIZED => MaxApiError::Unauthorized(message),
+            StatusCode::FORBIDDEN => MaxApiError::BlockedByUser(message),
+            StatusCode::TOO_MANY_REQUESTS => MaxApiError::RateLimited { retry_after },
+            s if s.is_server_error() => MaxApiError::Server { status, message },
+            _ => MaxApiError::BadRequest { status, code, message },
+        }
+    }
+
+    /// Код ошибки платформы (например, `attachment.not.ready`)
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            MaxApiError::BadRequest { code, .. } => code.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Платформа не принимает токен вложения: он истёк или отозван.
+    /// `attachment.not.ready` сюда не относится — такой файл просто ещё обрабатывается
+    pub fn is_invalid_attachment(&self) -> bool {
+        self.code().is_some_and(|c| c.starts_with("attachment.") && c != "attachment.not.ready")
+    }
+
+    /// Вид ошибки для метрик
+    pub fn kind(&self) -> &'static str {
+        match self {
+            MaxApiError::Unauthorized(_) => "unauthorized",
+            MaxApiError::RateLimited { .. } => "rate_limited",
+            MaxApiError::BlockedByUser(_) => "blocked",
+            MaxApiError::BadRequest { .. } => "bad_request",
+            MaxApiError::Server { .. } => "server_error",
+            MaxApiError::Transport(_) => "transport",
+            MaxApiError::InvalidResponse(_) => "invalid_response",
+            MaxApiError::TooLarge { .. } => "too_large",
+        }
+    }
+
+    /// Имеет ли смысл повторить запрос
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            MaxApiError::RateLimited { .. } | MaxApiError::Server { .. } => true,
+            MaxApiError::Transport(e) => e.is_timeout() || e.is_connect(),
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,270 @@
+//! HTML-разметка сообщений МАКС: экранирование пользовательского текста,
+//! простые помощники для разметки и деление длинных сообщений по лимиту платформы.
+
+/// Максимальная длина текста одного сообщения в МАКС, символы
+pub const MAX_MESSAGE_LENGTH: usize = 4000;
+
+/// Экранирует текст для вставки в сообщение с `format: "html"`
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Жирный текст; содержимое экранируется
+pub fn bold(text: &str) -> String {
+    format!("<b>{}</b>", escape(text))
+}
+
+/// Курсив; содержимое экранируется
+pub fn italic(text: &str) -> String {
+    format!("<i>{}</i>", escape(text))
+}
+
+/// Ссылка; текст и адрес экранируются
+pub fn link(text: &str, url: &str) -> String {
+    format!("<a href=\"{}\">{}</a>", escape(url), escape(text))
+}
+
+/// Маркированный список: по пункту на строку, пункты экранируются
+pub fn list<I, S>(items: I) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    items
+        .into_iter()
+        .map(|item| format!("• {}", escape(item.as_ref())))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Построитель HTML-сообщения: пользовательский текст экранируется,
+/// готовая разметка добавляется через [`MessageBuilder::raw`]
+#[derive(Debug, Clone, Default)]
+pub struct MessageBuilder {
+    html: String,
+}
+
+impl MessageBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Обычный текст (экранируется)
+    pub fn text(mut self, text: &str) -> Self {
+        self.html.push_str(&escape(text));
+        self
+    }
+
+    /// Готовая разметка без экранирования — только для текста, который пишет сам бот
+    pub fn raw(mut self, html: &str) -> Self {
+        self.html.push_str(html);
+        self
+    }
+
+    pub fn bold(mut self, text: &str) -> Self {
+        self.html.push_str(&bold(text));
+        self
+    }
+
+    pub fn italic(mut self, text: &str) -> Self {
+        self.html.push_str(&italic(text));
+        self
+    }
+
+    pub fn link(mut self, text: &str, url: &str) -> Self {
+        self.html.push_str(&link(text, url));
+        self
+    }
+
+    pub fn list<I, S>(mut self, items: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.html.push_str(&list(items));
+        self
+    }
+
+    /// Перевод строки
+    pub fn line(mut self) -> Self {
+        self.html.push('\n');
+        self
+    }
+
+    /// `<b>Подпись:</b> значение` с переводом строки; подпись — разметка бота, значение экранируется
+    pub fn field(self, label: &str, value: &str) -> Self {
+        self.raw(&format!("<b>{}:</b> ", label)).text(value).line()
+    }
+
+    pub fn build(self) -> String {
+        self.html
+    }
+}
+
+/// Часть HTML-текста, которую нельзя разрывать
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token<'a> {
+    Open { name: &'a str, tag: &'a str },
+    Close(&'a str),
+    /// Самозакрывающийся тег или сущность (`&amp;`)
+    Atom(&'a str),
+    Char(char),
+}
+
+impl Token<'_> {
+    fn len(&self) -> usize {
+        match self {
+            Token::Open { tag, .. } => tag.chars().count(),
+            Token::Close(tag) | Token::Atom(tag) => tag.chars().count(),
+            Token::Char(_) => 1,
+        }
+    }
+}
+
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+
+    while let Some(c) = rest.chars().next() {
+        let end = match c {
+            '<' => rest.find('>').map(|i| i + 1),
+            '&' => rest.find(';').filter(|&i| i <= 10 && !rest[1..i].contains(char::is_whitespace)).map(|i| i + 1),
+            _ => None,
+        };
+
+        let Some(end) = end else {
+            tokens.push(Token::Char(c));
+            rest = &rest[c.len_utf8()..];
+            continue;
+        };
+
+        let piece = &rest[..end];
+        tokens.push(if c == '&' || piece.ends_with("/>") {
+            Token::Atom(piece)
+        } else if let Some(name) = piece.strip_prefix("</") {
+            Token::Close(name.trim_end_matches('>').trim())
+        } else {
+            let name = piece[1..piece.len() - 1].split_whitespace().next().unwrap_or_default();
+            Token::Open { name, tag: piece }
+        });
+        rest = &rest[end..];
+    }
+
+    tokens
+}
+
+/// Открытые теги после применения `tokens` к стеку `open`
+fn apply<'a>(mut open: Vec<Token<'a>>, tokens: &[Token<'a>]) -> Vec<Token<'a>> {
+    for token in tokens {
+        match token {
+            Token::Open { .. } => open.push(token.clone()),
+            Token::Close(name) => {
+                if let Some(i) = open.iter().rposition(|t| matches!(t, Token::Open { name: n, .. } if n == name)) {
+                    open.truncate(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    open
+}
+
+fn closing_len(open: &[Token<'_>]) -> usize {
+    open.iter()
+        .map(|t| match t {
+            Token::Open { name, .. } => name.chars().count() + 3,
+            _ => 0,
+        })
+        .sum()
+}
+
+fn render(open: &[Token<'_>], tokens: &[Token<'_>], close: &[Token<'_>]) -> String {
+    let mut html = String::new();
+    for token in open.iter().chain(tokens) {
+        match token {
+            Token::Open { tag, .. } => html.push_str(tag),
+            Token::Close(name) => html.push_str(&format!("</{}>", name)),
+            Token::Atom(piece) => html.push_str(piece),
+            Token::Char(c) => html.push(*c),
+        }
+    }
+    for token in close.iter().rev() {
+        if let Token::Open { name, .. } = token {
+            html.push_str(&format!("</{}>", name));
+        }
+    }
+    html
+}
+
+/// Делит HTML-текст на части не длиннее `limit` символов. Делит по строкам, затем по пробелам;
+/// теги и сущности не разрываются: открытые теги закрываются в конце части и открываются заново в следующей.
+pub fn split(html: &str, limit: usize) -> Vec<String> {
+    let tokens = tokenize(html);
+    let mut parts = Vec::new();
+    let mut reopen: Vec<Token<'_>> = Vec::new();
+    let mut start = 0;
+
+    while start < tokens.len() {
+        let prefix_len: usize = reopen.iter().map(Token::len).sum();
+        let mut len = prefix_len;
+        let mut open = reopen.clone();
+        let mut end = start;
+        let (mut line_break, mut space_break) = (None, None);
+
+        while end < tokens.len() {
+            let next = apply(open.clone(), &tokens[end..=end]);
+            let next_len = len + tokens[end].len();
+            if next_len + closing_len(&next) > limit && end > start {
+                break;
+            }
+            match tokens[end] {
+                Token::Char('\n') => line_break = Some(end + 1),
+                Token::Char(' ') => space_break = Some(end + 1),
+                _ => {}
+            }
+            open = next;
+            len = next_len;
+            end += 1;
+        }
+
+        if end < tokens.len() {
+            end = line_break.or(space_break).unwrap_or(end);
+        }
+
+        let close = apply(reopen.clone(), &tokens[start..end]);
+        let part = render(&reopen, &tokens[start..end], &close);
+        if !part.trim().is_empty() {
+            parts.push(part.trim_end().to_string());
+        }
+
+        reopen = close;
+        start = end;
+        while matches!(tokens.get(start), Some(Token::Char('\n' | ' '))) {
+            start += 1;
+        }
+    }
+
+    parts
+}
+
+/// Обрезает HTML-текст до `limit` символов с многоточием, не разрывая теги
+pub fn truncate(html: &str, limit: usize) -> String {
+    if html.chars().count() <= limit {
+        return html.to_string();
+    }
+
+    let mut parts = split(html, limit.saturating_sub(1));
+    let mut first = if parts.is_empty() { String::new() } else { parts.swap_remove(0) };
+    first.push('…');
+    first
+}
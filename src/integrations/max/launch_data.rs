@@ -0,0 +1,135 @@
+//! Проверка данных запуска мини-приложения МАКС (`WebApp.initData`).
+//!
+//! Платформа передаёт мини-приложению строку в формате query string, подписанную токеном бота:
+//! `secret = HMAC-SHA256("WebAppData", bot_token)`,
+//! `hash = hex(HMAC-SHA256(secret, data_check_string))`, где `data_check_string` —
+//! все поля, кроме `hash`, в виде `key=value`, отсортированные по ключу и разделённые `\n`.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Допустимое расхождение часов, если `auth_date` оказался в будущем
+const CLOCK_SKEW_SECS: i64 = 60;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum LaunchDataError {
+    #[error("launch data is malformed: {0}")]
+    Malformed(&'static str),
+    #[error("launch data has no hash")]
+    MissingHash,
+    #[error("launch data signature is invalid")]
+    InvalidSignature,
+    #[error("launch data is expired")]
+    Expired,
+    #[error("launch data has no user")]
+    MissingUser,
+}
+
+/// Пользователь МАКС, открывший мини-приложение
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchUser {
+    pub id: i64,
+    #[serde(default)]
+    pub first_name: String,
+    #[serde(default)]
+    pub last_name: Option<String>,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub language_code: Option<String>,
+    #[serde(default)]
+    pub photo_url: Option<String>,
+}
+
+/// Проверенные данные запуска
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchData {
+    pub auth_date: DateTime<Utc>,
+    pub query_id: Option<String>,
+    pub user: LaunchUser,
+    pub start_param: Option<String>,
+}
+
+fn mac(data_check_string: &str, bot_token: &str) -> HmacSha256 {
+    let mut secret = HmacSha256::new_from_slice(b"WebAppData").expect("HMAC accepts any key length");
+    secret.update(bot_token.as_bytes());
+    let secret = secret.finalize().into_bytes();
+
+    let mut mac = HmacSha256::new_from_slice(&secret).expect("HMAC accepts any key length");
+    mac.update(data_check_string.as_bytes());
+    mac
+}
+
+/// Подпись строки проверки данных (hex)
+pub fn sign(data_check_string: &str, bot_token: &str) -> String {
+    hex::encode(mac(data_check_string, bot_token).finalize().into_bytes())
+}
+
+/// Проверка подписи и свежести данных запуска.
+///
+/// `max_age` — сколько времени с `auth_date` данные считаются действительными, `now` — текущее время.
+pub fn validate(
+    init_data: &str,
+    bot_token: &str,
+    max_age: Duration,
+    now: DateTime<Utc>,
+) -> Result<LaunchData, LaunchDataError> {
+    let init_data = init_data.trim().trim_start_matches('?');
+
+    let mut hash = None;
+    let mut pairs = Vec::new();
+    for (key, value) in form_urlencoded::parse(init_data.as_bytes()) {
+        if key == "hash" {
+            if hash.replace(value.into_owned()).is_some() {
+                return Err(LaunchDataError::Malformed("duplicate hash"));
+            }
+        } else {
+            if pairs.iter().any(|(k, _): &(String, String)| *k == key) {
+                return Err(LaunchDataError::Malformed("duplicate key"));
+            }
+            pairs.push((key.into_owned(), value.into_owned()));
+        }
+    }
+
+    let hash = hash.ok_or(LaunchDataError::MissingHash)?;
+    let expected = hex::decode(hash.to_lowercase()).map_err(|_| LaunchDataError::InvalidSignature)?;
+
+    pairs.sort_by(|a, b| a.0.cmp(&b.0));
+    let data_check_string = pairs
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    // Сравнение за постоянное время
+    mac(&data_check_string, bot_token)
+        .verify_slice(&expected)
+        .map_err(|_| LaunchDataError::InvalidSignature)?;
+
+    let field = |name: &str| pairs.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone());
+
+    let auth_date = field("auth_date")
+        .ok_or(LaunchDataError::Malformed("missing auth_date"))?
+        .parse::<i64>()
+        .ok()
+        .and_then(|ts| Utc.timestamp_opt(ts, 0).single())
+        .ok_or(LaunchDataError::Malformed("invalid auth_date"))?;
+
+    if auth_date > now + Duration::seconds(CLOCK_SKEW_SECS) || now - auth_date > max_age {
+        return Err(LaunchDataError::Expired);
+    }
+
+    let user = field("user").ok_or(LaunchDataError::MissingUser)?;
+    let user: LaunchUser = serde_json::from_str(&user).map_err(|_| LaunchDataError::Malformed("invalid user"))?;
+
+    Ok(LaunchData {
+        auth_date,
+        query_id: field("query_id"),
+        user,
+        start_param: field("start_param"),
+    })
+}
@@ -1,4 +1,15 @@
 pub mod api_client;
+pub mod error;
+pub mod formatting;
+pub mod launch_data;
+pub mod models;
+pub mod rate_limiter;
 
 
-pub use api_client::MaxApiClient;
+pub use api_client::{MaxApiClient, MaxApiSettings, DEFAULT_API_BASE_URL};
+pub use error::MaxApiError;
+pub use formatting::{MessageBuilder, MAX_MESSAGE_LENGTH};
+pub use models::{
+    Attachment, AttachmentRequest, Button, CallbackAnswer, FileAttachment, InlineKeyboard, SendMessageRequest,
+    Update, UploadType,
+};
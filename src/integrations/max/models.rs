@@ -0,0 +1,545 @@
+use serde::{Deserialize, Serialize};
+
+use super::formatting::{self, MAX_MESSAGE_LENGTH};
+
+// ---------------------------------------------------------------------------
+// Входящие обновления (вебхук)
+// ---------------------------------------------------------------------------
+
+/// Обновление, которое МАКС присылает на вебхук
+#[derive(Debug, Deserialize)]
+#[serde(tag = "update_type", rename_all = "snake_case")]
+pub enum Update {
+    MessageCreated {
+        timestamp: u64,
+        message: Message,
+        #[serde(default)]
+        user_locale: Option<String>,
+    },
+    MessageCallback {
+        timestamp: u64,
+        callback: Callback,
+        #[serde(default)]
+        message: Option<Message>,
+        #[serde(default)]
+        user_locale: Option<String>,
+    },
+    BotStarted {
+        timestamp: u64,
+        chat_id: i64,
+        user: Sender,
+        #[serde(default)]
+        payload: Option<String>,
+        #[serde(default)]
+        user_locale: Option<String>,
+    },
+    /// Типы обновлений, которые бот пока не обрабатывает
+    #[serde(other)]
+    Unsupported,
+}
+
+impl Update {
+    /// Тип обновления, как его называет платформа
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Update::MessageCreated { .. } => "message_created",
+            Update::MessageCallback { .. } => "message_callback",
+            Update::BotStarted { .. } => "bot_started",
+            Update::Unsupported => "unsupported",
+        }
+    }
+
+    /// Язык пользователя, приславшего обновление
+    pub fn user_locale(&self) -> Option<&str> {
+        match self {
+            Update::MessageCreated { user_locale, .. }
+            | Update::MessageCallback { user_locale, .. }
+            | Update::BotStarted { user_locale, .. } => user_locale.as_deref(),
+            Update::Unsupported => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Message {
+    pub recipient: Recipient,
+    pub timestamp: u64,
+    pub body: MessageBody,
+    pub sender: Sender,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Recipient {
+    #[serde(default)]
+    pub chat_id: i64,
+    #[serde(default)]
+    pub chat_type: String,
+    #[serde(default)]
+    pub user_id: Option<i64>,
+}
+
+impl Recipient {
+    /// Групповой чат или канал, а не личный диалог с ботом
+    pub fn is_group(&self) -> bool {
+        matches!(self.chat_type.as_str(), "chat" | "channel")
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MessageBody {
+    pub mid: String,
+    pub seq: u64,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub attachments: Option<Vec<Attachment>>,
+    /// Разметка текста: выделения, ссылки, упоминания
+    #[serde(default)]
+    pub markup: Option<Vec<Markup>>,
+}
+
+impl MessageBody {
+    /// Первый прикреплённый к сообщению файл (документ)
+    pub fn file(&self) -> Option<&FileAttachment> {
+        self.attachments.iter().flatten().find_map(|a| match a {
+            Attachment::File(file) => Some(file),
+            _ => None,
+        })
+    }
+}
+
+/// Элемент разметки входящего сообщения. `from` и `length` — позиция в тексте в символах.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Markup {
+    UserMention {
+        from: usize,
+        length: usize,
+        #[serde(default)]
+        user_id: Option<i64>,
+        /// `@username`, если у пользователя есть публичное имя
+        #[serde(default)]
+        user_link: Option<String>,
+    },
+    /// Прочая разметка (жирный, курсив, ссылки) для бота значения не имеет
+    #[serde(other)]
+    Other,
+}
+
+/// Вложение входящего сообщения
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Attachment {
+    File(FileAttachment),
+    Image { payload: ImagePayload },
+    /// Видео, аудио, стикеры и прочие вложения, которые бот не обрабатывает
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FileAttachment {
+    pub payload: FilePayload,
+    pub filename: String,
+    #[serde(default)]
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FilePayload {
+    /// Временная ссылка на скачивание файла
+    pub url: String,
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImagePayload {
+    #[serde(default)]
+    pub photo_id: Option<i64>,
+    #[serde(default)]
+    pub token: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Sender {
+    pub user_id: i64,
+    #[serde(default)]
+    pub first_name: String,
+    #[serde(default)]
+    pub last_name: Option<String>,
+    #[serde(default)]
+    pub is_bot: bool,
+    #[serde(default)]
+    pub last_activity_time: u64,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// Нажатие на callback-кнопку
+#[derive(Debug, Deserialize)]
+pub struct Callback {
+    pub timestamp: u64,
+    pub callback_id: String,
+    #[serde(default)]
+    pub payload: Option<String>,
+    pub user: Sender,
+}
+
+// ---------------------------------------------------------------------------
+// Исходящие сообщения
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SendMessageRequest {
+    pub format: String,
+    pub text: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<AttachmentRequest>,
+}
+
+impl SendMessageRequest {
+    /// Сообщение в формате HTML без вложений
+    pub fn html(text: impl Into<String>) -> Self {
+        Self {
+            format: "html".to_string(),
+            text: text.into(),
+            attachments: Vec::new(),
+        }
+    }
+
+    /// Добавляет к сообщению вложение (файл, изображение)
+    pub fn with_attachment(mut self, attachment: AttachmentRequest) -> Self {
+        self.attachments.push(attachment);
+        self
+    }
+
+    /// Добавляет к сообщению inline-клавиатуру
+    pub fn with_keyboard(mut self, keyboard: InlineKeyboard) -> Self {
+        if !keyboard.buttons.is_empty() {
+            self.attachments.push(AttachmentRequest::InlineKeyboard(keyboard));
+        }
+        self
+    }
+
+    /// Делит слишком длинное сообщение на несколько по [`MAX_MESSAGE_LENGTH`];
+    /// вложения и клавиатура остаются у последней части
+    pub fn split(&self) -> Vec<SendMessageRequest> {
+        if self.text.chars().count() <= MAX_MESSAGE_LENGTH {
+            return vec![self.clone()];
+        }
+
+        let mut parts: Vec<_> = formatting::split(&self.text, MAX_MESSAGE_LENGTH)
+            .into_iter()
+            .map(|text| SendMessageRequest { text, attachments: Vec::new(), ..self.clone() })
+            .collect();
+        if let Some(last) = parts.last_mut() {
+            last.attachments = self.attachments.clone();
+        }
+        parts
+    }
+
+    /// Сообщение, обрезанное до [`MAX_MESSAGE_LENGTH`] — для ответов, которые нельзя разделить
+    pub fn truncated(&self) -> SendMessageRequest {
+        SendMessageRequest { text: formatting::truncate(&self.text, MAX_MESSAGE_LENGTH), ..self.clone() }
+    }
+}
+
+/// Вложение исходящего сообщения
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "payload", rename_all = "snake_case")]
+pub enum AttachmentRequest {
+    Image(MediaToken),
+    Video(MediaToken),
+    Audio(MediaToken),
+    File(MediaToken),
+    InlineKeyboard(InlineKeyboard),
+}
+
+impl AttachmentRequest {
+    /// Токен загруженного файла (`None` для клавиатуры)
+    pub fn media_token(&self) -> Option<&str> {
+        match self {
+            AttachmentRequest::Image(media)
+            | AttachmentRequest::Video(media)
+            | AttachmentRequest::Audio(media)
+            | AttachmentRequest::File(media) => Some(&media.token),
+            AttachmentRequest::InlineKeyboard(_) => None,
+        }
+    }
+}
+
+/// Ссылка на файл, ранее загруженный через `/uploads`
+#[derive(Debug, Clone, Serialize)]
+pub struct MediaToken {
+    pub token: String,
+}
+
+// ---------------------------------------------------------------------------
+// Загрузка файлов
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UploadType {
+    Image,
+    Video,
+    Audio,
+    File,
+}
+
+impl UploadType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UploadType::Image => "image",
+            UploadType::Video => "video",
+            UploadType::Audio => "audio",
+            UploadType::File => "file",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "image" => Some(UploadType::Image),
+            "video" => Some(UploadType::Video),
+            "audio" => Some(UploadType::Audio),
+            "file" => Some(UploadType::File),
+            _ => None,
+        }
+    }
+
+    /// Вложение исходящего сообщения с загруженным файлом
+    pub fn attachment(&self, token: String) -> AttachmentRequest {
+        match self {
+            UploadType::Image => AttachmentRequest::Image(MediaToken { token }),
+            UploadType::Video => AttachmentRequest::Video(MediaToken { token }),
+            UploadType::Audio => AttachmentRequest::Audio(MediaToken { token }),
+            UploadType::File => AttachmentRequest::File(MediaToken { token }),
+        }
+    }
+}
+
+/// Ответ `POST /uploads`: адрес, куда загружать содержимое
+#[derive(Debug, Clone, Deserialize)]
+pub struct UploadEndpoint {
+    pub url: String,
+    /// Для некоторых типов токен выдаётся сразу
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+/// Inline-клавиатура: строки кнопок под сообщением
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct InlineKeyboard {
+    pub buttons: Vec<Vec<Button>>,
+}
+
+impl InlineKeyboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Добавляет строку кнопок (пустые строки пропускаются)
+    pub fn row(mut self, row: Vec<Button>) -> Self {
+        if !row.is_empty() {
+            self.buttons.push(row);
+        }
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Button {
+    /// Кнопка, нажатие на которую приходит боту как `message_callback`
+    Callback { text: String, payload: String },
+    /// Кнопка-ссылка
+    Link { text: String, url: String },
+    /// Кнопка запуска мини-приложения
+    OpenApp {
+        text: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        web_app: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        contact_id: Option<i64>,
+    },
+}
+
+impl Button {
+    pub fn callback(text: impl Into<String>, payload: impl Into<String>) -> Self {
+        Button::Callback { text: text.into(), payload: payload.into() }
+    }
+
+    pub fn link(text: impl Into<String>, url: impl Into<String>) -> Self {
+        Button::Link { text: text.into(), url: url.into() }
+    }
+
+    pub fn open_app(text: impl Into<String>, web_app: impl Into<String>) -> Self {
+        Button::OpenApp { text: text.into(), web_app: Some(web_app.into()), contact_id: None }
+    }
+}
+
+/// Ответ на нажатие callback-кнопки: новое содержимое сообщения и/или всплывающее уведомление
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CallbackAnswer {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<SendMessageRequest>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notification: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Бот, чаты и подписка на обновления
+// ---------------------------------------------------------------------------
+
+/// Команда в меню бота
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BotCommand {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Ответ `GET /me`
+#[derive(Debug, Clone, Deserialize)]
+pub struct BotInfo {
+    pub user_id: i64,
+    #[serde(default)]
+    pub first_name: String,
+    #[serde(default)]
+    pub last_name: Option<String>,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub is_bot: bool,
+    #[serde(default)]
+    pub last_activity_time: u64,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub avatar_url: Option<String>,
+    #[serde(default)]
+    pub commands: Option<Vec<BotCommand>>,
+}
+
+/// Тело `PATCH /me`: изменяются только заданные поля
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BotPatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commands: Option<Vec<BotCommand>>,
+}
+
+/// Ответ `GET /chats/{chat_id}`
+#[derive(Debug, Clone, Deserialize)]
+pub struct Chat {
+    pub chat_id: i64,
+    /// `dialog`, `chat` или `channel`
+    #[serde(rename = "type")]
+    pub chat_type: String,
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub last_event_time: u64,
+    #[serde(default)]
+    pub participants_count: u64,
+    #[serde(default)]
+    pub owner_id: Option<i64>,
+    #[serde(default)]
+    pub is_public: bool,
+    #[serde(default)]
+    pub link: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChatMember {
+    pub user_id: i64,
+    #[serde(default)]
+    pub first_name: String,
+    #[serde(default)]
+    pub last_name: Option<String>,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub is_bot: bool,
+    #[serde(default)]
+    pub last_activity_time: u64,
+    #[serde(default)]
+    pub is_owner: bool,
+    #[serde(default)]
+    pub is_admin: bool,
+    #[serde(default)]
+    pub join_time: Option<u64>,
+}
+
+/// Ответ `GET /chats/{chat_id}/members`; `marker` — курсор следующей страницы
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChatMembersList {
+    pub members: Vec<ChatMember>,
+    #[serde(default)]
+    pub marker: Option<i64>,
+}
+
+/// Действие бота, которое видят участники чата
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatAction {
+    /// «Печатает…»
+    TypingOn,
+    SendingPhoto,
+    SendingVideo,
+    SendingAudio,
+    SendingFile,
+    MarkSeen,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatActionRequest {
+    pub action: ChatAction,
+}
+
+/// Подписка бота на обновления через вебхук
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookSubscription {
+    pub url: String,
+    #[serde(default)]
+    pub time: u64,
+    #[serde(default)]
+    pub update_types: Option<Vec<String>>,
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+/// Ответ `GET /subscriptions`
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookSubscriptionList {
+    pub subscriptions: Vec<WebhookSubscription>,
+}
+
+/// Тело `POST /subscriptions`
+#[derive(Debug, Clone, Serialize)]
+pub struct SubscribeRequest {
+    pub url: String,
+    /// Пустой список — все типы обновлений
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub update_types: Vec<String>,
+    /// Секрет, который платформа передаёт в заголовке `X-Max-Bot-Api-Secret`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+/// Ответ методов без данных: `{"success": true}`
+#[derive(Debug, Clone, Deserialize)]
+pub struct SimpleQueryResult {
+    pub success: bool,
+    #[serde(default)]
+    pub message: Option<String>,
+}
@@ -0,0 +1,51 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Ограничитель частоты запросов по алгоритму token bucket.
+/// Копит не больше `capacity` запросов, пополняясь со скоростью `rate` в секунду.
+#[derive(Debug)]
+pub struct RateLimiter {
+    rate: f64,
+    capacity: f64,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(requests_per_second: u32) -> Self {
+        let rate = requests_per_second.max(1) as f64;
+        Self {
+            rate,
+            capacity: rate,
+            bucket: Mutex::new(Bucket { tokens: rate, updated_at: Instant::now() }),
+        }
+    }
+
+    /// Ожидание разрешения на очередной запрос
+    pub async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+                let now = Instant::now();
+                let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+                bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.capacity);
+                bucket.updated_at = now;
+
+                if bucket.tokens >= 1.0 {
+                    bucket.tokens -= 1.0;
+                    return;
+                }
+                Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate)
+            };
+
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
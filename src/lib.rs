@@ -1,7 +1,10 @@
+pub mod admin;
 pub mod config;
 pub mod error;
 pub mod api;
+pub mod bot;
 pub mod core;
+pub mod i18n;
 pub mod infrastructure;
 pub mod integrations;
-pub mod state;
\ No newline at end of file
+pub mod state;
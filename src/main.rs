@@ -1,57 +1,152 @@
-use axum::Router;
-use std::net::SocketAddr;
-use tokio::net::TcpListener;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use axum_server::tls_rustls::RustlsConfig;
+use tokio::{net::TcpListener, time::Instant};
+use tokio_util::{sync::CancellationToken, task::TaskTracker};
 use dotenv::dotenv;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
-mod config;
-mod error;
-mod api;
-mod core;
-mod infrastructure;
-mod integrations;
-mod state;
-
-use state::AppState;
+use max_app::{
+    api, bot, config,
+    infrastructure::{self, shutdown, storage::FileStorage},
+    state::AppState,
+};
 
 #[tokio::main]
 async fn main() {
     // Загрузка переменных окружения из .env
     dotenv().ok();
 
-    // Инициализация логирования
-    tracing_subscriber::registry()
-    .with(
-        tracing_subscriber::EnvFilter::try_from_default_env()
-            .unwrap_or_else(|_| "max_app=trace,tower_http=debug".into()),
-    )
-    .with(tracing_subscriber::fmt::layer())
-    .init();
+    // Загрузка конфигурации; журнала ещё нет, поэтому ошибки — прямо в stderr
+    let config = config::load().unwrap_or_else(|e| {
+        eprintln!("❌ {}", e);
+        std::process::exit(1);
+    });
 
-    tracing::info!("🚀 Запуск приложения max_app");
+    // Инициализация логирования; guard держим до выхода, чтобы дописать файл журнала
+    let _log_guard = infrastructure::logger::init(&config).unwrap_or_else(|e| {
+        eprintln!("❌ {}", e);
+        std::process::exit(1);
+    });
 
-    // Загрузка конфигурации
-    let config = config::load().expect("Не удалось загрузить конфигурацию");
+    tracing::info!("🚀 Запуск приложения max_app");
 
     // Инициализация базы данных
-    let pool = infrastructure::database::connect(&config.database_url)
+    let pool = infrastructure::database::connect(config.database_url.expose(), &config.pool_settings())
         .await
-        .expect("Не удалось подключиться к базе данных");
+        .unwrap_or_else(|e| fail(format!("Не удалось подключиться к базе данных: {}", e)));
+
+    // Схема должна соответствовать версии приложения
+    if let Err(e) = infrastructure::database::ensure_schema(&pool, config.database_auto_migrate).await {
+        fail(e);
+    }
 
     // Создание состояния приложения (оборачиваем в Arc сразу)
     let app_state = Arc::new(AppState {
         pool: pool.clone(),
         max_bot_token: config.max_bot_token.clone(),
+        max_api: config.max_api_client(),
+        max_webhook_secret: config.max_webhook_secret.clone(),
+        jwt_secret: config.jwt_secret.clone(),
+        max_mini_app: config.max_mini_app.clone(),
+        bot_dialog_ttl_secs: config.bot_dialog_ttl_secs,
+        storage: FileStorage::new(&config.file_storage_path),
+        max_upload_size_mb: config.max_upload_size_mb,
+        http_body_limit: config.http_body_limit(),
+        max_launch_data_ttl_secs: config.max_launch_data_ttl_secs,
+        bot_analytics_enabled: config.bot_analytics_enabled,
+        analytics_salt: config.analytics_salt.clone(),
+        health_check_max_api: config.health_check_max_api,
+        metrics: config.metrics_enabled.then(infrastructure::metrics::install),
+        metrics_token: config.metrics_token.clone(),
+        metrics_bind_address: config.metrics_bind_address,
     });
 
+    // Сигнал остановки для сервера и фоновых задач
+    let stop = CancellationToken::new();
+    let workers = TaskTracker::new();
+
+    // Фоновая очистка просроченных диалогов бота
+    workers.spawn(bot::dialogs::purge_expired_task(pool.clone(), app_state.storage.clone(), stop.clone()));
+
+    // Размер хранилища для метрик
+    if config.metrics_enabled {
+        workers.spawn(infrastructure::metrics::storage_size_task(app_state.storage.clone(), stop.clone()));
+    }
+
+    // Меню команд и подписка на вебхук в МАКС — в фоне, чтобы не задерживать запуск
+    {
+        let state = app_state.clone();
+        let config = config.clone();
+        let stop = stop.clone();
+        workers.spawn(async move {
+            tokio::select! {
+                _ = stop.cancelled() => {}
+                _ = bot::setup::sync_on_startup(&state.max_api, &config) => {}
+            }
+        });
+    }
+
+    // Рассылка уведомлений подписчикам о новых работах
+    workers.spawn(bot::notifier::notify_task(app_state.clone(), stop.clone()));
+
+    // Метрики на отдельном адресе, недоступном снаружи
+    if let (true, Some(addr)) = (config.metrics_enabled, config.metrics_bind_address) {
+        let listener = TcpListener::bind(addr)
+            .await
+            .unwrap_or_else(|e| fail(format!("Не удалось открыть адрес для метрик {}: {}", addr, e)));
+        let metrics_app = api::routes::create_metrics_router(app_state.clone());
+        tracing::info!("📊 Метрики доступны на http://{}/metrics", addr);
+        workers.spawn(shutdown::serve(listener, metrics_app, None, stop.clone()));
+    }
+
     // Создание маршрутов
     let app = api::routes::create_router(app_state);
 
-    // Запуск сервера — НОВЫЙ РЕКОМЕНДОВАННЫЙ СПОСОБ для Axum 0.7+
-    let addr = SocketAddr::from(([127, 0, 0, 1], config.port));
-    tracing::info!("📡 Сервер запущен на http://{}", addr);
+    // Запуск сервера: HTTPS, если заданы сертификат и ключ, иначе HTTP
+    let addr = config.server_address();
+    let tls = match config.tls() {
+        Some((cert, key)) => {
+            let tls = RustlsConfig::from_pem_file(cert, key).await;
+            Some(tls.unwrap_or_else(|e| fail(format!("Не удалось загрузить сертификат TLS {}: {}", cert, e))))
+        }
+        None => None,
+    };
+    let listener = TcpListener::bind(addr)
+        .await
+        .unwrap_or_else(|e| fail(format!("Не удалось открыть адрес сервера {}: {}", addr, e)));
+    tracing::info!("📡 Сервер запущен на {}://{}", if tls.is_some() { "https" } else { "http" }, addr);
+    let mut server = tokio::spawn(shutdown::serve(listener, app, tls, stop.clone()));
+
+    tokio::select! {
+        _ = shutdown::signal() => stop.cancel(),
+        result = &mut server => {
+            stop.cancel();
+            if let Ok(Err(e)) = result {
+                tracing::error!("❌ Сервер остановился с ошибкой: {}", e);
+            }
+        }
+    }
+
+    // Начатые запросы (загрузки, обновления вебхука) и текущие проходы фоновых задач
+    // доделываются в пределах общего срока; после него оставшееся прерывается
+    let deadline = Instant::now() + Duration::from_secs(config.shutdown_timeout_secs);
+    if !server.is_finished() && tokio::time::timeout_at(deadline, &mut server).await.is_err() {
+        tracing::warn!("⏱️ Не все запросы завершились за {} с, соединения закрываются", config.shutdown_timeout_secs);
+        server.abort();
+    }
+    workers.close();
+    if tokio::time::timeout_at(deadline, workers.wait()).await.is_err() {
+        tracing::warn!("⏱️ Фоновые задачи не завершились за {} с", config.shutdown_timeout_secs);
+    }
+
+    // Соединения, которые всё ещё заняты прерванными задачами, не ждём
+    if tokio::time::timeout_at(deadline, pool.close()).await.is_err() {
+        tracing::warn!("⏱️ Пул соединений с базой закрыт, не дождавшись всех соединений");
+    }
+    tracing::info!("👋 Приложение остановлено");
+}
 
-    let listener = TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
-}
\ No newline at end of file
+/// Остановка при ошибке запуска: понятное сообщение в журнале вместо паники
+fn fail(error: impl std::fmt::Display) -> ! {
+    tracing::error!("❌ {}", error);
+    std::process::exit(1);
+}
@@ -1,7 +1,34 @@
+use metrics_exporter_prometheus::PrometheusHandle;
 use sqlx::PgPool;
+use std::net::SocketAddr;
+
+use crate::{config::Secret, infrastructure::storage::FileStorage, integrations::max::MaxApiClient};
 
 #[derive(Clone)]
 pub struct AppState {
     pub pool: PgPool,
-    pub max_bot_token: String,
-}
\ No newline at end of file
+    pub max_bot_token: Secret<String>,
+    /// Общий клиент MAX Bot API с лимитом запросов на всё приложение
+    pub max_api: MaxApiClient,
+    /// Секрет, который платформа присылает с каждым обновлением вебхука
+    pub max_webhook_secret: Secret<String>,
+    pub jwt_secret: Secret<String>,
+    pub max_mini_app: Option<String>,
+    pub bot_dialog_ttl_secs: u64,
+    pub storage: FileStorage,
+    pub max_upload_size_mb: u64,
+    /// Наибольший размер тела запроса к API, байты
+    pub http_body_limit: usize,
+    pub max_launch_data_ttl_secs: u64,
+    pub bot_analytics_enabled: bool,
+    /// Ключ обезличивания пользователей в аналитике
+    pub analytics_salt: Secret<String>,
+    /// Проверять доступность MAX API в `/health/ready`
+    pub health_check_max_api: bool,
+    /// Выдача `/metrics`; `None` — метрики отключены
+    pub metrics: Option<PrometheusHandle>,
+    /// Токен для `Authorization: Bearer` на `/metrics`
+    pub metrics_token: Option<Secret<String>>,
+    /// Отдельный адрес для `/metrics`; если задан, на основном адресе метрик нет
+    pub metrics_bind_address: Option<SocketAddr>,
+}
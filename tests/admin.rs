@@ -0,0 +1,287 @@
+//! Операции `max_app-admin`: пользователи, импорт и выгрузка работ, поиск, обложки, хранилище и вебхук.
+
+mod support;
+
+use std::{io::Cursor, path::Path, process::Output};
+
+use image::{ImageOutputFormat, RgbImage};
+use tokio::process::Command;
+
+use max_app::{
+    admin::{self, works::WorkImport},
+    core::{
+        models::{UserCreateDto, UserRole, Work, WorkCreateDto, WorkStatus, WorkType, WorkUpdateDto},
+        services::{AuthService, WorkService},
+    },
+    error::AppError,
+    infrastructure::thumbnails,
+};
+use support::{TestApp, BOT_TOKEN};
+
+fn work(title: &str, file_path: &str, status: WorkStatus) -> WorkCreateDto {
+    WorkCreateDto {
+        title: title.to_string(),
+        work_type: WorkType::Project,
+        specialty: "Информационные системы".to_string(),
+        author_name: "Иванов И. И.".to_string(),
+        supervisor_name: "Петров П. П.".to_string(),
+        year: 2024,
+        annotation: Some("Распознавание рукописного текста нейросетями".to_string()),
+        keywords: None,
+        file_path: file_path.to_string(),
+        thumbnail_path: None,
+        status,
+        submitter_max_user_id: None,
+    }
+}
+
+fn png(width: u32, height: u32) -> Vec<u8> {
+    let mut data = Vec::new();
+    RgbImage::from_pixel(width, height, image::Rgb([200, 40, 40]))
+        .write_to(&mut Cursor::new(&mut data), ImageOutputFormat::Png)
+        .unwrap();
+    data
+}
+
+fn field_names(error: AppError) -> Vec<String> {
+    match error {
+        AppError::InvalidFields(fields) => fields.into_iter().map(|f| f.field).collect(),
+        other => panic!("ожидались ошибки полей, получено {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn admin_is_created_once_and_password_can_be_reset() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let auth = AuthService::new(app.pool.clone(), app.state.jwt_secret.clone());
+    let dto = || UserCreateDto {
+        username: " librarian ".to_string(),
+        password: "first-password".to_string(),
+        role: UserRole::Admin,
+        full_name: Some("Библиотекарь".to_string()),
+    };
+
+    let user = auth.create_user(dto()).await.unwrap();
+    assert_eq!(user.username, "librarian");
+    assert_eq!(user.role, UserRole::Admin);
+    assert!(matches!(auth.create_user(dto()).await, Err(AppError::Conflict(_))));
+
+    let short = UserCreateDto { username: "other".to_string(), password: "short".to_string(), ..dto() };
+    assert_eq!(field_names(auth.create_user(short).await.unwrap_err()), ["password"]);
+
+    auth.login("librarian", "first-password").await.unwrap();
+    auth.reset_password("librarian", "second-password").await.unwrap();
+    assert!(auth.login("librarian", "first-password").await.is_err());
+    auth.login("librarian", "second-password").await.unwrap();
+
+    assert!(matches!(auth.reset_password("nobody", "second-password").await, Err(AppError::NotFound)));
+    assert_eq!(field_names(auth.reset_password("librarian", "short").await.unwrap_err()), ["password"]);
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn import_is_all_or_nothing_and_round_trips_export() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let works = WorkService::new(app.pool.clone());
+
+    let invalid = vec![
+        work("Первая", "works/1.pdf", WorkStatus::Published),
+        WorkCreateDto { title: " ".to_string(), year: 1800, ..work("Вторая", "works/2.pdf", WorkStatus::Draft) },
+    ];
+    let mut fields = field_names(admin::works::import(&app.pool, invalid).await.unwrap_err());
+    fields.sort();
+    assert_eq!(fields, ["works[1].title", "works[1].year"]);
+    assert!(works.list_all(None).await.unwrap().is_empty());
+
+    // Сбой базы на второй записи откатывает и первую
+    sqlx::query(
+        "CREATE FUNCTION reject_work() RETURNS trigger LANGUAGE plpgsql AS
+         $$ BEGIN IF NEW.title = 'Сбой' THEN RAISE EXCEPTION 'сбой записи'; END IF; RETURN NEW; END $$",
+    )
+    .execute(&app.pool)
+    .await
+    .unwrap();
+    sqlx::query("CREATE TRIGGER reject_work BEFORE INSERT ON works FOR EACH ROW EXECUTE FUNCTION reject_work()")
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    let failing = vec![work("Первая", "works/1.pdf", WorkStatus::Published), work("Сбой", "works/2.pdf", WorkStatus::Draft)];
+    assert!(matches!(admin::works::import(&app.pool, failing).await, Err(AppError::DatabaseError(_))));
+    assert!(works.list_all(None).await.unwrap().is_empty());
+
+    let imported = admin::works::import(
+        &app.pool,
+        vec![work("Первая", "works/1.pdf", WorkStatus::Published), work("Вторая", "works/2.pdf", WorkStatus::Draft)],
+    )
+    .await
+    .unwrap();
+    assert_eq!(imported.len(), 2);
+    // Импортированный архив не рассылается подписчикам как новинки
+    assert!(works.list_unnotified(10).await.unwrap().is_empty());
+
+    let published = works.list_all(Some(WorkStatus::Published)).await.unwrap();
+    assert_eq!(published.len(), 1);
+    assert_eq!(published[0].title, "Первая");
+
+    // Выгрузка читается импортом как есть
+    let exported = serde_json::to_string(&works.list_all(None).await.unwrap()).unwrap();
+    let again: Vec<WorkImport> = serde_json::from_str(&exported).unwrap();
+    admin::works::import(&app.pool, again.into_iter().map(Into::into).collect()).await.unwrap();
+    let all: Vec<Work> = works.list_all(None).await.unwrap();
+    assert_eq!(all.len(), 4);
+    assert_eq!(all.iter().filter(|w| w.title == "Вторая" && w.status == WorkStatus::Draft).count(), 2);
+    assert_eq!(all.iter().filter(|w| w.title == "Первая" && w.status == WorkStatus::Published).count(), 2);
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn search_index_is_rebuilt() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let works = WorkService::new(app.pool.clone());
+    admin::works::import(&app.pool, vec![work("Нейросети в медицине", "works/1.pdf", WorkStatus::Published)])
+        .await
+        .unwrap();
+
+    sqlx::query("ALTER TABLE works DISABLE TRIGGER USER").execute(&app.pool).await.unwrap();
+    sqlx::query("UPDATE works SET search_vector = NULL").execute(&app.pool).await.unwrap();
+    sqlx::query("ALTER TABLE works ENABLE TRIGGER USER").execute(&app.pool).await.unwrap();
+    let found = works.search(Some("нейросети"), None, None, None, 1, 10).await.unwrap();
+    assert!(found.is_empty());
+
+    assert_eq!(works.rebuild_search_index().await.unwrap(), 1);
+    let found = works.search(Some("нейросети"), None, None, None, 1, 10).await.unwrap();
+    assert_eq!(found.len(), 1);
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn thumbnails_are_generated_for_images_only() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let storage = &app.state.storage;
+    let works = WorkService::new(app.pool.clone());
+
+    storage.write("works/poster.png", &png(1200, 600)).await.unwrap();
+    storage.write("works/report.pdf", b"%PDF-1.4 ...").await.unwrap();
+    let ids = admin::works::import(
+        &app.pool,
+        vec![
+            work("Плакат", "works/poster.png", WorkStatus::Published),
+            work("Отчёт", "works/report.pdf", WorkStatus::Published),
+            work("Потерянный", "works/lost.png", WorkStatus::Published),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let report = admin::works::regenerate_thumbnails(&app.pool, storage, false).await.unwrap();
+    assert_eq!(report.generated, 1);
+    assert_eq!(report.skipped, 1);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].0, ids[2]);
+
+    let poster = works.get_by_id(ids[0]).await.unwrap().unwrap();
+    assert_eq!(poster.thumbnail_path.as_deref(), Some(thumbnails::thumbnail_path(ids[0]).as_str()));
+    let thumbnail = image::load_from_memory(&storage.read(poster.thumbnail_path.as_deref().unwrap()).await.unwrap()).unwrap();
+    assert_eq!((thumbnail.width(), thumbnail.height()), (thumbnails::THUMBNAIL_SIZE, thumbnails::THUMBNAIL_SIZE / 2));
+
+    // Существующие обложки пересоздаются только с `force`
+    works.delete(ids[2]).await.unwrap();
+    let report = admin::works::regenerate_thumbnails(&app.pool, storage, false).await.unwrap();
+    assert_eq!((report.generated, report.skipped), (0, 2));
+    let report = admin::works::regenerate_thumbnails(&app.pool, storage, true).await.unwrap();
+    assert_eq!((report.generated, report.skipped), (1, 1));
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn storage_verify_reports_missing_and_orphaned_files() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let storage = &app.state.storage;
+
+    storage.write("works/ok.pdf", b"%PDF-1.4 ...").await.unwrap();
+    storage.write("works/empty.pdf", b"").await.unwrap();
+    storage.write("works/forgotten.pdf", b"%PDF-1.4 ...").await.unwrap();
+    let ids = admin::works::import(
+        &app.pool,
+        vec![
+            work("Целая", "works/ok.pdf", WorkStatus::Published),
+            work("Пустая", "works/empty.pdf", WorkStatus::Published),
+            work("Без файла", "works/gone.pdf", WorkStatus::Draft),
+            work("Во внешнем хранилище", "https://files.example/remote.pdf", WorkStatus::Published),
+        ],
+    )
+    .await
+    .unwrap();
+    WorkService::new(app.pool.clone())
+        .update(ids[0], WorkUpdateDto { thumbnail_path: Some("thumbnails/none.jpg".to_string()), ..Default::default() })
+        .await
+        .unwrap();
+
+    let report = admin::storage::verify(&app.pool, storage).await.unwrap();
+    assert!(!report.is_ok());
+    assert_eq!(report.checked, 4);
+    let mut missing: Vec<(&str, &str)> = report.missing.iter().map(|m| (m.path.as_str(), m.problem)).collect();
+    missing.sort();
+    assert_eq!(
+        missing,
+        [("thumbnails/none.jpg", "нет файла"), ("works/empty.pdf", "пустой файл"), ("works/gone.pdf", "нет файла")]
+    );
+    assert_eq!(report.orphaned, ["works/forgotten.pdf"]);
+
+    app.cleanup().await;
+}
+
+/// `max_app-admin` с конфигурацией приложения из переменных окружения; процесс асинхронный,
+/// чтобы мок МАКС в том же рантайме мог отвечать на его запросы
+async fn admin(app: &TestApp, workdir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_max_app-admin"))
+        .args(args)
+        .current_dir(workdir)
+        .env("MAX_APP_PORT", "0")
+        .env("MAX_APP_DATABASE_URL", app.database_url())
+        .env("MAX_APP_MAX_BOT_TOKEN", BOT_TOKEN)
+        .env("MAX_APP_MAX_API_BASE_URL", app.max.url())
+        .env("MAX_APP_MAX_API_MAX_RETRIES", "0")
+        .env("MAX_APP_JWT_SECRET", app.state.jwt_secret.expose())
+        .env("MAX_APP_MAX_WEBHOOK_SECRET", "test-webhook-secret")
+        .env("MAX_APP_ANALYTICS_SALT", "test-analytics-salt")
+        .env("MAX_APP_FILE_STORAGE_PATH", app.state.storage.root())
+        .output()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn admin_cli_creates_admin_and_registers_webhook() {
+    let Some(app) = TestApp::spawn().await else { return };
+    const URL: &str = "https://archive.example/api/max/webhook";
+    let workdir = app.state.storage.root().with_extension("admin");
+    std::fs::create_dir_all(&workdir).unwrap();
+
+    let output = admin(&app, &workdir, &["user", "create-admin", "librarian"]).await;
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let password = stdout.lines().find_map(|line| line.strip_prefix("Пароль: ")).unwrap();
+    AuthService::new(app.pool.clone(), app.state.jwt_secret.clone()).login("librarian", password).await.unwrap();
+
+    let output = admin(&app, &workdir, &["user", "create-admin", "librarian"]).await;
+    assert!(!output.status.success());
+
+    let output = admin(&app, &workdir, &["webhook", "register", "--url", URL]).await;
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(app.max.subscriptions().iter().any(|s| s["url"] == URL));
+    let subscribe = app.max.calls().into_iter().rfind(|c| c.path == "/subscriptions").unwrap();
+    assert_eq!(subscribe.body["secret"], "test-webhook-secret");
+    let output = admin(&app, &workdir, &["webhook", "register", "--url", URL]).await;
+    assert!(String::from_utf8(output.stdout).unwrap().contains("уже зарегистрирован"));
+
+    let output = admin(&app, &workdir, &["storage", "verify"]).await;
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let _ = std::fs::remove_dir_all(&workdir);
+    app.cleanup().await;
+}
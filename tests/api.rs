@@ -0,0 +1,279 @@
+//! HTTP API: формат ошибок, проверка запросов, идентификатор запроса и язык сообщений.
+
+mod support;
+
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::{body::to_bytes, response::IntoResponse};
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+use uuid::Uuid;
+
+use max_app::{
+    api::routes::create_router,
+    core::models::{WorkCreateDto, WorkStatus, WorkType, WorkUpdateDto},
+    error::{AppError, FieldError},
+    state::AppState,
+};
+use validator::Validate;
+use support::TestApp;
+
+async fn error_body(error: AppError) -> (u16, Value) {
+    let response = error.into_response();
+    let status = response.status().as_u16();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn every_error_has_status_and_code() {
+    let cases = [
+        (AppError::Internal("секрет".into()), 500, "internal_error"),
+        (AppError::BadRequest("плохо".into()), 400, "bad_request"),
+        (AppError::Unauthorized, 401, "unauthorized"),
+        (AppError::Forbidden, 403, "forbidden"),
+        (AppError::NotFound, 404, "not_found"),
+        (AppError::ValidationError("неверно".into()), 400, "validation_failed"),
+        (AppError::invalid_field("year", "неверно"), 400, "validation_failed"),
+        (AppError::Conflict("уже есть".into()), 409, "conflict"),
+        (AppError::PayloadTooLarge(50 * 1024 * 1024), 413, "payload_too_large"),
+        (AppError::TooManyRequests(None), 429, "too_many_requests"),
+        (AppError::UnsupportedMediaType("text/plain".into()), 415, "unsupported_media_type"),
+        (AppError::ServiceUnavailable("база недоступна".into()), 503, "service_unavailable"),
+    ];
+
+    for (error, status, code) in cases {
+        let (actual_status, body) = error_body(error).await;
+        assert_eq!((actual_status, body["error"]["code"].as_str()), (status, Some(code)));
+        assert!(body["error"]["message"].as_str().is_some_and(|m| !m.is_empty()));
+    }
+}
+
+#[tokio::test]
+async fn internal_details_are_not_exposed() {
+    let (_, body) = error_body(AppError::Internal("пароль от базы".into())).await;
+    assert_eq!(body, json!({ "error": { "code": "internal_error", "message": "Внутренняя ошибка сервера" } }));
+
+    let (_, body) = error_body(AppError::ServiceUnavailable("pool timed out".into())).await;
+    assert!(!body.to_string().contains("pool"));
+}
+
+#[tokio::test]
+async fn validation_errors_list_fields() {
+    let error = AppError::InvalidFields(vec![
+        FieldError { field: "title".into(), message: "Пустое название".into() },
+        FieldError { field: "year".into(), message: "Неверный год".into() },
+    ]);
+    let (status, body) = error_body(error).await;
+
+    assert_eq!(status, 400);
+    assert_eq!(body["error"]["message"], "Проверьте поля запроса");
+    assert_eq!(
+        body["error"]["details"],
+        json!([
+            { "field": "title", "message": "Пустое название" },
+            { "field": "year", "message": "Неверный год" },
+        ])
+    );
+}
+
+#[tokio::test]
+async fn too_many_requests_sets_retry_after() {
+    let response = AppError::TooManyRequests(Some(30)).into_response();
+    assert_eq!(response.headers()["retry-after"], "30");
+}
+
+#[tokio::test]
+async fn errors_carry_request_id() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let http = reqwest::Client::new();
+
+    let response = http
+        .get(format!("{}/api/works/{}", app.url, Uuid::new_v4()))
+        .header("X-Request-Id", "req-42")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.headers()["x-request-id"], "req-42");
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body, json!({ "error": { "code": "not_found", "message": "Ресурс не найден", "request_id": "req-42" } }));
+
+    // Без заголовка идентификатор генерируется; неизвестный адрес — та же JSON-ошибка
+    let response = http.get(format!("{}/api/unknown", app.url)).send().await.unwrap();
+    let request_id = response.headers()["x-request-id"].to_str().unwrap().to_string();
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"]["code"], "not_found");
+    assert_eq!(body["error"]["request_id"], request_id.as_str());
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn api_errors_follow_accept_language() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let http = reqwest::Client::new();
+
+    let me = |language: &'static str| {
+        http.get(format!("{}/api/auth/me", app.url)).header("Accept-Language", language).send()
+    };
+
+    let response = me("en-GB,en;q=0.9,ru;q=0.5").await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"]["code"], "unauthorized");
+    assert_eq!(body["error"]["message"], "Authentication required");
+
+    let body: Value = me("ru-RU,ru;q=0.9").await.unwrap().json().await.unwrap();
+    assert_eq!(body["error"]["message"], "Требуется авторизация");
+
+    app.cleanup().await;
+}
+
+fn fields(error: AppError) -> Vec<(String, String)> {
+    match error {
+        AppError::InvalidFields(fields) => fields.into_iter().map(|f| (f.field, f.message)).collect(),
+        other => panic!("ожидалась ошибка полей, получено {:?}", other),
+    }
+}
+
+#[test]
+fn work_dto_reports_every_invalid_field() {
+    let dto = WorkCreateDto {
+        title: "   ".to_string(),
+        work_type: WorkType::Project,
+        specialty: "x".repeat(201),
+        author_name: "Сидорова Анна".to_string(),
+        supervisor_name: String::new(),
+        year: 3000,
+        annotation: None,
+        keywords: None,
+        file_path: "works/1.pdf".to_string(),
+        thumbnail_path: None,
+        status: WorkStatus::Draft,
+        submitter_max_user_id: None,
+    };
+
+    let errors = fields(dto.validate().unwrap_err().into());
+    assert_eq!(
+        errors,
+        [
+            ("specialty".to_string(), "Не длиннее 200 символов".to_string()),
+            ("supervisor_name".to_string(), "Значение не может быть пустым".to_string()),
+            ("title".to_string(), "Значение не может быть пустым".to_string()),
+            ("year".to_string(), "Значение должно быть от 1900 до 2100".to_string()),
+        ]
+    );
+
+    // В изменении проверяются только переданные поля
+    assert!(WorkUpdateDto::default().validate().is_ok());
+    let update = WorkUpdateDto { year: Some(1800), ..Default::default() };
+    assert_eq!(fields(update.validate().unwrap_err().into())[0].0, "year");
+}
+
+#[test]
+fn work_dto_is_trimmed_and_ignores_server_fields() {
+    let dto: WorkCreateDto = serde_json::from_value(json!({
+        "title": "  Анализ данных ",
+        "work_type": "Essay",
+        "specialty": "09.02.07",
+        "author_name": "Сидорова Анна",
+        "supervisor_name": " Иванова М. П.",
+        "year": 2025,
+        "annotation": "   ",
+        "file_path": "works/1.pdf",
+        "status": "published",
+        "submitter_max_user_id": 42,
+    }))
+    .unwrap();
+    // Статус и автора заявки клиент задать не может
+    assert_eq!(dto.status, WorkStatus::Draft);
+    assert_eq!(dto.submitter_max_user_id, None);
+
+    let dto = dto.trimmed();
+    assert_eq!(dto.title, "Анализ данных");
+    assert_eq!(dto.supervisor_name, "Иванова М. П.");
+    assert_eq!(dto.annotation, None);
+
+    let update = WorkUpdateDto { title: Some(" \t ".to_string()), ..Default::default() }.trimmed();
+    assert_eq!(fields(update.validate().unwrap_err().into())[0].0, "title");
+}
+
+#[tokio::test]
+async fn requests_are_validated_before_handlers() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let http = reqwest::Client::new();
+    let login = format!("{}/api/auth/login", app.url);
+
+    // Все ошибки сразу, а не только первая
+    let response = http.post(&login).json(&json!({ "username": " ", "password": "" })).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"]["code"], "validation_failed");
+    assert_eq!(
+        body["error"]["details"],
+        json!([
+            { "field": "password", "message": "Длина — от 1 до 1024 символов" },
+            { "field": "username", "message": "Значение не может быть пустым" },
+        ])
+    );
+
+    let response = http.post(&login).body(r#"{"username":"a","password":"b"}"#).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    let response = http
+        .post(&login)
+        .header("Content-Type", "application/json")
+        .body("{")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"]["code"], "bad_request");
+
+    let response = http
+        .get(format!("{}/api/works/search?year=3000&work_type=bogus&limit=500", app.url))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body: Value = response.json().await.unwrap();
+    let invalid: Vec<&str> =
+        body["error"]["details"].as_array().unwrap().iter().map(|d| d["field"].as_str().unwrap()).collect();
+    assert_eq!(invalid, ["limit", "work_type", "year"]);
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn request_body_limit_is_configurable() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let state = Arc::new(AppState { http_body_limit: 1024 * 1024, ..(*app.state).clone() });
+    let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))).await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, create_router(state)).await.unwrap() });
+
+    let password = "x".repeat(1024 * 1024);
+    let response = reqwest::Client::new()
+        .post(format!("{}/api/auth/login", url))
+        .json(&json!({ "username": "admin", "password": password }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"]["message"], "Слишком большой запрос: допускается до 1 МБ");
+
+    // Под лимитом запрос доходит до проверки полей
+    let response = reqwest::Client::new()
+        .post(format!("{}/api/auth/login", url))
+        .json(&json!({ "username": "admin", "password": "x".repeat(2000) }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    app.cleanup().await;
+}
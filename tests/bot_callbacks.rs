@@ -0,0 +1,71 @@
+//! Payload callback-кнопок: кодирование и разбор действий бота.
+
+use uuid::Uuid;
+
+use max_app::{
+    bot::{callbacks::CallbackAction, commands::SearchFilter},
+    core::models::WorkType,
+};
+
+fn round_trip(action: CallbackAction) {
+    let payload = action.encode();
+    assert_eq!(CallbackAction::parse(&payload), Some(action), "{}", payload);
+}
+
+#[test]
+fn actions_survive_encoding() {
+    let id = Uuid::new_v4();
+    round_trip(CallbackAction::Work(id));
+    round_trip(CallbackAction::Download(id));
+    round_trip(CallbackAction::Review { id, approve: true });
+    round_trip(CallbackAction::Review { id, approve: false });
+    round_trip(CallbackAction::Unsubscribe(id));
+    round_trip(CallbackAction::Dialog("essay".to_string()));
+    round_trip(CallbackAction::Cancel);
+    round_trip(CallbackAction::Search { page: 3, filter: SearchFilter::by_query("нейросети: обзор") });
+    round_trip(CallbackAction::Search { page: 1, filter: SearchFilter::default() });
+    // Специальность идёт последней и может содержать ':'
+    round_trip(CallbackAction::Search {
+        page: 2,
+        filter: SearchFilter {
+            query: None,
+            specialty: Some("09.02.07: Информационные системы".to_string()),
+            year: Some(2025),
+            work_type: Some(WorkType::Essay),
+        },
+    });
+}
+
+#[test]
+fn dialog_choice_keeps_separators() {
+    assert_eq!(CallbackAction::parse("dlg:a:b"), Some(CallbackAction::Dialog("a:b".to_string())));
+    assert_eq!(CallbackAction::Dialog("09.02.07".to_string()).encode(), "dlg:09.02.07");
+}
+
+#[test]
+fn long_queries_are_cut_in_payload() {
+    let action = CallbackAction::Search { page: 2, filter: SearchFilter::by_query(&"я".repeat(500)) };
+    let Some(CallbackAction::Search { filter, .. }) = CallbackAction::parse(&action.encode()) else {
+        panic!("ожидалась страница поиска");
+    };
+    assert_eq!(filter.query.unwrap().chars().count(), 200);
+}
+
+#[test]
+fn malformed_payloads_are_rejected() {
+    for payload in [
+        "",
+        "work",
+        "work:не-uuid",
+        "search:0:нейросети",
+        "search:x:нейросети",
+        "search:2",
+        "browse:1:не-год::",
+        "browse:1::неизвестный:",
+        "browse:1:2025",
+        "unknown:1",
+        "cancel:1",
+    ] {
+        assert_eq!(CallbackAction::parse(payload), None, "{}", payload);
+    }
+}
@@ -0,0 +1,977 @@
+//! Сквозные тесты бота: обновления приходят на вебхук приложения,
+//! ответы бота перехватывает мок MAX Bot API.
+
+mod support;
+
+use reqwest::StatusCode;
+use serde_json::json;
+
+use max_app::{
+    api::handlers::max_webhook::SECRET_HEADER,
+    bot::{analytics, notifier, registry, setup},
+    core::{
+        models::{UserRole, Work, WorkCreateDto, WorkStatus, WorkType},
+        services::{SubscriptionService, WorkService},
+    },
+    integrations::max::MaxApiError,
+};
+use support::{
+    bot_started, group_message_created, message_callback, message_created, mock_max::BOT_USER_ID, with_locale, TestApp,
+    WEBHOOK_SECRET,
+};
+
+const CHAT_ID: i64 = 1001;
+const USER_ID: i64 = 2001;
+const GROUP_CHAT_ID: i64 = -5001;
+
+async fn create_work(app: &TestApp, title: &str, status: WorkStatus) -> Work {
+    WorkService::new(app.pool.clone())
+        .create(WorkCreateDto {
+            title: title.to_string(),
+            work_type: WorkType::Project,
+            specialty: "09.02.07 Информационные системы и программирование".to_string(),
+            author_name: "Сидорова Анна".to_string(),
+            supervisor_name: "Иванова Мария Петровна".to_string(),
+            year: 2025,
+            annotation: None,
+            keywords: Some("нейросети, python".to_string()),
+            file_path: "works/2025/missing.pdf".to_string(),
+            thumbnail_path: None,
+            status,
+            submitter_max_user_id: None,
+        })
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn bot_started_sends_greeting() {
+    let Some(app) = TestApp::spawn().await else { return };
+
+    assert_eq!(app.send_update(bot_started(CHAT_ID, USER_ID)).await, StatusCode::OK);
+
+    let messages = app.max.messages();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].chat_id, Some(CHAT_ID));
+    assert_eq!(messages[0].user_id, Some(USER_ID));
+    assert!(messages[0].text.starts_with("👋 Добро пожаловать в Цифровой архив"));
+    assert_eq!(messages[0].body["format"], "html");
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn unknown_command_gets_exact_reply() {
+    let Some(app) = TestApp::spawn().await else { return };
+
+    app.send_text(CHAT_ID, USER_ID, "/unknown").await;
+
+    let messages = app.max.messages();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].text, "❌ Неизвестная команда. Введите /help для справки.");
+    assert!(messages[0].body.get("attachments").is_none());
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn forged_updates_without_secret_are_rejected() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let http = reqwest::Client::new();
+    let webhook = format!("{}/api/max/webhook", app.url);
+    let forged = message_created(CHAT_ID, USER_ID, json!({ "mid": "mid.in", "seq": 1, "text": "/help" }));
+
+    let response = http.post(&webhook).json(&forged).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = http.post(&webhook).header(SECRET_HEADER, "guessed-secret").json(&forged).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert!(app.max.messages().is_empty());
+
+    // С настоящим секретом то же обновление обрабатывается
+    assert_eq!(app.send_update(forged).await, StatusCode::OK);
+    assert_eq!(app.max.messages().len(), 1);
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn invalid_webhook_body_is_rejected() {
+    let Some(app) = TestApp::spawn().await else { return };
+
+    assert_eq!(app.send_update(json!({ "update_type": "message_created" })).await, StatusCode::BAD_REQUEST);
+    assert!(app.max.calls().is_empty());
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn search_shows_only_published_works_with_buttons() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let published = create_work(&app, "Нейросети в медицине", WorkStatus::Published).await;
+    create_work(&app, "Нейросети черновик", WorkStatus::Draft).await;
+
+    app.send_text(CHAT_ID, USER_ID, "/search нейросети").await;
+
+    let messages = app.max.messages();
+    assert_eq!(messages.len(), 1);
+    let reply = &messages[0];
+    assert!(reply.text.starts_with("🔍 Результаты поиска по запросу \"нейросети\":"), "{}", reply.text);
+    assert!(reply.text.contains("1. <b>Нейросети в медицине</b>"));
+    assert!(!reply.text.contains("черновик"));
+    assert_eq!(
+        reply.callback_payloads(),
+        vec![format!("work:{}", published.id), format!("download:{}", published.id)]
+    );
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn next_page_button_replaces_results_and_outdated_buttons_are_answered() {
+    let Some(app) = TestApp::spawn().await else { return };
+    for i in 1..=6 {
+        create_work(&app, &format!("Нейросети, часть {}", i), WorkStatus::Published).await;
+    }
+
+    app.send_text(CHAT_ID, USER_ID, "/search нейросети").await;
+    let payloads = app.max.messages()[0].callback_payloads();
+    assert_eq!(payloads.last().unwrap(), "search:2:нейросети");
+
+    // «Далее» заменяет сообщение со страницей результатов
+    app.send_update(message_callback(CHAT_ID, USER_ID, "cb-next", "search:2:нейросети")).await;
+    let answers = app.max.callback_answers();
+    let page = answers[0].1["message"]["text"].as_str().unwrap();
+    assert!(page.starts_with("🔍 Результаты поиска"), "{}", page);
+    assert!(page.contains("6. <b>Нейросети, часть 1</b>"), "{}", page);
+    assert_eq!(app.max.messages().len(), 1);
+
+    app.send_update(message_callback(CHAT_ID, USER_ID, "cb-old", "open:старая-кнопка")).await;
+    assert_eq!(app.max.callback_answers()[1].1, json!({ "notification": "❌ Кнопка устарела или неизвестна" }));
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn search_accepts_arbitrary_user_text() {
+    let Some(app) = TestApp::spawn().await else { return };
+    create_work(&app, "Нейросети в медицине", WorkStatus::Published).await;
+
+    app.send_text(CHAT_ID, USER_ID, "/search нейросети  медицине").await;
+    app.send_text(CHAT_ID, USER_ID, "/search C++ (java").await;
+    app.send_text(CHAT_ID, USER_ID, "/search нейросети & | !").await;
+
+    let messages = app.max.messages();
+    assert_eq!(messages.len(), 3);
+    assert!(messages[0].text.contains("1. <b>Нейросети в медицине</b>"), "{}", messages[0].text);
+    assert!(messages[1].text.starts_with("🔍 Ничего не найдено"), "{}", messages[1].text);
+    assert!(messages[2].text.contains("1. <b>Нейросети в медицине</b>"), "{}", messages[2].text);
+
+    // Тот же запрос через REST
+    let response = reqwest::get(format!("{}/api/works/search?query=C%2B%2B%20%20(java", app.url)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn work_button_answers_callback_and_sends_card() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let work = create_work(&app, "Нейросети в медицине", WorkStatus::Published).await;
+
+    let update = message_callback(CHAT_ID, USER_ID, "cb-1", &format!("work:{}", work.id));
+    assert_eq!(app.send_update(update).await, StatusCode::OK);
+
+    let answers = app.max.callback_answers();
+    assert_eq!(answers.len(), 1);
+    assert_eq!(answers[0].0, "cb-1");
+    assert_eq!(answers[0].1, json!({ "notification": "📄 Открываю работу…" }));
+
+    let messages = app.max.messages();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].chat_id, Some(CHAT_ID));
+    assert!(messages[0].text.starts_with("📄 <b>Нейросети в медицине</b>"));
+    // Файла нет в хранилище — бот сообщает об этом вместо вложения
+    assert!(messages[0].text.ends_with("⚠️ Файл работы временно недоступен"));
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn work_card_attaches_thumbnail_and_file_uploaded_once() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let work = create_work(&app, "Нейросети в медицине", WorkStatus::Published).await;
+    sqlx::query("UPDATE works SET thumbnail_path = 'thumbnails/cover.png' WHERE id = $1")
+        .bind(work.id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    app.state.storage.write(&work.file_path, b"%PDF-1.4 test").await.unwrap();
+    app.state.storage.write("thumbnails/cover.png", b"\x89PNG test").await.unwrap();
+    let uploads = || app.max.calls().iter().filter(|c| c.path == "/uploads").count();
+
+    app.send_text(CHAT_ID, USER_ID, &format!("/work {}", work.id)).await;
+    let messages = app.max.messages();
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].text.ends_with("📎 <b>Файл работы</b> — во вложении"));
+    let types: Vec<_> = messages[0].body["attachments"].as_array().unwrap().iter().map(|a| a["type"].clone()).collect();
+    assert_eq!(types, [json!("image"), json!("file")]);
+    assert_eq!(messages[0].attachment_tokens(), ["token-1", "token-2"]);
+    assert_eq!(uploads(), 2);
+
+    // Повторная карточка берёт оба токена из кэша
+    app.send_text(CHAT_ID, USER_ID, &format!("/work {}", work.id)).await;
+    assert_eq!(app.max.messages()[1].attachment_tokens(), ["token-1", "token-2"]);
+    assert_eq!(uploads(), 2);
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn stale_upload_tokens_are_replaced() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let work = create_work(&app, "Нейросети в медицине", WorkStatus::Published).await;
+    app.state.storage.write(&work.file_path, b"%PDF-1.4 test").await.unwrap();
+    let download = |id: &str| message_callback(CHAT_ID, USER_ID, id, &format!("download:{}", work.id));
+
+    // Повторное скачивание берёт токен из кэша
+    app.send_update(download("cb-1")).await;
+    app.send_update(download("cb-2")).await;
+    let messages = app.max.messages();
+    assert_eq!(messages.len(), 2);
+    assert!(messages.iter().all(|m| m.attachment_tokens() == ["token-1"]));
+
+    // Платформа отвергла токен — запись кэша удаляется, файл загружается заново
+    app.max.revoke_token("token-1");
+    app.send_update(download("cb-3")).await;
+    let messages = app.max.messages();
+    assert_eq!(messages.len(), 3);
+    assert_eq!(messages[2].attachment_tokens(), ["token-2"]);
+    let cached: Vec<String> = sqlx::query_scalar("SELECT token FROM max_upload_cache").fetch_all(&app.pool).await.unwrap();
+    assert_eq!(cached, ["token-2"]);
+
+    // Устаревшая запись кэша не используется
+    sqlx::query("UPDATE max_upload_cache SET created_at = NOW() - INTERVAL '31 days'").execute(&app.pool).await.unwrap();
+    app.send_update(download("cb-4")).await;
+    assert_eq!(app.max.messages()[3].attachment_tokens(), ["token-3"]);
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn unpublished_works_are_visible_only_to_submitter_and_reviewers() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let draft = create_work(&app, "Черновик", WorkStatus::Submitted).await;
+    sqlx::query("UPDATE works SET submitter_max_user_id = $1 WHERE id = $2")
+        .bind(USER_ID)
+        .bind(draft.id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    const STRANGER_ID: i64 = 2002;
+    const REVIEWER_ID: i64 = 2003;
+    sqlx::query("INSERT INTO users (username, password_hash, role, max_user_id) VALUES ('teacher', '-', 'teacher', $1)")
+        .bind(REVIEWER_ID)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    // Чужой пользователь не отличает работу на проверке от несуществующей
+    app.send_text(CHAT_ID, STRANGER_ID, &format!("/work {}", draft.id)).await;
+    let update = message_callback(CHAT_ID, STRANGER_ID, "cb-1", &format!("download:{}", draft.id));
+    app.send_update(update).await;
+    let update = message_callback(CHAT_ID, STRANGER_ID, "cb-2", &format!("work:{}", draft.id));
+    app.send_update(update).await;
+    let messages = app.max.messages();
+    assert_eq!(messages.len(), 2);
+    assert!(messages.iter().all(|m| m.text.contains("не найдена") && !m.text.contains("Черновик")));
+    assert_eq!(app.max.callback_answers()[0].1, json!({ "notification": "❌ Работа не найдена" }));
+
+    // Приславший работу и проверяющий видят карточку
+    app.send_text(CHAT_ID, USER_ID, &format!("/work {}", draft.id)).await;
+    app.send_text(CHAT_ID, REVIEWER_ID, &format!("/work {}", draft.id)).await;
+    let messages = app.max.messages();
+    assert_eq!(messages.len(), 4);
+    assert!(messages[2..].iter().all(|m| m.text.starts_with("📄 <b>Черновик</b>")));
+
+    // REST API: без входа — 404, проверяющему — карточка
+    let http = reqwest::Client::new();
+    let url = format!("{}/api/works/{}", app.url, draft.id);
+    assert_eq!(http.get(&url).send().await.unwrap().status(), StatusCode::NOT_FOUND);
+    let student = app.login_as(UserRole::Student).await;
+    assert_eq!(http.get(&url).bearer_auth(student).send().await.unwrap().status(), StatusCode::NOT_FOUND);
+    let admin = app.login_as(UserRole::Admin).await;
+    let response = http.get(&url).bearer_auth(admin).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["title"], "Черновик");
+    // Идентификатор пользователя МАКС не раскрывается
+    assert!(body.get("submitter_max_user_id").is_none());
+
+    // Мини-приложение без привязанного аккаунта: автор видит свою заявку, остальные — нет
+    let session = app.login_max(USER_ID).await;
+    assert!(session["user"].is_null());
+    let token = session["token"].as_str().unwrap();
+    assert_eq!(http.get(&url).bearer_auth(token).send().await.unwrap().status(), StatusCode::OK);
+    let stranger = app.login_max(STRANGER_ID).await;
+    let token = stranger["token"].as_str().unwrap();
+    assert_eq!(http.get(&url).bearer_auth(token).send().await.unwrap().status(), StatusCode::NOT_FOUND);
+    // Эндпоинтам для пользователей архива такой сессии мало
+    let me = http.get(format!("{}/api/auth/me", app.url)).bearer_auth(token).send().await.unwrap();
+    assert_eq!(me.status(), StatusCode::UNAUTHORIZED);
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn browse_dialog_narrows_results_and_expires() {
+    let Some(app) = TestApp::spawn().await else { return };
+    create_work(&app, "Нейросети в медицине", WorkStatus::Published).await;
+
+    for text in ["/browse", "09.02.07 Информационные системы и программирование", "2025", "любой"] {
+        app.send_text(CHAT_ID, USER_ID, text).await;
+    }
+    let messages = app.max.messages();
+    assert_eq!(messages.len(), 4);
+    assert!(messages[0].text.starts_with("🎓 <b>Шаг 1 из 3.</b>"));
+    assert!(messages[1].text.starts_with("📅 <b>Шаг 2 из 3.</b>"));
+    assert!(messages[2].text.starts_with("📌 <b>Шаг 3 из 3.</b>"));
+    assert!(messages[3].text.contains("1. <b>Нейросети в медицине</b>"), "{}", messages[3].text);
+    // Диалог завершён — обычный текст снова считается командой
+    let open: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM bot_conversations").fetch_one(&app.pool).await.unwrap();
+    assert_eq!(open, 0);
+
+    // Просроченный диалог не продолжается
+    app.send_text(CHAT_ID, USER_ID, "/browse").await;
+    sqlx::query("UPDATE bot_conversations SET expires_at = NOW() - INTERVAL '1 minute'").execute(&app.pool).await.unwrap();
+    app.send_text(CHAT_ID, USER_ID, "09.02.07").await;
+    assert!(app.max.messages()[5].text.starts_with("⌛ Время ожидания ответа истекло"));
+
+    app.send_text(CHAT_ID, USER_ID, "/browse").await;
+    app.send_text(CHAT_ID, USER_ID, "/cancel").await;
+    app.send_text(CHAT_ID, USER_ID, "/cancel").await;
+    let messages = app.max.messages();
+    assert_eq!(messages[7].text, "✖️ Диалог отменён.");
+    assert_eq!(messages[8].text, "ℹ️ Нет активного диалога.");
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn submit_dialog_creates_work_for_review() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let file_url = app.max.add_file("report.pdf", b"%PDF-1.4 test");
+
+    for text in ["/submit", "Анализ данных", "essay", "09.02.07", "2025", "Иванова М.П."] {
+        assert_eq!(app.send_text(CHAT_ID, USER_ID, text).await, StatusCode::OK);
+    }
+    let file = json!({
+        "mid": "mid.file",
+        "seq": 2,
+        "attachments": [{ "type": "file", "filename": "report.pdf", "size": 13, "payload": { "url": file_url } }],
+    });
+    app.send_update(message_created(CHAT_ID, USER_ID, file)).await;
+    // Двойное нажатие кнопки: работа создаётся один раз
+    tokio::join!(
+        app.send_update(message_callback(CHAT_ID, USER_ID, "cb-submit", "dlg:submit")),
+        app.send_update(message_callback(CHAT_ID, USER_ID, "cb-submit-2", "dlg:submit")),
+    );
+
+    let messages = app.max.messages();
+    assert!(messages[0].text.contains("Шаг 1 из 6"));
+    assert!(messages.iter().any(|m| m.text.starts_with("📋 <b>Проверьте данные работы:</b>")));
+
+    // Последний шаг отвечает через ответ на callback
+    let answers = app.max.callback_answers();
+    let texts: Vec<&str> = answers.iter().filter_map(|(_, a)| a["message"]["text"].as_str()).collect();
+    let final_text = texts.iter().find(|t| t.starts_with("✅ Работа сохранена!")).unwrap();
+    assert!(final_text.ends_with("На проверке"));
+    assert!(texts.iter().any(|t| t.starts_with("ℹ️ Диалог уже завершён")), "{:?}", texts);
+
+    let works = WorkService::new(app.pool.clone()).list_by_submitter(USER_ID, 10).await.unwrap();
+    assert_eq!(works.len(), 1);
+    assert_eq!(works[0].title, "Анализ данных");
+    assert_eq!(works[0].work_type, WorkType::Essay);
+    assert_eq!(works[0].status, WorkStatus::Submitted);
+    assert_eq!(app.state.storage.read(&works[0].file_path).await.unwrap(), b"%PDF-1.4 test");
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn submit_dialog_rejects_invalid_answers_and_saves_drafts() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let pdf_url = app.max.add_file("report.pdf", b"%PDF-1.4 test");
+    let exe_url = app.max.add_file("virus.exe", b"MZ");
+    let file = |name: &str, url: &str| {
+        let body = json!({
+            "mid": "mid.file",
+            "seq": 2,
+            "attachments": [{ "type": "file", "filename": name, "size": 13, "payload": { "url": url } }],
+        });
+        message_created(CHAT_ID, USER_ID, body)
+    };
+
+    for text in ["/submit", "   ", "Анализ данных", "стихотворение", "essay", "09.02.07", "1800", "2025", "Иванова М.П."] {
+        app.send_text(CHAT_ID, USER_ID, text).await;
+    }
+    // Текст вместо файла — повтор вопроса; файл недопустимого формата — ошибка
+    app.send_text(CHAT_ID, USER_ID, "вот файл").await;
+    app.send_update(file("virus.exe", &exe_url)).await;
+    app.send_update(file("report.pdf", &pdf_url)).await;
+    app.send_text(CHAT_ID, USER_ID, "черновик").await;
+
+    let texts: Vec<String> = app.max.messages().into_iter().map(|m| m.text).collect();
+    assert_eq!(texts[1], "⚠️ Значение не может быть пустым. Попробуйте ещё раз или нажмите «Отмена».");
+    assert!(texts[3].starts_with("📌 <b>Шаг 2 из 6.</b>"), "{}", texts[3]);
+    assert_eq!(texts[6], "⚠️ Год должен быть числом в диапазоне 1900-2100. Попробуйте ещё раз или нажмите «Отмена».");
+    assert_eq!(texts[9], texts[8]);
+    assert!(texts[10].starts_with("⚠️ Недопустимый формат файла."), "{}", texts[10]);
+    assert!(texts[11].starts_with("📋 <b>Проверьте данные работы:</b>"), "{}", texts[11]);
+    assert!(texts[12].starts_with("✅ Работа сохранена!"), "{}", texts[12]);
+
+    let works = WorkService::new(app.pool.clone()).list_by_submitter(USER_ID, 10).await.unwrap();
+    assert_eq!(works.len(), 1);
+    assert_eq!(works[0].status, WorkStatus::Draft);
+    assert_eq!(works[0].year, 2025);
+    assert_eq!(works[0].author_name, "Иван Петров");
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn submitted_file_is_downloaded_at_once_and_capped() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let file_url = app.max.add_file("report.pdf", b"%PDF-1.4 test");
+
+    for text in ["/submit", "Анализ данных", "essay", "09.02.07", "2025", "Иванова М.П."] {
+        app.send_text(CHAT_ID, USER_ID, text).await;
+    }
+    let file = |size: Option<u64>| {
+        let mut attachment = json!({ "type": "file", "filename": "report.pdf", "payload": { "url": file_url } });
+        if let Some(size) = size {
+            attachment["size"] = json!(size);
+        }
+        json!({ "mid": "mid.file", "seq": 2, "attachments": [attachment] })
+    };
+
+    // Без размера файл не принимается
+    app.send_update(message_created(CHAT_ID, USER_ID, file(None))).await;
+    let messages = app.max.messages();
+    assert!(messages.last().unwrap().text.contains("Не удалось определить размер файла"));
+
+    // Файл скачивается на шаге выбора: к подтверждению ссылка может уже истечь
+    app.send_update(message_created(CHAT_ID, USER_ID, file(Some(13)))).await;
+    app.max.remove_file("report.pdf");
+    app.send_update(message_callback(CHAT_ID, USER_ID, "cb-submit", "dlg:submit")).await;
+
+    let works = WorkService::new(app.pool.clone()).list_by_submitter(USER_ID, 10).await.unwrap();
+    assert_eq!(works.len(), 1);
+    assert_eq!(app.state.storage.read(&works[0].file_path).await.unwrap(), b"%PDF-1.4 test");
+
+    // Скачивание прерывается на лимите, а не после чтения всего ответа
+    let url = app.max.add_file("big.pdf", &[0; 4096]);
+    assert!(matches!(app.state.max_api.download(&url, 1024).await, Err(MaxApiError::TooLarge { limit: 1024 })));
+    assert_eq!(app.state.max_api.download(&url, 4096).await.unwrap().len(), 4096);
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn cancelled_submission_removes_downloaded_file() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let file_url = app.max.add_file("report.pdf", b"%PDF-1.4 test");
+
+    for text in ["/submit", "Анализ данных", "essay", "09.02.07", "2025", "Иванова М.П."] {
+        app.send_text(CHAT_ID, USER_ID, text).await;
+    }
+    let file = json!({
+        "mid": "mid.file",
+        "seq": 2,
+        "attachments": [{ "type": "file", "filename": "report.pdf", "size": 13, "payload": { "url": file_url } }],
+    });
+    app.send_update(message_created(CHAT_ID, USER_ID, file)).await;
+
+    let state: serde_json::Value = sqlx::query_scalar("SELECT state FROM bot_conversations WHERE user_id = $1")
+        .bind(USER_ID)
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    let file_path = state["draft"]["file_path"].as_str().unwrap().to_string();
+    assert!(app.state.storage.read(&file_path).await.is_ok());
+
+    app.send_text(CHAT_ID, USER_ID, "/cancel").await;
+    assert!(app.state.storage.read(&file_path).await.is_err());
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn subscribers_get_digest_and_blocked_chats_are_dropped() {
+    let Some(app) = TestApp::spawn().await else { return };
+    const BLOCKED_CHAT_ID: i64 = 1002;
+
+    app.send_text(CHAT_ID, USER_ID, "/subscribe 09.02.07").await;
+    app.send_text(BLOCKED_CHAT_ID, USER_ID, "/subscribe руководитель Иванова").await;
+    assert_eq!(
+        app.max.messages()[0].text,
+        "✅ Подписка оформлена: 🎓 Специальность «09.02.07»\n\n\
+        Когда появятся новые работы, я пришлю их в этот чат.\nВсе подписки: /subscriptions"
+    );
+
+    app.max.clear();
+    app.max.fail_chat(
+        BLOCKED_CHAT_ID,
+        axum::http::StatusCode::FORBIDDEN,
+        json!({ "code": "chat.denied", "message": "chat.denied" }),
+    );
+    create_work(&app, "Нейросети в медицине", WorkStatus::Published).await;
+    create_work(&app, "Черновик", WorkStatus::Draft).await;
+
+    assert_eq!(notifier::notify_pending(&app.state).await.unwrap(), 1);
+
+    let messages = app.max.messages();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].chat_id, Some(CHAT_ID));
+    assert!(messages[0].text.starts_with("🔔 <b>Новые работы по вашим подпискам:</b>"));
+    assert!(messages[0].text.contains("<b>Нейросети в медицине</b>"));
+    assert!(!messages[0].text.contains("Черновик"));
+
+    let subscriptions = SubscriptionService::new(app.pool.clone());
+    assert_eq!(subscriptions.list(CHAT_ID).await.unwrap().len(), 1);
+    assert!(subscriptions.list(BLOCKED_CHAT_ID).await.unwrap().is_empty());
+
+    // Повторный проход ничего не рассылает
+    assert_eq!(notifier::notify_pending(&app.state).await.unwrap(), 0);
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn subscriptions_are_listed_matched_by_tag_and_removed() {
+    let Some(app) = TestApp::spawn().await else { return };
+
+    app.send_text(CHAT_ID, USER_ID, "/subscribe #Python").await;
+    app.send_text(CHAT_ID, USER_ID, "/subscribe тег python").await;
+    app.send_text(CHAT_ID, USER_ID, "/subscribe руководитель Петров").await;
+    app.send_text(CHAT_ID, USER_ID, "/subscribe").await;
+    let messages = app.max.messages();
+    assert!(messages[0].text.starts_with("✅ Подписка оформлена: 🔑 Ключевое слово «python»"));
+    assert_eq!(messages[1].text, "ℹ️ Такая подписка уже есть. Все подписки: /subscriptions");
+    assert!(messages[2].text.starts_with("✅ Подписка оформлена: 👨‍🏫 Руководитель «петров»"));
+    assert!(messages[3].text.starts_with("🔔 <b>Подписка на новые работы</b>"));
+
+    app.send_text(CHAT_ID, USER_ID, "/subscriptions").await;
+    let list = app.max.messages().pop().unwrap();
+    assert_eq!(
+        list.text,
+        "🔔 <b>Подписки чата:</b>\n\n1. 🔑 Ключевое слово «python»\n2. 👨‍🏫 Руководитель «петров»\n\n\
+        Отписаться от всех: /unsubscribe все"
+    );
+    let buttons = list.callback_payloads();
+    assert_eq!(buttons.len(), 2);
+    assert!(buttons.iter().all(|p| p.starts_with("unsub:")));
+
+    // Ключевое слово совпадает с тегом работы без учёта регистра, руководитель — нет
+    app.max.clear();
+    create_work(&app, "Нейросети в медицине", WorkStatus::Published).await;
+    assert_eq!(notifier::notify_pending(&app.state).await.unwrap(), 1);
+    assert!(app.max.messages()[0].text.contains("<b>Нейросети в медицине</b>"));
+
+    // Кнопка отписки заменяет список ответом, повторное нажатие ничего не ломает
+    app.max.clear();
+    app.send_update(message_callback(CHAT_ID, USER_ID, "cb-1", &buttons[0])).await;
+    app.send_update(message_callback(CHAT_ID, USER_ID, "cb-2", &buttons[0])).await;
+    let answers = app.max.callback_answers();
+    assert_eq!(answers[0].1["message"]["text"], "✅ Подписка удалена: 🔑 Ключевое слово «python»");
+    assert_eq!(answers[1].1["message"]["text"], "ℹ️ Подписка уже удалена.");
+
+    app.send_text(CHAT_ID, USER_ID, "/unsubscribe python").await;
+    app.send_text(CHAT_ID, USER_ID, "/unsubscribe все").await;
+    app.send_text(CHAT_ID, USER_ID, "/unsubscribe все").await;
+    let messages = app.max.messages();
+    assert_eq!(messages[0].text, "❌ Подписка не найдена. Список подписок: /subscriptions");
+    assert_eq!(messages[1].text, "✅ Удалено подписок: 1");
+    assert_eq!(messages[2].text, "ℹ️ Подписок нет.");
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn startup_sync_publishes_commands_and_reconciles_webhook() {
+    let Some(app) = TestApp::spawn().await else { return };
+    const URL: &str = "https://archive.example/api/max/webhook";
+    let update_types = vec!["message_created".to_string(), "bot_started".to_string()];
+    app.max.add_subscription("https://old.example/webhook", &["message_created"]);
+    app.max.add_subscription(URL, &["message_created"]);
+
+    assert!(setup::sync_commands(&app.state.max_api).await.unwrap());
+    assert!(!setup::sync_commands(&app.state.max_api).await.unwrap());
+    let commands = registry::menu_commands();
+    let search = commands.iter().find(|c| c.name == "search").unwrap();
+    assert_eq!(
+        search.description.as_deref(),
+        Some("Поиск по названию, автору, ключевым словам / Search by title, author or keywords")
+    );
+    assert!(!commands.iter().any(|c| c.name == "approve"));
+
+    assert!(setup::sync_webhook(&app.state.max_api, URL, &update_types, WEBHOOK_SECRET).await.unwrap());
+    assert_eq!(
+        app.max.subscriptions(),
+        vec![json!({ "url": URL, "time": 0, "update_types": ["message_created", "bot_started"] })]
+    );
+    let subscribe = app.max.calls().into_iter().rfind(|c| c.path == "/subscriptions").unwrap();
+    assert_eq!(subscribe.body["secret"], WEBHOOK_SECRET);
+
+    // Порядок типов не важен — повторная сверка ничего не удаляет, но обновляет секрет
+    let reordered = vec!["bot_started".to_string(), "message_created".to_string()];
+    app.max.clear();
+    assert!(!setup::sync_webhook(&app.state.max_api, URL, &reordered, "rotated-secret").await.unwrap());
+    let calls: Vec<_> = app.max.calls().into_iter().map(|c| (c.method.to_string(), c.body["secret"].clone())).collect();
+    assert_eq!(calls, [("GET".to_string(), json!(null)), ("POST".to_string(), json!("rotated-secret"))]);
+    assert_eq!(app.max.subscriptions().len(), 1);
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn commands_are_parsed_with_aliases_and_arguments() {
+    let (command, args) = registry::parse("/ПОИСК  нейросети в медицине").unwrap();
+    assert_eq!((command.name, args), ("search", "нейросети в медицине"));
+    assert_eq!(registry::parse("помощь").unwrap().0.name, "help");
+    assert_eq!(registry::parse("/work@archive_bot 42").map(|(c, a)| (c.name, a)), Some(("work", "42")));
+    assert!(registry::parse("/searching").is_none());
+    assert!(registry::parse("просто текст").is_none());
+}
+
+#[tokio::test]
+async fn work_card_escapes_user_content() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let work = create_work(&app, "C++ & <Rust>", WorkStatus::Published).await;
+
+    app.send_text(CHAT_ID, USER_ID, &format!("/work {}", work.id)).await;
+
+    let messages = app.max.messages();
+    assert!(messages[0].text.starts_with("📄 <b>C++ &amp; &lt;Rust&gt;</b>"), "{}", messages[0].text);
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn internal_errors_are_not_shown_to_users() {
+    let Some(app) = TestApp::spawn().await else { return };
+
+    // Текст ошибки Postgres не должен попасть в чат — только общий текст
+    sqlx::query("ALTER TABLE works RENAME TO works_unavailable").execute(&app.pool).await.unwrap();
+    app.send_text(CHAT_ID, USER_ID, "/search <b>нейросети").await;
+
+    let messages = app.max.messages();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].text, "❌ Ошибка при поиске: Ошибка базы данных");
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn replies_in_user_locale() {
+    let Some(app) = TestApp::spawn().await else { return };
+
+    let text = json!({ "mid": "mid.in", "seq": 1, "text": "/unknown" });
+    app.send_update(with_locale(message_created(CHAT_ID, USER_ID, text), "en-US")).await;
+    app.send_update(with_locale(bot_started(CHAT_ID, USER_ID), "en")).await;
+    app.send_update(with_locale(bot_started(CHAT_ID, USER_ID), "de")).await;
+
+    let messages = app.max.messages();
+    assert_eq!(messages[0].text, "❌ Unknown command. Type /help for the list of commands.");
+    assert!(messages[1].text.starts_with("👋 Welcome to the Digital Archive"));
+    // Неподдерживаемый язык — русский
+    assert!(messages[2].text.starts_with("👋 Добро пожаловать в Цифровой архив"));
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn digest_uses_subscription_locale() {
+    let Some(app) = TestApp::spawn().await else { return };
+
+    let text = json!({ "mid": "mid.in", "seq": 1, "text": "/subscribe specialty 09.02.07" });
+    app.send_update(with_locale(message_created(CHAT_ID, USER_ID, text), "en")).await;
+    assert!(app.max.messages()[0].text.starts_with("✅ Subscribed: 🎓 Specialty «09.02.07»"));
+
+    app.max.clear();
+    create_work(&app, "Нейросети в медицине", WorkStatus::Published).await;
+    assert_eq!(notifier::notify_pending(&app.state).await.unwrap(), 1);
+
+    let messages = app.max.messages();
+    assert!(messages[0].text.starts_with("🔔 <b>New works matching your subscriptions:</b>"));
+    assert!(messages[0].text.contains("Project"), "{}", messages[0].text);
+
+    app.cleanup().await;
+}
+
+/// Сообщение с текстом в групповой чат
+fn group_text(text: &str) -> serde_json::Value {
+    group_message_created(GROUP_CHAT_ID, USER_ID, json!({ "mid": "mid.in", "seq": 1, "text": text }))
+}
+
+#[tokio::test]
+async fn group_chat_answers_only_addressed_messages() {
+    let Some(app) = TestApp::spawn().await else { return };
+    create_work(&app, "Нейросети в медицине", WorkStatus::Published).await;
+
+    for text in ["привет всем", "/help", "/search@other_bot нейросети"] {
+        assert_eq!(app.send_update(group_text(text)).await, StatusCode::OK);
+    }
+    assert!(app.max.messages().is_empty());
+
+    app.send_update(group_text("/search@Archive_Bot нейросети")).await;
+    // Упоминание с текстом — поиск по оставшемуся тексту
+    app.send_update(group_text("@archive_bot, нейросети")).await;
+    // Упоминание из разметки без текста — справка
+    app.send_update(group_message_created(
+        GROUP_CHAT_ID,
+        USER_ID,
+        json!({
+            "mid": "mid.in",
+            "seq": 1,
+            "text": "Архив ЮТК",
+            "markup": [{ "type": "user_mention", "from": 0, "length": 9, "user_id": BOT_USER_ID }],
+        }),
+    ))
+    .await;
+
+    let messages = app.max.messages();
+    assert_eq!(messages.len(), 3);
+    assert!(messages.iter().all(|m| m.chat_id == Some(GROUP_CHAT_ID)));
+    assert!(messages[0].text.contains("<b>Нейросети в медицине</b>"));
+    assert!(messages[1].text.contains("<b>Нейросети в медицине</b>"));
+    assert!(messages[2].text.starts_with("📖 Справка по командам:"));
+
+    // Открытый диалог продолжают только обращённые к боту сообщения, остальная переписка не перехватывается
+    app.send_update(group_text("/submit@archive_bot")).await;
+    app.send_update(group_text("привет всем")).await;
+    let messages = app.max.messages();
+    assert_eq!(messages.len(), 4);
+    let state: serde_json::Value = sqlx::query_scalar("SELECT state FROM bot_conversations").fetch_one(&app.pool).await.unwrap();
+    assert!(!state.to_string().contains("привет всем"), "{}", state);
+
+    app.send_update(group_text("@archive_bot Нейросети в биологии")).await;
+    let messages = app.max.messages();
+    assert_eq!(messages.len(), 5);
+    assert!(messages[4].text.starts_with("📌 <b>Шаг 2 из 6.</b>"), "{}", messages[4].text);
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn accounts_are_linked_by_web_and_bot_codes() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let http = reqwest::Client::new();
+    let linked = |max_user_id: i64| {
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM users WHERE max_user_id = $1")
+            .bind(max_user_id)
+            .fetch_one(&app.pool)
+    };
+
+    // Код из веб-приложения вводится в боте
+    let token = app.login_as(UserRole::Student).await;
+    let response = http
+        .post(format!("{}/api/account/max/link-code", app.url))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let code: serde_json::Value = response.json().await.unwrap();
+    let code = code["code"].as_str().unwrap();
+
+    app.send_text(CHAT_ID, USER_ID, &format!("/link {}", code)).await;
+    assert!(app.max.messages()[0].text.starts_with("✅ Аккаунт МАКС привязан к пользователю"));
+    assert_eq!(linked(USER_ID).await.unwrap(), 1);
+
+    // Код одноразовый
+    app.send_text(CHAT_ID, USER_ID, &format!("/link {}", code)).await;
+    assert_eq!(app.max.messages()[1].text, "❌ Код неверный или истёк. Получите новый код в веб-приложении.");
+
+    app.send_text(CHAT_ID, USER_ID, "/unlink").await;
+    app.send_text(CHAT_ID, USER_ID, "/unlink").await;
+    let messages = app.max.messages();
+    assert_eq!(messages[2].text, "✖️ Аккаунт МАКС отвязан от архива.");
+    assert_eq!(messages[3].text, "ℹ️ Аккаунт МАКС не привязан.");
+    assert_eq!(linked(USER_ID).await.unwrap(), 0);
+
+    // Код от бота вводится в веб-приложении
+    const OTHER_USER_ID: i64 = 2002;
+    app.send_text(CHAT_ID, OTHER_USER_ID, "/link").await;
+    let text = app.max.messages()[4].text.clone();
+    let code = text.split("<b>").nth(1).and_then(|s| s.split("</b>").next()).unwrap();
+
+    let url = format!("{}/api/account/max/link", app.url);
+    let response = http.post(&url).bearer_auth(&token).json(&json!({ "code": "000000" })).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = http.post(&url).bearer_auth(&token).json(&json!({ "code": code })).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let user: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(user["max_user_id"], OTHER_USER_ID);
+
+    let response = http.delete(&url).bearer_auth(&token).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(linked(OTHER_USER_ID).await.unwrap(), 0);
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn account_linking_is_refused_in_groups() {
+    let Some(app) = TestApp::spawn().await else { return };
+
+    for text in ["/link@archive_bot", "/link@archive_bot 123456", "/unlink@archive_bot"] {
+        app.send_update(group_text(text)).await;
+    }
+
+    let messages = app.max.messages();
+    assert_eq!(messages.len(), 3);
+    assert!(messages.iter().all(|m| m.text.starts_with("🔒 Привязка аккаунта доступна только в личном чате")));
+    let codes: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM max_link_codes").fetch_one(&app.pool).await.unwrap();
+    assert_eq!(codes, 0);
+
+    // В личном диалоге код выдаётся
+    app.send_text(CHAT_ID, USER_ID, "/link").await;
+    assert!(app.max.messages().last().unwrap().text.starts_with("🔗 Ваш код привязки"));
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn group_admins_set_default_specialty() {
+    let Some(app) = TestApp::spawn().await else { return };
+    const ADMIN_ID: i64 = 3001;
+    app.max.add_chat_admin(GROUP_CHAT_ID, ADMIN_ID);
+
+    create_work(&app, "Нейросети в медицине", WorkStatus::Published).await;
+    WorkService::new(app.pool.clone())
+        .create(WorkCreateDto {
+            title: "Нейросети в юриспруденции".to_string(),
+            work_type: WorkType::Article,
+            specialty: "40.02.04 Юриспруденция".to_string(),
+            author_name: "Смирнов Олег".to_string(),
+            supervisor_name: "Петрова Анна Ивановна".to_string(),
+            year: 2024,
+            annotation: None,
+            keywords: Some("нейросети".to_string()),
+            file_path: "works/2024/missing.pdf".to_string(),
+            thumbnail_path: None,
+            status: WorkStatus::Published,
+            submitter_max_user_id: None,
+        })
+        .await
+        .unwrap();
+
+    let filter = |user_id: i64, text: &str| {
+        group_message_created(GROUP_CHAT_ID, user_id, json!({ "mid": "mid.in", "seq": 1, "text": text }))
+    };
+
+    app.send_update(filter(USER_ID, "/filter@archive_bot 09.02.07")).await;
+    app.send_update(filter(ADMIN_ID, "/filter@archive_bot 09.02.07")).await;
+    app.send_update(filter(USER_ID, "/filter@archive_bot")).await;
+    app.send_update(group_text("/search@archive_bot нейросети")).await;
+    app.send_text(CHAT_ID, USER_ID, "/filter 09.02.07").await;
+    app.send_text(CHAT_ID, USER_ID, "/search нейросети").await;
+
+    let messages = app.max.messages();
+    assert_eq!(messages[0].text, "⛔ Менять настройки чата могут только его администраторы.");
+    assert_eq!(messages[1].text, "✅ Теперь /search и /browse в этом чате ищут по специальности «09.02.07».");
+    assert!(messages[2].text.starts_with("🎓 Поиск в этом чате ограничен специальностью «09.02.07»."));
+
+    assert!(messages[3].text.contains("специальность 09.02.07"), "{}", messages[3].text);
+    assert!(messages[3].text.contains("Нейросети в медицине"));
+    assert!(!messages[3].text.contains("Нейросети в юриспруденции"));
+
+    // В личном диалоге специальность чата не задаётся и не применяется
+    assert_eq!(messages[4].text, "ℹ️ Специальность по умолчанию задаётся только в групповых чатах.");
+    assert!(messages[5].text.contains("Нейросети в юриспруденции"));
+
+    app.max.clear();
+    app.send_update(filter(ADMIN_ID, "/filter@archive_bot off")).await;
+    app.send_update(group_text("/search@archive_bot нейросети")).await;
+    let messages = app.max.messages();
+    assert_eq!(messages[0].text, "✖️ Специальность по умолчанию сброшена.");
+    assert!(messages[1].text.contains("Нейросети в юриспруденции"));
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn analytics_report_counts_queries_and_funnel() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let work = create_work(&app, "Нейросети в медицине", WorkStatus::Published).await;
+
+    app.send_text(CHAT_ID, USER_ID, "/search  Нейросети").await;
+    app.send_text(CHAT_ID, USER_ID, "/search квантовые компьютеры").await;
+    app.send_text(CHAT_ID + 1, USER_ID + 1, "/search нейросети").await;
+    app.send_update(message_callback(CHAT_ID, USER_ID, "cb.1", &format!("work:{}", work.id))).await;
+    app.send_text(CHAT_ID, USER_ID, "как дела").await;
+    // Болтовня в группе не записывается
+    app.send_update(group_text("привет всем")).await;
+
+    let (count, hashes): (i64, i64) =
+        sqlx::query_as("SELECT COUNT(*), COUNT(DISTINCT user_hash) FROM bot_events").fetch_one(&app.pool).await.unwrap();
+    assert_eq!((count, hashes), (5, 2));
+    let leaked: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM bot_events WHERE user_hash LIKE $1")
+        .bind(format!("%{}%", USER_ID))
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(leaked, 0);
+    // Обезличивание — на отдельном ключе, а не на ключе подписи сессий
+    let salted: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM bot_events WHERE user_hash = $1")
+        .bind(analytics::anonymize(USER_ID, app.state.analytics_salt.expose()))
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(salted, 4);
+
+    let http = reqwest::Client::new();
+    let url = format!("{}/api/admin/analytics/bot?days=7", app.url);
+    let student = app.login_as(UserRole::Student).await;
+    assert_eq!(http.get(&url).bearer_auth(student).send().await.unwrap().status(), StatusCode::FORBIDDEN);
+
+    let admin = app.login_as(UserRole::Admin).await;
+    let report: serde_json::Value = http.get(&url).bearer_auth(admin).send().await.unwrap().json().await.unwrap();
+
+    assert_eq!(report["daily_active_users"][0]["users"], 2);
+    assert_eq!(report["top_queries"][0], json!({ "query": "нейросети", "count": 2, "users": 2 }));
+    assert_eq!(report["zero_result_queries"], json!([{ "query": "квантовые компьютеры", "count": 1, "users": 1 }]));
+    assert_eq!(report["commands"][0]["command"], "search");
+    assert_eq!(report["commands"][0]["count"], 3);
+    assert_eq!(
+        report["funnel"],
+        json!([
+            { "step": "search", "users": 2, "conversion": null },
+            { "step": "work", "users": 1, "conversion": 0.5 },
+            { "step": "download", "users": 0, "conversion": 0.0 },
+        ])
+    );
+
+    let invalid = http
+        .get(format!("{}/api/admin/analytics/bot?days=0", app.url))
+        .bearer_auth(app.login_as(UserRole::Admin).await)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = invalid.json().await.unwrap();
+    assert_eq!(body["error"]["code"], "validation_failed");
+    assert_eq!(body["error"]["details"][0]["field"], "days");
+
+    let invalid = http
+        .get(format!("{}/api/admin/analytics/bot?days=400", app.url))
+        .bearer_auth(app.login_as(UserRole::Admin).await)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = invalid.json().await.unwrap();
+    assert_eq!(body["error"]["details"][0]["message"], "Значение должно быть от 1 до 365");
+
+    app.cleanup().await;
+}
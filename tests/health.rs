@@ -0,0 +1,117 @@
+//! Проверки `/health/live` и `/health/ready`.
+
+mod support;
+
+use std::sync::Arc;
+
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use max_app::{
+    api::routes::create_router,
+    infrastructure::storage::FileStorage,
+    integrations::max::{MaxApiClient, MaxApiSettings},
+    state::AppState,
+};
+use support::{TestApp, BOT_TOKEN};
+
+async fn get(url: String) -> (StatusCode, Value) {
+    let response = reqwest::get(url).await.unwrap();
+    (response.status(), response.json().await.unwrap())
+}
+
+async fn serve(state: AppState) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let app = create_router(Arc::new(state));
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    url
+}
+
+#[tokio::test]
+async fn ready_when_dependencies_are_up() {
+    let Some(app) = TestApp::spawn().await else { return };
+
+    let (status, body) = get(format!("{}/health/live", app.url)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ok");
+
+    let (status, body) = get(format!("{}/health/ready", app.url)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ok");
+    for check in ["database", "migrations", "storage"] {
+        assert_eq!(body["checks"][check]["status"], "ok", "{}", body);
+    }
+    assert!(body["checks"].get("max_api").is_none());
+
+    // MAX API проверяется только по настройке
+    let url = serve(AppState { health_check_max_api: true, ..(*app.state).clone() }).await;
+    let (status, body) = get(format!("{}/health/ready", url)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["checks"]["max_api"]["status"], "ok");
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn degraded_when_storage_is_not_writable_or_migrations_pending() {
+    let Some(app) = TestApp::spawn().await else { return };
+
+    // Корень хранилища — обычный файл: каталог не создать
+    let file = std::env::temp_dir().join(format!("max_app_storage_{}", uuid::Uuid::new_v4()));
+    std::fs::write(&file, b"").unwrap();
+    let url = serve(AppState { storage: FileStorage::new(&file), ..(*app.state).clone() }).await;
+
+    let (status, body) = get(format!("{}/health/ready", url)).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["status"], "degraded");
+    assert_eq!(body["checks"]["storage"]["status"], "degraded");
+    // Причина сбоя остаётся в журнале
+    assert!(body["checks"]["storage"].get("error").is_none());
+    assert_eq!(body["checks"]["database"]["status"], "ok");
+    std::fs::remove_file(&file).unwrap();
+
+    sqlx::query("DELETE FROM _sqlx_migrations WHERE version = (SELECT max(version) FROM _sqlx_migrations)")
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    let (status, body) = get(format!("{}/health/ready", app.url)).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["checks"]["migrations"]["status"], "degraded");
+    // Версии миграций наружу не отдаются
+    assert!(!body.to_string().contains("2026"), "{}", body);
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn degraded_when_database_is_down() {
+    let Some(app) = TestApp::spawn().await else { return };
+
+    app.pool.close().await;
+    let (status, body) = get(format!("{}/health", app.url)).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["checks"]["database"]["status"], "degraded");
+    assert_eq!(body["checks"]["storage"]["status"], "ok");
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn max_api_probe_does_not_retry() {
+    let Some(app) = TestApp::spawn().await else { return };
+
+    // Платформа недоступна: с повторами клиента проверка ждала бы несколько секунд
+    let max_api = MaxApiClient::with_settings(
+        BOT_TOKEN.to_string(),
+        MaxApiSettings { api_base_url: "http://127.0.0.1:1".to_string(), max_retries: 5, ..MaxApiSettings::default() },
+    );
+    let url = serve(AppState { health_check_max_api: true, max_api, ..(*app.state).clone() }).await;
+
+    let (status, body) = get(format!("{}/health/ready", url)).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["checks"]["max_api"]["status"], "degraded");
+    assert!(body["checks"]["max_api"]["latency_ms"].as_u64().unwrap() < 500, "{}", body);
+
+    app.cleanup().await;
+}
@@ -0,0 +1,63 @@
+//! Каталог сообщений: полнота переводов и выбор языка.
+
+use max_app::{
+    core::models::WorkType,
+    i18n::{self, Locale},
+    t,
+};
+
+/// Ключи сообщений из исходника каталога `.ftl`
+fn keys(source: &str) -> Vec<&str> {
+    source
+        .lines()
+        .filter(|line| !line.starts_with([' ', '#']) && !line.is_empty())
+        .filter_map(|line| line.split_once(" =").map(|(key, _)| key))
+        .collect()
+}
+
+#[test]
+fn catalogs_have_the_same_keys() {
+    let ru = keys(include_str!("../locales/ru.ftl"));
+    let en = keys(include_str!("../locales/en.ftl"));
+
+    assert!(!ru.is_empty());
+    assert_eq!(ru, en);
+    for key in ru {
+        for locale in Locale::ALL {
+            assert!(i18n::has_message(locale, key), "{} нет в каталоге {}", key, locale.as_str());
+        }
+    }
+}
+
+#[test]
+fn work_types_are_translated() {
+    for locale in Locale::ALL {
+        for work_type in WorkType::ALL {
+            let key = format!("work-type-{}", work_type.as_str());
+            assert!(i18n::has_message(locale, &key), "{} нет в каталоге {}", key, locale.as_str());
+        }
+    }
+}
+
+#[test]
+fn parses_accept_language() {
+    assert_eq!(Locale::from_accept_language("en-US,en;q=0.9"), Locale::En);
+    assert_eq!(Locale::from_accept_language("de-DE, en;q=0.5, ru;q=0.8"), Locale::Ru);
+    assert_eq!(Locale::from_accept_language("ru;q=0, en;q=0.1"), Locale::En);
+    assert_eq!(Locale::from_accept_language("fr, de"), Locale::Ru);
+    assert_eq!(Locale::from_accept_language(""), Locale::Ru);
+
+    assert_eq!(Locale::from_user_locale(Some("en_GB")), Locale::En);
+    assert_eq!(Locale::from_user_locale(None), Locale::Ru);
+}
+
+#[tokio::test]
+async fn message_uses_scope_locale_and_arguments() {
+    assert_eq!(t!("error-not-found"), "Ресурс не найден");
+
+    let text = i18n::scope(Locale::En, async { t!("unsubscribe-all-done", count = 3) }).await;
+    assert_eq!(text, "✅ Subscriptions removed: 3");
+
+    // Неизвестный ключ возвращается как есть
+    assert_eq!(i18n::message(Locale::En, "no-such-key", None), "no-such-key");
+}
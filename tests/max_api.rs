@@ -0,0 +1,122 @@
+//! Методы MAX Bot API: форма запросов, которые клиент отправляет на платформу (мок MAX Bot API).
+
+mod support;
+
+use axum::http::Method;
+use serde_json::json;
+
+use max_app::integrations::max::{
+    models::{ChatAction, SubscribeRequest},
+    Button, CallbackAnswer, InlineKeyboard, MaxApiClient, MaxApiSettings, SendMessageRequest,
+};
+use support::mock_max::{MockMax, BOT_USERNAME, BOT_USER_ID};
+
+const CHAT_ID: i64 = -5001;
+
+/// Клиент мока; завершающий `/` в адресе не должен ломать пути методов
+async fn client() -> (MaxApiClient, MockMax) {
+    let max = MockMax::start().await;
+    let client = MaxApiClient::with_settings(
+        "test-token".to_string(),
+        MaxApiSettings { api_base_url: format!("{}/", max.url()), max_retries: 0, ..MaxApiSettings::default() },
+    );
+    (client, max)
+}
+
+#[tokio::test]
+async fn messages_are_edited_and_deleted_by_id() {
+    let (client, max) = client().await;
+    let keyboard = InlineKeyboard::new().row(vec![Button::callback("Ещё", "page:2")]);
+
+    let request = SendMessageRequest::html("<b>Новый текст</b>").with_keyboard(keyboard);
+    client.edit_message("mid.7", &request).await.unwrap();
+    client.delete_message("mid.7").await.unwrap();
+
+    let calls = max.calls();
+    assert_eq!(calls.len(), 2);
+    assert_eq!((calls[0].method.clone(), calls[0].path.as_str()), (Method::PUT, "/messages"));
+    assert_eq!(calls[0].query["message_id"], "mid.7");
+    assert_eq!(calls[0].body["text"], "<b>Новый текст</b>");
+    assert_eq!(calls[0].body["format"], "html");
+    assert_eq!(calls[0].body["attachments"][0]["type"], "inline_keyboard");
+    assert_eq!((calls[1].method.clone(), calls[1].path.as_str()), (Method::DELETE, "/messages"));
+    assert_eq!(calls[1].query["message_id"], "mid.7");
+}
+
+#[tokio::test]
+async fn callback_answer_is_sent_with_callback_id() {
+    let (client, max) = client().await;
+    let answer = CallbackAnswer { message: None, notification: Some("Готово".to_string()) };
+
+    client.answer_callback("cb-42", &answer).await.unwrap();
+
+    assert_eq!(max.callback_answers(), [("cb-42".to_string(), json!({ "notification": "Готово" }))]);
+}
+
+#[tokio::test]
+async fn chat_action_and_chat_info() {
+    let (client, max) = client().await;
+
+    client.send_action(CHAT_ID, ChatAction::TypingOn).await.unwrap();
+    let chat = client.get_chat(CHAT_ID).await.unwrap();
+
+    assert_eq!(chat.chat_id, CHAT_ID);
+    assert_eq!(chat.chat_type, "chat");
+    assert_eq!(chat.title.as_deref(), Some("Группа ИС-31"));
+    let calls = max.calls();
+    assert_eq!(calls[0].path, format!("/chats/{}/actions", CHAT_ID));
+    assert_eq!(calls[0].body, json!({ "action": "typing_on" }));
+}
+
+#[tokio::test]
+async fn chat_members_are_requested_by_page_and_by_id() {
+    let (client, max) = client().await;
+    max.add_chat_admin(CHAT_ID, 3001);
+
+    client.get_chat_members(CHAT_ID, Some(1700), 50).await.unwrap();
+    let members = client.get_chat_members_by_id(CHAT_ID, &[3001, 3002]).await.unwrap();
+
+    let calls = max.calls();
+    assert_eq!(calls[0].path, format!("/chats/{}/members", CHAT_ID));
+    assert_eq!(calls[0].query["count"], "50");
+    assert_eq!(calls[0].query["marker"], "1700");
+    assert_eq!(calls[1].query["user_ids"], "3001,3002");
+    let admins: Vec<_> = members.members.iter().map(|m| (m.user_id, m.is_admin)).collect();
+    assert_eq!(admins, [(3001, true), (3002, false)]);
+}
+
+#[tokio::test]
+async fn webhook_subscriptions_are_created_listed_and_removed() {
+    let (client, max) = client().await;
+    let url = "https://archive.example.com/api/max/webhook";
+
+    client
+        .subscribe(&SubscribeRequest {
+            url: url.to_string(),
+            update_types: vec!["message_created".to_string()],
+            secret: Some("webhook-secret".to_string()),
+        })
+        .await
+        .unwrap();
+    assert_eq!(max.calls()[0].body["secret"], "webhook-secret");
+
+    let subscriptions = client.get_subscriptions().await.unwrap();
+    assert_eq!(subscriptions.len(), 1);
+    assert_eq!(subscriptions[0].url, url);
+
+    client.unsubscribe(url).await.unwrap();
+    assert_eq!(max.calls()[2].query["url"], url);
+    assert!(client.get_subscriptions().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn bot_info_is_requested_once() {
+    let (client, max) = client().await;
+
+    let info = client.bot_info().await.unwrap();
+    assert_eq!(info.user_id, BOT_USER_ID);
+    assert_eq!(info.username.as_deref(), Some(BOT_USERNAME));
+    client.bot_info().await.unwrap();
+
+    assert_eq!(max.calls().iter().filter(|c| c.path == "/me").count(), 1);
+}
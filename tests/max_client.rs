@@ -0,0 +1,174 @@
+//! Клиент MAX Bot API: разбор ошибок платформы и повторы запросов.
+//! Повторы проверяются на локальном сервере, который отвечает заранее заданной последовательностью.
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::State,
+    http::{header::RETRY_AFTER, StatusCode as HttpStatus},
+    response::{IntoResponse, Response},
+    Router,
+};
+use reqwest::StatusCode;
+use serde_json::json;
+
+use max_app::integrations::max::{MaxApiClient, MaxApiError, MaxApiSettings};
+
+/// Ответы сервера по порядку; когда они заканчиваются — `{"success": true}`
+#[derive(Clone, Default)]
+struct Script {
+    responses: Arc<Mutex<VecDeque<Response>>>,
+    requests: Arc<Mutex<u32>>,
+}
+
+impl Script {
+    fn requests(&self) -> u32 {
+        *self.requests.lock().unwrap()
+    }
+}
+
+async fn scripted(State(script): State<Script>) -> Response {
+    *script.requests.lock().unwrap() += 1;
+    let next = script.responses.lock().unwrap().pop_front();
+    next.unwrap_or_else(|| axum::Json(json!({ "success": true })).into_response())
+}
+
+async fn start(responses: Vec<Response>, max_retries: u32) -> (MaxApiClient, Script) {
+    let script = Script { responses: Arc::new(Mutex::new(responses.into())), ..Script::default() };
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let app = Router::new().fallback(scripted).with_state(script.clone());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let client = MaxApiClient::with_settings(
+        "test-token".to_string(),
+        MaxApiSettings { api_base_url: url, max_retries, ..MaxApiSettings::default() },
+    );
+    (client, script)
+}
+
+fn unavailable() -> Response {
+    (HttpStatus::SERVICE_UNAVAILABLE, "upstream unavailable").into_response()
+}
+
+#[test]
+fn errors_are_classified_by_status_and_code() {
+    let body = r#"{"code":"verify.token","message":"Invalid access_token"}"#;
+    let error = MaxApiError::from_parts(StatusCode::UNAUTHORIZED, body, None);
+    assert!(matches!(error, MaxApiError::Unauthorized(ref m) if m == "Invalid access_token"));
+
+    let error = MaxApiError::from_parts(StatusCode::FORBIDDEN, r#"{"code":"access.denied"}"#, None);
+    assert!(matches!(error, MaxApiError::BlockedByUser(ref m) if m == "access.denied"));
+
+    // Код блокировки важнее статуса ответа
+    let body = r#"{"code":"chat.not.found","message":"Chat 1 not found"}"#;
+    let error = MaxApiError::from_parts(StatusCode::NOT_FOUND, body, None);
+    assert!(matches!(error, MaxApiError::BlockedByUser(ref m) if m == "Chat 1 not found"));
+
+    let error = MaxApiError::from_parts(StatusCode::TOO_MANY_REQUESTS, "", Some(Duration::from_secs(3)));
+    assert!(matches!(error, MaxApiError::RateLimited { retry_after: Some(d) } if d == Duration::from_secs(3)));
+
+    let error = MaxApiError::from_parts(StatusCode::BAD_GATEWAY, "<html>Bad Gateway</html>", None);
+    assert!(matches!(
+        error,
+        MaxApiError::Server { status: StatusCode::BAD_GATEWAY, ref message } if message == "<html>Bad Gateway</html>"
+    ));
+
+    let body = r#"{"code":"attachment.not.ready","message":"Key: errors.process.attachment.file.not.processed"}"#;
+    let error = MaxApiError::from_parts(StatusCode::BAD_REQUEST, body, None);
+    assert_eq!(error.code(), Some("attachment.not.ready"));
+    assert!(matches!(error, MaxApiError::BadRequest { status: StatusCode::BAD_REQUEST, .. }));
+    assert!(!error.is_invalid_attachment());
+    let error = MaxApiError::from_parts(StatusCode::BAD_REQUEST, r#"{"code":"attachment.invalid"}"#, None);
+    assert!(error.is_invalid_attachment());
+
+    // Тело не JSON — сообщением становится сам текст ответа
+    let error = MaxApiError::from_parts(StatusCode::BAD_REQUEST, "bad request", None);
+    assert!(matches!(error, MaxApiError::BadRequest { code: None, ref message, .. } if message == "bad request"));
+}
+
+#[test]
+fn only_temporary_errors_are_retried() {
+    assert!(MaxApiError::RateLimited { retry_after: None }.is_retryable());
+    assert!(MaxApiError::from_parts(StatusCode::INTERNAL_SERVER_ERROR, "", None).is_retryable());
+
+    assert!(!MaxApiError::from_parts(StatusCode::UNAUTHORIZED, "", None).is_retryable());
+    assert!(!MaxApiError::from_parts(StatusCode::FORBIDDEN, "", None).is_retryable());
+    assert!(!MaxApiError::from_parts(StatusCode::BAD_REQUEST, "", None).is_retryable());
+    assert!(!MaxApiError::InvalidResponse("eof".to_string()).is_retryable());
+    assert!(!MaxApiError::TooLarge { limit: 1 }.is_retryable());
+}
+
+#[tokio::test]
+async fn server_errors_are_retried_with_exponential_backoff() {
+    let (client, script) = start(vec![unavailable(), unavailable()], 3).await;
+    let started = Instant::now();
+
+    client.delete_message("mid.1").await.unwrap();
+
+    // Паузы 500 и 1000 мс плюс случайная добавка до четверти каждой
+    let elapsed = started.elapsed();
+    assert_eq!(script.requests(), 3);
+    assert!(elapsed >= Duration::from_millis(1500), "{:?}", elapsed);
+    assert!(elapsed < Duration::from_millis(2200), "{:?}", elapsed);
+}
+
+#[tokio::test]
+async fn retries_stop_after_max_retries() {
+    let (client, script) = start(vec![unavailable(), unavailable(), unavailable()], 1).await;
+
+    let error = client.delete_message("mid.1").await.unwrap_err();
+
+    assert!(matches!(error, MaxApiError::Server { status: StatusCode::SERVICE_UNAVAILABLE, .. }));
+    assert_eq!(script.requests(), 2);
+}
+
+#[tokio::test]
+async fn zero_retries_sends_single_request() {
+    let (client, script) = start(vec![unavailable()], 0).await;
+
+    assert!(client.delete_message("mid.1").await.is_err());
+    assert_eq!(script.requests(), 1);
+}
+
+#[tokio::test]
+async fn client_errors_are_not_retried() {
+    let rejected = (HttpStatus::BAD_REQUEST, axum::Json(json!({ "code": "proto.payload", "message": "bad payload" })));
+    let (client, script) = start(vec![rejected.into_response()], 3).await;
+
+    let error = client.delete_message("mid.1").await.unwrap_err();
+
+    assert_eq!(error.code(), Some("proto.payload"));
+    assert_eq!(script.requests(), 1);
+}
+
+#[tokio::test]
+async fn rate_limit_waits_for_retry_after() {
+    let limited = (HttpStatus::TOO_MANY_REQUESTS, [(RETRY_AFTER, "1")], "too many requests");
+    let (client, script) = start(vec![limited.into_response()], 3).await;
+    let started = Instant::now();
+
+    client.delete_message("mid.1").await.unwrap();
+
+    // Пауза из заголовка, а не 500 мс экспоненциальной задержки
+    assert_eq!(script.requests(), 2);
+    assert!(started.elapsed() >= Duration::from_secs(1), "{:?}", started.elapsed());
+}
+
+#[tokio::test]
+async fn unsuccessful_result_is_an_error() {
+    let failed = axum::Json(json!({ "success": false, "message": "message not found" }));
+    let (client, script) = start(vec![failed.into_response()], 3).await;
+
+    let error = client.delete_message("mid.1").await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        MaxApiError::BadRequest { status: StatusCode::OK, ref message, .. } if message == "message not found"
+    ));
+    assert_eq!(script.requests(), 1);
+}
@@ -0,0 +1,110 @@
+//! Разметка сообщений МАКС: экранирование и деление длинных сообщений.
+
+use max_app::{
+    bot::error_text,
+    error::AppError,
+    integrations::max::{
+        formatting::{self, escape, split, truncate},
+        Button, InlineKeyboard, MessageBuilder, SendMessageRequest, MAX_MESSAGE_LENGTH,
+    },
+};
+
+#[test]
+fn escapes_user_content() {
+    assert_eq!(escape(r#"<b>Tom & "Jerry"</b>"#), "&lt;b&gt;Tom &amp; &quot;Jerry&quot;&lt;/b&gt;");
+    assert_eq!(formatting::bold("a < b"), "<b>a &lt; b</b>");
+    assert_eq!(formatting::italic("x&y"), "<i>x&amp;y</i>");
+    assert_eq!(
+        formatting::link("Отчёт", "https://example.com/?a=1&b=2"),
+        r#"<a href="https://example.com/?a=1&amp;b=2">Отчёт</a>"#
+    );
+    assert_eq!(formatting::list(["один", "<два>"]), "• один\n• &lt;два&gt;");
+}
+
+#[test]
+fn bot_errors_are_escaped() {
+    let error = AppError::Conflict("Логин <b>admin</b> уже занят".to_string());
+    assert_eq!(error_text(&error), "Логин &lt;b&gt;admin&lt;/b&gt; уже занят");
+}
+
+#[test]
+fn builder_escapes_text_but_keeps_raw_markup() {
+    let html = MessageBuilder::new()
+        .raw("📄 ")
+        .bold("C++ & <Rust>")
+        .line()
+        .field("Автор", "O'Neil <admin>")
+        .text("1 < 2")
+        .build();
+
+    assert_eq!(html, "📄 <b>C++ &amp; &lt;Rust&gt;</b>\n<b>Автор:</b> O'Neil &lt;admin&gt;\n1 &lt; 2");
+}
+
+#[test]
+fn short_text_is_not_split() {
+    assert_eq!(split("<b>Привет</b>", 100), vec!["<b>Привет</b>"]);
+}
+
+#[test]
+fn splits_on_line_breaks_within_limit() {
+    let html = "первая строка\nвторая строка\nтретья строка";
+    let parts = split(html, 30);
+
+    assert_eq!(parts, vec!["первая строка\nвторая строка", "третья строка"]);
+    assert!(parts.iter().all(|p| p.chars().count() <= 30));
+}
+
+#[test]
+fn reopens_tags_cut_by_split() {
+    let html = format!("<b>{}</b> конец", "слово ".repeat(10).trim_end());
+    let parts = split(&html, 30);
+
+    assert!(parts.len() > 1);
+    for part in &parts {
+        assert!(part.chars().count() <= 30, "{}", part);
+        assert_eq!(part.matches("<b>").count(), part.matches("</b>").count(), "{}", part);
+    }
+    assert!(parts[1].starts_with("<b>"));
+    assert!(parts.last().unwrap().ends_with("конец"));
+}
+
+#[test]
+fn never_cuts_entities_or_tags() {
+    let html = format!("{}<a href=\"https://example.com\">ссылка</a>", "&amp;".repeat(20));
+    for part in split(&html, 25) {
+        assert!(!part.ends_with('&') && !part.contains("&am<"), "{}", part);
+        assert_eq!(part.matches('<').count(), part.matches('>').count(), "{}", part);
+    }
+}
+
+#[test]
+fn long_words_are_split_by_characters() {
+    let html = "я".repeat(25);
+    let parts = split(&html, 10);
+
+    assert_eq!(parts.iter().map(|p| p.chars().count()).collect::<Vec<_>>(), vec![10, 10, 5]);
+}
+
+#[test]
+fn truncates_with_ellipsis() {
+    assert_eq!(truncate("коротко", 10), "коротко");
+
+    let truncated = truncate("<i>очень длинный текст сообщения</i>", 20);
+    assert!(truncated.chars().count() <= 20, "{}", truncated);
+    assert!(truncated.starts_with("<i>") && truncated.ends_with("</i>…"), "{}", truncated);
+}
+
+#[test]
+fn long_message_keeps_keyboard_on_last_part() {
+    let text = "строка текста\n".repeat(MAX_MESSAGE_LENGTH / 10);
+    let message =
+        SendMessageRequest::html(text).with_keyboard(InlineKeyboard::new().row(vec![Button::callback("ok", "ok")]));
+
+    let parts = message.split();
+    assert_eq!(parts.len(), 2);
+    assert!(parts.iter().all(|p| p.text.chars().count() <= MAX_MESSAGE_LENGTH));
+    assert!(parts[0].attachments.is_empty());
+    assert_eq!(parts[1].attachments.len(), 1);
+
+    assert!(message.truncated().text.ends_with('…'));
+}
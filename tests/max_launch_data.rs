@@ -0,0 +1,152 @@
+//! Тестовые векторы проверки данных запуска мини-приложения МАКС.
+//!
+//! Подписи посчитаны независимо от приложения (Python `hmac`) по схеме из `launch_data`.
+
+use chrono::{Duration, TimeZone, Utc};
+use max_app::integrations::max::launch_data::{sign, validate, LaunchData, LaunchDataError};
+
+const BOT_TOKEN: &str = "123456:test-bot-token";
+
+/// «Текущее» время для всех векторов — 2025-10-09 08:53:20 UTC
+const NOW: i64 = 1_760_000_000;
+
+const USER: &str = "%7B%22id%22%3A400123%2C%22first_name%22%3A%22%D0%98%D0%B2%D0%B0%D0%BD%22%2C%22last_name%22%3A%22%D0%9F%D0%B5%D1%82%D1%80%D0%BE%D0%B2%22%2C%22username%22%3A%22ivan_p%22%2C%22language_code%22%3A%22ru%22%7D";
+const SHORT_USER: &str = "%7B%22id%22%3A400123%2C%22first_name%22%3A%22%D0%98%D0%B2%D0%B0%D0%BD%22%7D";
+
+const VALID_HASH: &str = "5bb3742c025046c3227a3100fc0c655e3330d7a17bbb0a5e52641ce5223ce75f";
+
+fn check(init_data: &str) -> Result<LaunchData, LaunchDataError> {
+    validate(init_data, BOT_TOKEN, Duration::hours(24), Utc.timestamp_opt(NOW, 0).unwrap())
+}
+
+fn valid_init_data() -> String {
+    format!(
+        "auth_date=1760000000&query_id=AAF9tW0BAAAAAH21bQGVZ8Ri&user={}&start_param=work-42&hash={}",
+        USER, VALID_HASH
+    )
+}
+
+#[test]
+fn sign_matches_reference_vector() {
+    let data_check_string = concat!(
+        "auth_date=1760000000\n",
+        "query_id=AAF9tW0BAAAAAH21bQGVZ8Ri\n",
+        "start_param=work-42\n",
+        r#"user={"id":400123,"first_name":"Иван","last_name":"Петров","username":"ivan_p","language_code":"ru"}"#,
+    );
+    assert_eq!(sign(data_check_string, BOT_TOKEN), VALID_HASH);
+}
+
+#[test]
+fn accepts_valid_launch_data() {
+    let launch = check(&valid_init_data()).expect("valid launch data");
+
+    assert_eq!(launch.auth_date, Utc.timestamp_opt(NOW, 0).unwrap());
+    assert_eq!(launch.query_id.as_deref(), Some("AAF9tW0BAAAAAH21bQGVZ8Ri"));
+    assert_eq!(launch.start_param.as_deref(), Some("work-42"));
+    assert_eq!(launch.user.id, 400123);
+    assert_eq!(launch.user.first_name, "Иван");
+    assert_eq!(launch.user.last_name.as_deref(), Some("Петров"));
+    assert_eq!(launch.user.username.as_deref(), Some("ivan_p"));
+    assert_eq!(launch.user.language_code.as_deref(), Some("ru"));
+}
+
+#[test]
+fn field_order_and_leading_question_mark_do_not_matter() {
+    let init_data = format!(
+        "?hash={}&user={}&start_param=work-42&query_id=AAF9tW0BAAAAAH21bQGVZ8Ri&auth_date=1760000000",
+        VALID_HASH, USER
+    );
+    assert!(check(&init_data).is_ok());
+}
+
+#[test]
+fn accepts_uppercase_hash() {
+    let init_data = valid_init_data().replace(VALID_HASH, &VALID_HASH.to_uppercase());
+    assert!(check(&init_data).is_ok());
+}
+
+#[test]
+fn rejects_tampered_field() {
+    let init_data = valid_init_data().replace("work-42", "work-43");
+    assert_eq!(check(&init_data), Err(LaunchDataError::InvalidSignature));
+}
+
+#[test]
+fn rejects_tampered_user() {
+    let init_data = valid_init_data().replace("400123", "400124");
+    assert_eq!(check(&init_data), Err(LaunchDataError::InvalidSignature));
+}
+
+#[test]
+fn rejects_wrong_bot_token() {
+    let result = validate(
+        &valid_init_data(),
+        "123456:other-bot-token",
+        Duration::hours(24),
+        Utc.timestamp_opt(NOW, 0).unwrap(),
+    );
+    assert_eq!(result, Err(LaunchDataError::InvalidSignature));
+}
+
+#[test]
+fn rejects_non_hex_hash() {
+    let init_data = valid_init_data().replace(VALID_HASH, "not-a-hash");
+    assert_eq!(check(&init_data), Err(LaunchDataError::InvalidSignature));
+}
+
+#[test]
+fn rejects_missing_hash() {
+    let init_data = valid_init_data().replace(&format!("&hash={}", VALID_HASH), "");
+    assert_eq!(check(&init_data), Err(LaunchDataError::MissingHash));
+}
+
+#[test]
+fn rejects_duplicate_keys() {
+    let init_data = format!("{}&start_param=work-42", valid_init_data());
+    assert_eq!(check(&init_data), Err(LaunchDataError::Malformed("duplicate key")));
+
+    let init_data = format!("{}&hash={}", valid_init_data(), VALID_HASH);
+    assert_eq!(check(&init_data), Err(LaunchDataError::Malformed("duplicate hash")));
+}
+
+#[test]
+fn rejects_expired_auth_date() {
+    // auth_date на 100 000 секунд раньше NOW — больше суток
+    let init_data = format!(
+        "auth_date=1759900000&user={}&hash=e1b387a83fef619a2989551f2fc487eaddbbaa38e5aef2c279dc23cfac8abb2d",
+        SHORT_USER
+    );
+    assert_eq!(check(&init_data), Err(LaunchDataError::Expired));
+
+    // С более длинным сроком те же данные принимаются
+    let result = validate(&init_data, BOT_TOKEN, Duration::days(2), Utc.timestamp_opt(NOW, 0).unwrap());
+    assert!(result.is_ok());
+}
+
+#[test]
+fn rejects_auth_date_in_future() {
+    let init_data = format!(
+        "auth_date=1760000600&user={}&hash=bd73fb1fd1319fd180a575389abb82e2edbabb1ffc6b46801f80f6dbaedb82ca",
+        SHORT_USER
+    );
+    assert_eq!(check(&init_data), Err(LaunchDataError::Expired));
+}
+
+#[test]
+fn tolerates_small_clock_skew() {
+    let init_data = format!(
+        "auth_date=1760000030&user={}&hash=10e3988867d8f66ead3cbdb6f2cf5c84fd75713fed5de8852fbf2f3a2bd4b4cf",
+        SHORT_USER
+    );
+    let launch = check(&init_data).expect("auth_date within clock skew");
+    assert_eq!(launch.user.last_name, None);
+    assert_eq!(launch.query_id, None);
+}
+
+#[test]
+fn rejects_missing_user() {
+    let init_data = "auth_date=1760000000&query_id=AAF9tW0BAAAAAH21bQGVZ8Ri\
+        &hash=0d8818fad246f4901398f96bc7a886e5dc757e38d5605a954d524314e9cf11c2";
+    assert_eq!(check(init_data), Err(LaunchDataError::MissingUser));
+}
@@ -0,0 +1,79 @@
+//! Модели MAX Bot API: inline-клавиатуры, ответы на нажатия и разбор обновлений с кнопок.
+
+use serde_json::json;
+
+use max_app::integrations::max::{
+    models::Update, Button, CallbackAnswer, InlineKeyboard, SendMessageRequest,
+};
+
+#[test]
+fn keyboard_is_sent_as_inline_keyboard_attachment() {
+    let message = SendMessageRequest::html("Результаты").with_keyboard(
+        InlineKeyboard::new()
+            .row(vec![Button::callback("📄 Подробнее", "work:1"), Button::link("Сайт", "https://example.com")])
+            .row(Vec::new())
+            .row(vec![Button::open_app("Открыть", "archive_bot")]),
+    );
+
+    assert_eq!(
+        serde_json::to_value(&message).unwrap(),
+        json!({
+            "format": "html",
+            "text": "Результаты",
+            "attachments": [{
+                "type": "inline_keyboard",
+                "payload": { "buttons": [
+                    [
+                        { "type": "callback", "text": "📄 Подробнее", "payload": "work:1" },
+                        { "type": "link", "text": "Сайт", "url": "https://example.com" },
+                    ],
+                    [{ "type": "open_app", "text": "Открыть", "web_app": "archive_bot" }],
+                ] },
+            }],
+        })
+    );
+
+    // Пустая клавиатура не добавляет вложение
+    let plain = SendMessageRequest::html("Текст").with_keyboard(InlineKeyboard::new());
+    assert_eq!(serde_json::to_value(&plain).unwrap(), json!({ "format": "html", "text": "Текст" }));
+}
+
+#[test]
+fn callback_answer_omits_missing_parts() {
+    let notification = CallbackAnswer { message: None, notification: Some("Готово".to_string()) };
+    assert_eq!(serde_json::to_value(&notification).unwrap(), json!({ "notification": "Готово" }));
+
+    let message = CallbackAnswer { message: Some(SendMessageRequest::html("Страница 2")), notification: None };
+    assert_eq!(
+        serde_json::to_value(&message).unwrap(),
+        json!({ "message": { "format": "html", "text": "Страница 2" } })
+    );
+}
+
+#[test]
+fn message_callback_update_is_parsed() {
+    let update: Update = serde_json::from_value(json!({
+        "update_type": "message_callback",
+        "timestamp": 1,
+        "callback": {
+            "timestamp": 1,
+            "callback_id": "cb-1",
+            "payload": "download:42",
+            "user": { "user_id": 7, "first_name": "Иван" },
+        },
+        "user_locale": "ru",
+    }))
+    .unwrap();
+
+    assert_eq!(update.kind(), "message_callback");
+    assert_eq!(update.user_locale(), Some("ru"));
+    let Update::MessageCallback { callback, message, .. } = update else { panic!("ожидалось нажатие кнопки") };
+    assert_eq!(callback.callback_id, "cb-1");
+    assert_eq!(callback.payload.as_deref(), Some("download:42"));
+    assert_eq!(callback.user.user_id, 7);
+    // Сообщение с кнопкой платформа может не прислать
+    assert!(message.is_none());
+
+    let unknown: Update = serde_json::from_value(json!({ "update_type": "message_edited", "timestamp": 1 })).unwrap();
+    assert_eq!(unknown.kind(), "unsupported");
+}
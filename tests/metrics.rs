@@ -0,0 +1,109 @@
+//! Метрики Prometheus на `/metrics` и защита эндпоинта.
+
+mod support;
+
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::Router;
+use reqwest::StatusCode;
+use tokio::net::TcpListener;
+
+use max_app::{
+    api::routes::{create_metrics_router, create_router},
+    infrastructure::metrics,
+    state::AppState,
+};
+use support::{bot_started, TestApp};
+
+async fn serve(router: Router) -> String {
+    let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))).await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    url
+}
+
+#[test]
+fn max_api_endpoints_drop_ids() {
+    assert_eq!(metrics::max_api_endpoint("/messages"), "/messages");
+    assert_eq!(metrics::max_api_endpoint("/chats/-5001/members"), "/chats/:id/members");
+    assert_eq!(metrics::max_api_endpoint("/chats/42/actions"), "/chats/:id/actions");
+}
+
+#[tokio::test]
+async fn metrics_cover_http_search_webhook_and_pool() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let http = reqwest::Client::new();
+
+    let response = http.get(format!("{}/api/works/search?query=нейросети", app.url)).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    http.get(format!("{}/no/such/page", app.url)).send().await.unwrap();
+    assert_eq!(app.send_update(bot_started(1001, 2001)).await, StatusCode::OK);
+    std::fs::create_dir_all(app.state.storage.root().join("works")).unwrap();
+    std::fs::write(app.state.storage.root().join("works/1.pdf"), [0u8; 1234]).unwrap();
+    // Размер хранилища считает фоновая задача — здесь пересчитываем сразу
+    metrics::record_storage_size(&app.state.storage).await;
+    // Адреса файлов уникальны — в метриках у них одна метка
+    let file_url = app.max.add_file("report-1.pdf", b"%PDF");
+    app.state.max_api.download(&file_url, 1024).await.unwrap();
+
+    let response = http.get(format!("{}/metrics", app.url)).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()["content-type"].to_str().unwrap().starts_with("text/plain"));
+    let body = response.text().await.unwrap();
+
+    for expected in [
+        r#"http_requests_total{method="GET",route="/api/works/search",status="200"}"#,
+        r#"http_requests_total{method="GET",route="unmatched",status="404"}"#,
+        r#"http_request_duration_seconds_bucket{method="GET",route="/api/works/search",le="0.005"}"#,
+        "search_query_duration_seconds_count",
+        r#"max_webhook_updates_total{update_type="bot_started"}"#,
+        r#"max_api_requests_total{endpoint="/messages",outcome="success"}"#,
+        r#"max_api_requests_total{endpoint="download",outcome="success"}"#,
+        r#"db_pool_connections{state="idle"}"#,
+        "db_pool_max_connections 5",
+    ] {
+        assert!(body.contains(expected), "нет {} в\n{}", expected, body);
+    }
+    assert!(!body.contains("/files/"), "{}", body);
+    // Рекордер общий для всех тестов процесса, поэтому сравниваем не точно
+    let storage_bytes: f64 = body
+        .lines()
+        .find_map(|line| line.strip_prefix("storage_bytes "))
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(storage_bytes >= 1234.0);
+
+    app.cleanup().await;
+}
+
+#[tokio::test]
+async fn metrics_can_require_token_or_separate_address() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let http = reqwest::Client::new();
+
+    let state = Arc::new(AppState { metrics_token: Some("scrape-secret".to_string().into()), ..(*app.state).clone() });
+    let url = serve(create_router(state)).await;
+
+    let response = http.get(format!("{}/metrics", url)).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = http.get(format!("{}/metrics", url)).bearer_auth("wrong").send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = http.get(format!("{}/metrics", url)).bearer_auth("scrape-secret").send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // С отдельным адресом основной сервер метрики не отдаёт
+    let state = Arc::new(AppState {
+        metrics_bind_address: Some(SocketAddr::from(([127, 0, 0, 1], 9100))),
+        ..(*app.state).clone()
+    });
+    let main_url = serve(create_router(state.clone())).await;
+    let metrics_url = serve(create_metrics_router(state)).await;
+
+    let response = http.get(format!("{}/metrics", main_url)).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = http.get(format!("{}/metrics", metrics_url)).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    app.cleanup().await;
+}
@@ -0,0 +1,68 @@
+//! Встроенные миграции: проверка схемы при запуске и `max_app-admin migrate`.
+
+mod support;
+
+use std::process::{Command, Output};
+
+use max_app::infrastructure::database::{self, SchemaError, MIGRATOR};
+use support::TestDatabase;
+
+/// `max_app-admin` с конфигурацией только из переменных окружения
+fn admin(db: &TestDatabase, args: &[&str]) -> Output {
+    let workdir = std::env::temp_dir().join(format!("{}_admin", db.name));
+    std::fs::create_dir_all(&workdir).unwrap();
+
+    Command::new(env!("CARGO_BIN_EXE_max_app-admin"))
+        .args(args)
+        .current_dir(&workdir)
+        .env("MAX_APP_PORT", "0")
+        .env("MAX_APP_DATABASE_URL", &db.url)
+        .env("MAX_APP_MAX_BOT_TOKEN", "test-bot-token")
+        .env("MAX_APP_JWT_SECRET", "test-jwt-secret")
+        .env("MAX_APP_MAX_WEBHOOK_SECRET", "test-webhook-secret")
+        .env("MAX_APP_ANALYTICS_SALT", "test-analytics-salt")
+        .env("MAX_APP_FILE_STORAGE_PATH", workdir.join("uploads"))
+        .output()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn outdated_schema_is_refused_unless_auto_migrate() {
+    let Some(db) = TestDatabase::create().await else { return };
+    let all: Vec<i64> = MIGRATOR.iter().map(|m| m.version).collect();
+
+    match database::ensure_schema(&db.pool, false).await {
+        Err(SchemaError::Outdated(pending)) => assert_eq!(pending, all),
+        other => panic!("ожидалась устаревшая схема, получено {:?}", other),
+    }
+
+    database::ensure_schema(&db.pool, true).await.unwrap();
+    assert!(database::pending_migrations(&db.pool).await.unwrap().is_empty());
+    database::ensure_schema(&db.pool, false).await.unwrap();
+
+    db.drop().await;
+}
+
+#[tokio::test]
+async fn admin_cli_reports_and_applies_migrations() {
+    let Some(db) = TestDatabase::create().await else { return };
+    let total = MIGRATOR.iter().count();
+
+    let output = admin(&db, &["migrate", "status"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.matches("не применена").count(), total);
+    assert!(stdout.contains(&format!("Не применено миграций: {}", total)));
+
+    let output = admin(&db, &["migrate", "up"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8(output.stdout).unwrap().contains(&format!("Применено миграций: {}", total)));
+
+    let stdout = String::from_utf8(admin(&db, &["migrate", "status"]).stdout).unwrap();
+    assert!(stdout.contains("Не применено миграций: 0"));
+    let stdout = String::from_utf8(admin(&db, &["migrate", "up"]).stdout).unwrap();
+    assert!(stdout.contains("новых миграций нет"));
+
+    let _ = std::fs::remove_dir_all(std::env::temp_dir().join(format!("{}_admin", db.name)));
+    db.drop().await;
+}
@@ -0,0 +1,80 @@
+//! Ограничитель частоты запросов к MAX Bot API: token bucket на реальном времени.
+
+use std::time::{Duration, Instant};
+
+use max_app::integrations::max::rate_limiter::RateLimiter;
+
+#[tokio::test]
+async fn full_bucket_allows_burst_without_waiting() {
+    let limiter = RateLimiter::new(10);
+    let started = Instant::now();
+
+    for _ in 0..10 {
+        limiter.acquire().await;
+    }
+
+    assert!(started.elapsed() < Duration::from_millis(50), "{:?}", started.elapsed());
+}
+
+#[tokio::test]
+async fn empty_bucket_refills_at_configured_rate() {
+    let limiter = RateLimiter::new(20);
+    for _ in 0..20 {
+        limiter.acquire().await;
+    }
+
+    // Ещё 5 запросов при 20 в секунду — не раньше чем через 250 мс
+    let started = Instant::now();
+    for _ in 0..5 {
+        limiter.acquire().await;
+    }
+
+    let elapsed = started.elapsed();
+    assert!(elapsed >= Duration::from_millis(240), "{:?}", elapsed);
+    assert!(elapsed < Duration::from_millis(500), "{:?}", elapsed);
+}
+
+#[tokio::test]
+async fn idle_time_does_not_overfill_bucket() {
+    let limiter = RateLimiter::new(20);
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    // За простой запас не превышает ёмкость: 21-й запрос ждёт пополнения
+    let started = Instant::now();
+    for _ in 0..21 {
+        limiter.acquire().await;
+    }
+
+    assert!(started.elapsed() >= Duration::from_millis(40), "{:?}", started.elapsed());
+}
+
+#[tokio::test]
+async fn zero_rate_is_treated_as_one_request_per_second() {
+    let limiter = RateLimiter::new(0);
+    let started = Instant::now();
+
+    limiter.acquire().await;
+    assert!(started.elapsed() < Duration::from_millis(50));
+    limiter.acquire().await;
+
+    assert!(started.elapsed() >= Duration::from_millis(900), "{:?}", started.elapsed());
+}
+
+#[tokio::test]
+async fn concurrent_callers_share_one_bucket() {
+    let limiter = std::sync::Arc::new(RateLimiter::new(10));
+    let started = Instant::now();
+
+    let tasks: Vec<_> = (0..15)
+        .map(|_| {
+            let limiter = limiter.clone();
+            tokio::spawn(async move { limiter.acquire().await })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap();
+    }
+
+    // 10 запросов из запаса и ещё 5 по 100 мс
+    assert!(started.elapsed() >= Duration::from_millis(450), "{:?}", started.elapsed());
+}
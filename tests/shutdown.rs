@@ -0,0 +1,55 @@
+//! Плавная остановка: начатые запросы дообслуживаются, фоновые задачи выходят по сигналу.
+
+mod support;
+
+use std::{net::SocketAddr, time::Duration};
+
+use axum::{routing::get, Router};
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+
+use max_app::{bot, infrastructure::shutdown};
+use support::TestApp;
+
+#[tokio::test]
+async fn server_finishes_in_flight_requests_and_stops_accepting() {
+    let app = Router::new().route(
+        "/slow",
+        get(|| async {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            "готово"
+        }),
+    );
+    let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))).await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let stop = CancellationToken::new();
+    let server = tokio::spawn(shutdown::serve(listener, app, None, stop.clone()));
+
+    let request = tokio::spawn(reqwest::get(format!("{}/slow", url)));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    stop.cancel();
+
+    let response = request.await.unwrap().unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    assert_eq!(response.text().await.unwrap(), "готово");
+
+    tokio::time::timeout(Duration::from_secs(2), server).await.unwrap().unwrap().unwrap();
+    assert!(reqwest::get(format!("{}/slow", url)).await.is_err());
+}
+
+#[tokio::test]
+async fn background_workers_exit_on_shutdown() {
+    let Some(app) = TestApp::spawn().await else { return };
+    let stop = CancellationToken::new();
+
+    let notifier = tokio::spawn(bot::notifier::notify_task(app.state.clone(), stop.clone()));
+    let purge = tokio::spawn(bot::dialogs::purge_expired_task(app.pool.clone(), app.state.storage.clone(), stop.clone()));
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(!notifier.is_finished() && !purge.is_finished());
+
+    stop.cancel();
+    tokio::time::timeout(Duration::from_secs(2), notifier).await.unwrap().unwrap();
+    tokio::time::timeout(Duration::from_secs(2), purge).await.unwrap().unwrap();
+
+    app.cleanup().await;
+}
@@ -0,0 +1,285 @@
+//! Мок MAX Bot API: записывает все запросы бота и отвечает как платформа.
+
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{Method, StatusCode, Uri},
+    response::{IntoResponse, Response},
+    Json, Router,
+};
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+
+/// Бот, от имени которого работает приложение (`GET /me`)
+pub const BOT_USER_ID: i64 = 1;
+pub const BOT_USERNAME: &str = "archive_bot";
+
+/// Запрос, который бот отправил на платформу
+#[derive(Debug, Clone)]
+pub struct RecordedCall {
+    pub method: Method,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    /// JSON-тело запроса (`Null` для пустых и multipart-запросов)
+    pub body: Value,
+    /// Платформа ответила ошибкой на отозванный токен вложения
+    pub rejected: bool,
+}
+
+/// Сообщение, отправленное ботом через `POST /messages`
+#[derive(Debug, Clone)]
+pub struct SentMessage {
+    pub chat_id: Option<i64>,
+    pub user_id: Option<i64>,
+    pub text: String,
+    pub body: Value,
+}
+
+impl SentMessage {
+    /// Токены файлов во вложениях сообщения
+    pub fn attachment_tokens(&self) -> Vec<String> {
+        attachment_tokens(&self.body)
+    }
+
+    /// Payload всех callback-кнопок сообщения
+    pub fn callback_payloads(&self) -> Vec<String> {
+        self.body["attachments"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|a| a["type"] == "inline_keyboard")
+            .flat_map(|a| a["payload"]["buttons"].as_array().cloned().unwrap_or_default())
+            .flat_map(|row| row.as_array().cloned().unwrap_or_default())
+            .filter_map(|button| button["payload"].as_str().map(str::to_string))
+            .collect()
+    }
+}
+
+#[derive(Default)]
+struct Inner {
+    calls: Vec<RecordedCall>,
+    files: HashMap<String, Vec<u8>>,
+    /// Ответы с ошибкой для сообщений в заданные чаты
+    failing_chats: HashMap<i64, (StatusCode, Value)>,
+    /// Токены загруженных файлов, которые платформа больше не принимает
+    revoked_tokens: Vec<String>,
+    uploads: u64,
+    messages: u64,
+    /// Подписки на вебхук, как их вернёт `GET /subscriptions`
+    subscriptions: Vec<Value>,
+    /// Меню команд, заданное через `PATCH /me`
+    commands: Option<Value>,
+    /// Администраторы групповых чатов: (chat_id, user_id)
+    admins: Vec<(i64, i64)>,
+}
+
+#[derive(Clone)]
+pub struct MockMax {
+    url: String,
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl MockMax {
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))).await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let mock = Self { url, inner: Arc::new(Mutex::new(Inner::default())) };
+
+        let app = Router::new().fallback(handle).with_state(mock.clone());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        mock
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Файл, доступный по временной ссылке — как вложение входящего сообщения
+    pub fn add_file(&self, name: &str, data: &[u8]) -> String {
+        self.inner.lock().unwrap().files.insert(name.to_string(), data.to_vec());
+        format!("{}/files/{}", self.url, name)
+    }
+
+    /// Истечение временной ссылки на файл
+    pub fn remove_file(&self, name: &str) {
+        self.inner.lock().unwrap().files.remove(name);
+    }
+
+    /// Все следующие сообщения в чат будут отклоняться с указанной ошибкой
+    pub fn fail_chat(&self, chat_id: i64, status: StatusCode, body: Value) {
+        self.inner.lock().unwrap().failing_chats.insert(chat_id, (status, body));
+    }
+
+    /// Платформа перестаёт принимать токен загруженного файла
+    pub fn revoke_token(&self, token: &str) {
+        self.inner.lock().unwrap().revoked_tokens.push(token.to_string());
+    }
+
+    /// Подписка на вебхук, которая уже есть на платформе
+    pub fn add_subscription(&self, url: &str, update_types: &[&str]) {
+        self.inner.lock().unwrap().subscriptions.push(json!({ "url": url, "time": 0, "update_types": update_types }));
+    }
+
+    /// Пользователь становится администратором группового чата
+    pub fn add_chat_admin(&self, chat_id: i64, user_id: i64) {
+        self.inner.lock().unwrap().admins.push((chat_id, user_id));
+    }
+
+    pub fn subscriptions(&self) -> Vec<Value> {
+        self.inner.lock().unwrap().subscriptions.clone()
+    }
+
+    pub fn calls(&self) -> Vec<RecordedCall> {
+        self.inner.lock().unwrap().calls.clone()
+    }
+
+    /// Успешно отправленные сообщения в порядке отправки
+    pub fn messages(&self) -> Vec<SentMessage> {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .calls
+            .iter()
+            .filter(|c| c.method == Method::POST && c.path == "/messages")
+            .filter(|c| {
+                let chat_id = c.query.get("chat_id").and_then(|v| v.parse().ok());
+                !chat_id.is_some_and(|id| inner.failing_chats.contains_key(&id))
+            })
+            .filter(|c| !c.rejected)
+            .map(|c| SentMessage {
+                chat_id: c.query.get("chat_id").and_then(|v| v.parse().ok()),
+                user_id: c.query.get("user_id").and_then(|v| v.parse().ok()),
+                text: c.body["text"].as_str().unwrap_or_default().to_string(),
+                body: c.body.clone(),
+            })
+            .collect()
+    }
+
+    /// Тела ответов на нажатия кнопок с их `callback_id`
+    pub fn callback_answers(&self) -> Vec<(String, Value)> {
+        self.calls()
+            .into_iter()
+            .filter(|c| c.path == "/answers")
+            .map(|c| (c.query.get("callback_id").cloned().unwrap_or_default(), c.body))
+            .collect()
+    }
+
+    pub fn clear(&self) {
+        self.inner.lock().unwrap().calls.clear();
+    }
+}
+
+async fn handle(State(mock): State<MockMax>, method: Method, uri: Uri, body: Bytes) -> Response {
+    let path = uri.path().to_string();
+    let query: HashMap<String, String> = form_urlencoded::parse(uri.query().unwrap_or_default().as_bytes())
+        .into_owned()
+        .collect();
+    let body = serde_json::from_slice(&body).unwrap_or(Value::Null);
+
+    let mut inner = mock.inner.lock().unwrap();
+    inner.calls.push(RecordedCall {
+        method: method.clone(),
+        path: path.clone(),
+        query: query.clone(),
+        body: body.clone(),
+        rejected: false,
+    });
+
+    match (method.clone(), path.as_str()) {
+        (Method::POST, "/messages") => {
+            let chat_id = query.get("chat_id").and_then(|v| v.parse::<i64>().ok());
+            if let Some((status, error)) = chat_id.and_then(|id| inner.failing_chats.get(&id)) {
+                return (*status, Json(error.clone())).into_response();
+            }
+            if attachment_tokens(&body).iter().any(|t| inner.revoked_tokens.contains(t)) {
+                if let Some(call) = inner.calls.last_mut() {
+                    call.rejected = true;
+                }
+                let error = json!({ "code": "attachment.invalid", "message": "Invalid attachment token" });
+                return (StatusCode::BAD_REQUEST, Json(error)).into_response();
+            }
+            inner.messages += 1;
+            Json(json!({ "message": { "body": { "mid": format!("mid.{}", inner.messages), "seq": inner.messages } } }))
+                .into_response()
+        }
+        (Method::POST, "/answers")
+        | (Method::PUT, "/messages")
+        | (Method::DELETE, "/messages") => Json(json!({ "success": true })).into_response(),
+        (Method::POST, "/subscriptions") => {
+            // Повторная подписка на тот же адрес заменяет прежнюю (секрет в `GET /subscriptions` не виден)
+            let subscription = json!({ "url": body["url"], "time": 0, "update_types": body["update_types"] });
+            inner.subscriptions.retain(|s| s["url"] != body["url"]);
+            inner.subscriptions.push(subscription);
+            Json(json!({ "success": true })).into_response()
+        }
+        (Method::DELETE, "/subscriptions") => {
+            let url = query.get("url").cloned().unwrap_or_default();
+            inner.subscriptions.retain(|s| s["url"] != url.as_str());
+            Json(json!({ "success": true })).into_response()
+        }
+        (Method::POST, "/uploads") => {
+            inner.uploads += 1;
+            Json(json!({ "url": format!("{}/upload/{}", mock.url, inner.uploads) })).into_response()
+        }
+        (Method::POST, p) if p.starts_with("/upload/") => {
+            Json(json!({ "token": format!("token-{}", &p["/upload/".len()..]) })).into_response()
+        }
+        (Method::GET, p) if p.starts_with("/files/") => match inner.files.get(&p["/files/".len()..]) {
+            Some(data) => data.clone().into_response(),
+            None => StatusCode::NOT_FOUND.into_response(),
+        },
+        (Method::GET, "/me") | (Method::PATCH, "/me") => {
+            if method == Method::PATCH {
+                inner.commands = Some(body["commands"].clone());
+            }
+            Json(json!({
+                "user_id": BOT_USER_ID,
+                "first_name": "Архив ЮТК",
+                "username": BOT_USERNAME,
+                "is_bot": true,
+                "commands": inner.commands,
+            }))
+            .into_response()
+        }
+        (Method::GET, p) if p.starts_with("/chats/") && p.ends_with("/members") => {
+            let chat_id: i64 = p["/chats/".len()..p.len() - "/members".len()].parse().unwrap_or_default();
+            let members: Vec<Value> = query
+                .get("user_ids")
+                .map(String::as_str)
+                .unwrap_or_default()
+                .split(',')
+                .filter_map(|id| id.parse::<i64>().ok())
+                .map(|user_id| {
+                    let is_admin = inner.admins.contains(&(chat_id, user_id));
+                    json!({ "user_id": user_id, "first_name": "Иван", "is_admin": is_admin, "is_owner": false })
+                })
+                .collect();
+            Json(json!({ "members": members })).into_response()
+        }
+        (Method::POST, p) if p.starts_with("/chats/") && p.ends_with("/actions") => {
+            Json(json!({ "success": true })).into_response()
+        }
+        (Method::GET, p) if p.starts_with("/chats/") => match p["/chats/".len()..].parse::<i64>() {
+            Ok(chat_id) => Json(json!({ "chat_id": chat_id, "type": "chat", "status": "active", "title": "Группа ИС-31" }))
+                .into_response(),
+            Err(_) => StatusCode::NOT_FOUND.into_response(),
+        },
+        (Method::GET, "/subscriptions") => Json(json!({ "subscriptions": inner.subscriptions })).into_response(),
+        _ => (StatusCode::NOT_FOUND, Json(json!({ "code": "not.found", "message": "Unknown method" }))).into_response(),
+    }
+}
+
+fn attachment_tokens(body: &Value) -> Vec<String> {
+    body["attachments"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|a| a["payload"]["token"].as_str().map(str::to_string))
+        .collect()
+}
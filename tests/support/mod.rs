@@ -0,0 +1,254 @@
+//! Окружение для интеграционных тестов: приложение из `create_router`, отдельная база Postgres
+//! и мок MAX Bot API.
+//!
+//! Базы создаются на сервере из `TEST_DATABASE_URL` (например,
+//! `postgres://postgres@localhost:5432/postgres`). Без этой переменной тесты пропускаются.
+//! Полнотекстовому поиску нужна локаль с кириллицей — по умолчанию `C.UTF-8`,
+//! другую можно задать в `TEST_DATABASE_LOCALE`.
+
+// Модуль подключается в несколько тестовых крейтов, каждый использует только часть помощников
+#![allow(dead_code)]
+
+pub mod mock_max;
+
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use sqlx::{
+    postgres::{PgConnectOptions, PgPoolOptions},
+    Connection, Executor, PgConnection, PgPool,
+};
+use tokio::net::TcpListener;
+use uuid::Uuid;
+
+use max_app::{
+    api::{handlers::max_webhook::SECRET_HEADER, routes::create_router},
+    core::{
+        models::{User, UserRole},
+        services::AuthService,
+    },
+    infrastructure::{database, metrics, storage::FileStorage},
+    integrations::max::{launch_data, MaxApiClient, MaxApiSettings},
+    state::AppState,
+};
+
+pub use mock_max::MockMax;
+
+pub const BOT_TOKEN: &str = "test-bot-token";
+/// Секрет подписки на вебхук, с которым платформа присылает обновления
+pub const WEBHOOK_SECRET: &str = "test-webhook-secret";
+
+pub struct TestApp {
+    pub url: String,
+    pub state: Arc<AppState>,
+    pub pool: PgPool,
+    pub max: MockMax,
+    db: TestDatabase,
+    storage_root: PathBuf,
+    http: reqwest::Client,
+}
+
+impl TestApp {
+    /// Запуск приложения на чистой базе; `None`, если `TEST_DATABASE_URL` не задан
+    pub async fn spawn() -> Option<Self> {
+        let db = TestDatabase::create().await?;
+        let pool = db.pool.clone();
+        database::MIGRATOR.run(&pool).await.unwrap();
+
+        let max = MockMax::start().await;
+        let storage_root = std::env::temp_dir().join(&db.name);
+
+        let state = Arc::new(AppState {
+            pool: pool.clone(),
+            max_bot_token: BOT_TOKEN.to_string().into(),
+            max_api: MaxApiClient::with_settings(
+                BOT_TOKEN.to_string(),
+                MaxApiSettings { api_base_url: max.url().to_string(), max_retries: 0, ..MaxApiSettings::default() },
+            ),
+            max_webhook_secret: WEBHOOK_SECRET.to_string().into(),
+            jwt_secret: "test-jwt-secret".to_string().into(),
+            max_mini_app: None,
+            bot_dialog_ttl_secs: 900,
+            storage: FileStorage::new(&storage_root),
+            max_upload_size_mb: 50,
+            http_body_limit: 2 * 1024 * 1024,
+            max_launch_data_ttl_secs: 24 * 60 * 60,
+            bot_analytics_enabled: true,
+            analytics_salt: "test-analytics-salt".to_string().into(),
+            health_check_max_api: false,
+            metrics: Some(metrics::install()),
+            metrics_token: None,
+            metrics_bind_address: None,
+        });
+
+        let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))).await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let app = create_router(state.clone());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        Some(Self { url, state, pool, max, db, storage_root, http: reqwest::Client::new() })
+    }
+
+    /// Доставка обновления на вебхук, как это делает платформа
+    pub async fn send_update(&self, update: Value) -> StatusCode {
+        self.http
+            .post(format!("{}/api/max/webhook", self.url))
+            .header(SECRET_HEADER, WEBHOOK_SECRET)
+            .json(&update)
+            .send()
+            .await
+            .unwrap()
+            .status()
+    }
+
+    /// Текстовое сообщение пользователя боту
+    pub async fn send_text(&self, chat_id: i64, user_id: i64, text: &str) -> StatusCode {
+        self.send_update(message_created(chat_id, user_id, json!({ "mid": "mid.in", "seq": 1, "text": text })))
+            .await
+    }
+
+    /// Пользователь архива с указанной ролью и сессионный токен для него
+    pub async fn login_as(&self, role: UserRole) -> String {
+        let user: User = sqlx::query_as(
+            "INSERT INTO users (username, password_hash, role) VALUES ($1, '-', $2::user_role) RETURNING *",
+        )
+        .bind(format!("{:?}-{}", role, Uuid::new_v4().simple()))
+        .bind(format!("{:?}", role).to_lowercase())
+        .fetch_one(&self.pool)
+        .await
+        .unwrap();
+
+        AuthService::new(self.pool.clone(), self.state.jwt_secret.clone()).issue_token(&user).unwrap()
+    }
+
+    /// Вход из мини-приложения с подписанными данными запуска; возвращает ответ `/api/auth/max`
+    pub async fn login_max(&self, max_user_id: i64) -> Value {
+        let auth_date = chrono::Utc::now().timestamp().to_string();
+        let user = json!({ "id": max_user_id, "first_name": "Иван" }).to_string();
+        let hash = launch_data::sign(&format!("auth_date={}\nuser={}", auth_date, user), BOT_TOKEN);
+
+        let mut url = reqwest::Url::parse("http://init.data/").unwrap();
+        url.query_pairs_mut().append_pair("auth_date", &auth_date).append_pair("user", &user).append_pair("hash", &hash);
+        let response = self
+            .http
+            .post(format!("{}/api/auth/max", self.url))
+            .json(&json!({ "init_data": url.query().unwrap() }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        response.json().await.unwrap()
+    }
+
+    /// Адрес тестовой базы для дочерних процессов
+    pub fn database_url(&self) -> &str {
+        &self.db.url
+    }
+
+    /// Удаление тестовой базы и файлов
+    pub async fn cleanup(self) {
+        let _ = tokio::fs::remove_dir_all(&self.storage_root).await;
+        self.db.drop().await;
+    }
+}
+
+/// Пустая база без миграций на сервере из `TEST_DATABASE_URL`
+pub struct TestDatabase {
+    pub name: String,
+    /// Адрес базы для дочерних процессов (например, `max_app-admin`)
+    pub url: String,
+    pub pool: PgPool,
+    admin_url: String,
+}
+
+impl TestDatabase {
+    /// `None`, если `TEST_DATABASE_URL` не задан
+    pub async fn create() -> Option<Self> {
+        let Ok(admin_url) = std::env::var("TEST_DATABASE_URL") else {
+            eprintln!("TEST_DATABASE_URL не задан — интеграционный тест пропущен");
+            return None;
+        };
+
+        let name = format!("max_app_test_{}", Uuid::new_v4().simple());
+        let mut admin = PgConnection::connect(&admin_url).await.expect("Нет подключения к TEST_DATABASE_URL");
+        let locale = std::env::var("TEST_DATABASE_LOCALE").unwrap_or_else(|_| "C.UTF-8".to_string());
+        admin
+            .execute(
+                format!(
+                    r#"CREATE DATABASE "{}" TEMPLATE template0 ENCODING 'UTF8' LC_COLLATE '{}' LC_CTYPE '{}'"#,
+                    name, locale, locale
+                )
+                .as_str(),
+            )
+            .await
+            .unwrap();
+        admin.close().await.unwrap();
+
+        let mut url = reqwest::Url::parse(&admin_url).expect("TEST_DATABASE_URL — не URL");
+        url.set_path(&name);
+        let options = admin_url.parse::<PgConnectOptions>().unwrap().database(&name);
+        let pool = PgPoolOptions::new().max_connections(5).connect_with(options).await.unwrap();
+
+        Some(Self { name, url: url.to_string(), pool, admin_url })
+    }
+
+    pub async fn drop(self) {
+        self.pool.close().await;
+
+        let mut admin = PgConnection::connect(&self.admin_url).await.unwrap();
+        admin
+            .execute(format!(r#"DROP DATABASE IF EXISTS "{}" WITH (FORCE)"#, self.name).as_str())
+            .await
+            .unwrap();
+    }
+}
+
+pub fn sender(user_id: i64) -> Value {
+    json!({ "user_id": user_id, "first_name": "Иван", "last_name": "Петров", "is_bot": false, "last_activity_time": 0 })
+}
+
+fn message(chat_id: i64, user_id: i64, body: Value) -> Value {
+    json!({
+        "recipient": { "chat_id": chat_id, "chat_type": "dialog", "user_id": user_id },
+        "timestamp": 0,
+        "body": body,
+        "sender": sender(user_id),
+    })
+}
+
+/// Сообщение участника группового чата
+pub fn group_message_created(chat_id: i64, user_id: i64, body: Value) -> Value {
+    let message = json!({
+        "recipient": { "chat_id": chat_id, "chat_type": "chat" },
+        "timestamp": 0,
+        "body": body,
+        "sender": sender(user_id),
+    });
+    json!({ "update_type": "message_created", "timestamp": 0, "message": message })
+}
+
+/// Обновление `message_created` с произвольным телом сообщения
+pub fn message_created(chat_id: i64, user_id: i64, body: Value) -> Value {
+    json!({ "update_type": "message_created", "timestamp": 0, "message": message(chat_id, user_id, body) })
+}
+
+/// Нажатие callback-кнопки под сообщением бота
+pub fn message_callback(chat_id: i64, user_id: i64, callback_id: &str, payload: &str) -> Value {
+    json!({
+        "update_type": "message_callback",
+        "timestamp": 0,
+        "callback": { "timestamp": 0, "callback_id": callback_id, "payload": payload, "user": sender(user_id) },
+        "message": message(chat_id, 1, json!({ "mid": "mid.bot", "seq": 1, "text": "" })),
+    })
+}
+
+pub fn bot_started(chat_id: i64, user_id: i64) -> Value {
+    json!({ "update_type": "bot_started", "timestamp": 0, "chat_id": chat_id, "user": sender(user_id) })
+}
+
+/// Обновление от пользователя с заданным языком интерфейса МАКС
+pub fn with_locale(mut update: Value, locale: &str) -> Value {
+    update["user_locale"] = json!(locale);
+    update
+}